@@ -1,47 +1,107 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[path = "build/discover.rs"]
+mod discover;
+#[path = "build/link.rs"]
+mod link;
+
+use link::Target;
 
 fn main() {
   println!("cargo:rerun-if-changed=wrapper.h");
+  println!("cargo:rerun-if-changed=src/ffi/generated");
+  println!("cargo:rustc-check-cfg=cfg(visa_linked)");
+  for var in discover::ENV_VARS {
+    println!("cargo:rerun-if-env-changed={}", var);
+  }
+
+  let target = Target::from_env();
+
+  // With runtime loading the functions are resolved by `ffi::dynamic`, so
+  // nothing is linked.
+  let dynamic_loading = env::var_os("CARGO_FEATURE_DYNAMIC_LOADING").is_some();
 
-  #[cfg(target_os = "macos")]
-    // Tell the linker to use the VISA framework on macOS
-    println!("cargo:rustc-link-arg=/Library/Frameworks/VISA.framework/Versions/Current/VISA");
-  #[cfg(target_os = "windows")]
-  {
-    // TODO: Not tested on Windows
-    // Link the VISA library on Windows
-    println!("cargo:rustc-link-search=C:\\Program Files (x86)\\IVI Foundation\\VISA\\WinNT\\Bin");
-    println!("cargo:rustc-link-lib=dylib=visa32");
+  if !dynamic_loading && link_visa(&target) {
+    // Lets the tests that talk to a real instrument know they can be built.
+    println!("cargo:rustc-cfg=visa_linked");
   }
-  #[cfg(target_os = "windows")]
-  {
-    // TODO: Not tested on Linux
-    // Link the VISA library on Linux
-    println!("cargo:rustc-link-search=/usr/lib");
-    println!("cargo:rustc-link-lib=dylib=visa");
+
+  let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+  #[cfg(feature = "bindgen")]
+  generate_bindings(&target, &out_path);
+
+  #[cfg(not(feature = "bindgen"))]
+  copy_pregenerated_bindings(&target, &out_path);
+}
+
+/// Copies the bindings committed under `src/ffi/generated` for the target's
+/// pointer width, so neither `visa.h` nor libclang is needed to build the crate.
+#[cfg_attr(feature = "bindgen", allow(dead_code))]
+fn copy_pregenerated_bindings(target: &Target, out_path: &Path) {
+  let generated = Path::new("src/ffi/generated");
+
+  for part in ["types", "functions"] {
+    let source = generated.join(format!("{}_{}.rs", part, target.pointer_width));
+    fs::copy(&source, out_path.join(format!("{}.rs", part)))
+      .unwrap_or_else(|e| panic!("No pre-generated bindings at {}: {}", source.display(), e));
   }
+}
 
-  #[cfg(target_os = "macos")]
-  const NI_VISA_INCLUDE_PATH: &str = "/Library/Frameworks/VISA.framework/Headers";
+/// Runs bindgen against the installed `visa.h`. Types and constants go to
+/// `types.rs`, function declarations to `functions.rs`, matching the layout
+/// of the pre-generated files.
+#[cfg(feature = "bindgen")]
+fn generate_bindings(target: &Target, out_path: &Path) {
+  use bindgen::CodegenConfig;
 
-  #[cfg(target_os = "linux")]
-  pub const NI_VISA_INCLUDE_PATH: &str = "/usr/include/";
+  let include_dir = discover::include_dir(target).unwrap_or_else(|e| panic!("{}", e));
 
-  #[cfg(target_os = "windows")]
-  const NI_VISA_INCLUDE_PATH: &str = "C:\\Program Files (x86)\\IVI Foundation\\VISA\\WinNT\\Include";
+  let parts = [
+    ("types.rs", CodegenConfig::TYPES | CodegenConfig::VARS),
+    ("functions.rs", CodegenConfig::FUNCTIONS),
+  ];
 
-  // Generate bindings
-  let bindings = bindgen::Builder::default()
-    .header("wrapper.h") // Point to visa.h
-    .clang_arg(format!("-I{}", NI_VISA_INCLUDE_PATH))
-    .clang_arg("-v")
-    .generate()
-    .expect("Unable to generate bindings");
+  for (file, config) in parts {
+    let bindings = bindgen::Builder::default()
+      .header("wrapper.h") // Point to visa.h
+      .clang_arg(format!("-I{}", include_dir.display()))
+      .allowlist_function("vi.*")
+      .allowlist_type("Vi.*")
+      .allowlist_var("_?VI_.*")
+      .with_codegen_config(config)
+      .generate()
+      .expect("Unable to generate bindings");
 
-  // Write bindings to the output directory
-  let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
-  bindings
-    .write_to_file(out_path.join("visa_bindings.rs"))
-    .expect("Couldn't write bindings!");
+    // Write bindings to the output directory
+    bindings
+      .write_to_file(out_path.join(file))
+      .expect("Couldn't write bindings!");
+  }
+}
+
+/// Emits the link directives for the VISA library, if one can be found.
+/// Returns whether anything was linked.
+///
+/// A missing library is only a warning, so the crate itself still builds
+/// (e.g. for `cargo check` or docs); binaries calling into VISA then fail to
+/// link. It is an error when the location was given explicitly.
+fn link_visa(target: &Target) -> bool {
+  let library = match discover::library(target) {
+    Ok(library) => library,
+    Err(e) if e.explicit => panic!("{}", e),
+    Err(e) => {
+      for line in e.to_string().lines() {
+        println!("cargo:warning={}", line);
+      }
+      return false;
+    }
+  };
+
+  for directive in link::directives(&library) {
+    println!("{}", directive);
+  }
+  true
 }
@@ -0,0 +1,222 @@
+//! Locates the VISA header and library for the build script.
+//!
+//! Every lookup goes through the same sources, in order:
+//!
+//! 1. the `VISA_INCLUDE_DIR`, `VISA_LIB_DIR` and `VISA_LIB_NAME` environment
+//!    overrides,
+//! 2. pkg-config (`visa.pc`, shipped by NI-VISA on Linux),
+//! 3. the VXIplug&play directories under `VXIPNPPATH`/`VXIPNPPATH64`,
+//! 4. the default install locations of NI-VISA, Keysight IO Libraries and
+//!    R&S VISA.
+//!
+//! When nothing is found the error lists every path that was tried.
+
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::link::{default_lib_names, lib_file_names, Target, VisaLibrary};
+
+pub const INCLUDE_DIR_ENV: &str = "VISA_INCLUDE_DIR";
+pub const LIB_DIR_ENV: &str = "VISA_LIB_DIR";
+pub const LIB_NAME_ENV: &str = "VISA_LIB_NAME";
+
+/// Every environment variable the lookup depends on.
+pub const ENV_VARS: [&str; 5] = [INCLUDE_DIR_ENV, LIB_DIR_ENV, LIB_NAME_ENV, "VXIPNPPATH", "VXIPNPPATH64"];
+
+/// Nothing usable was found.
+#[derive(Debug)]
+pub struct NotFound {
+    what: &'static str,
+    tried: Vec<PathBuf>,
+    overrides: &'static str,
+    /// Whether the search was restricted by an environment override. A miss
+    /// is then a configuration mistake rather than a missing installation.
+    pub explicit: bool,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Could not find the {}. Paths tried:", self.what)?;
+        for path in &self.tried {
+            writeln!(f, "  {}", path.display())?;
+        }
+        write!(f, "Set {} to point at your VISA installation.", self.overrides)
+    }
+}
+
+/// Finds the directory containing `visa.h`.
+#[cfg_attr(not(feature = "bindgen"), allow(dead_code))]
+pub fn include_dir(target: &Target) -> Result<PathBuf, NotFound> {
+    let explicit = env::var_os(INCLUDE_DIR_ENV).map(PathBuf::from);
+    let candidates = match &explicit {
+        Some(dir) => vec![dir.clone()],
+        None => {
+            let mut candidates = pkg_config().map(|lib| lib.include_paths).unwrap_or_default();
+            candidates.extend(vxipnp_dirs(target).iter().flat_map(|dir| vxipnp_include_dirs(target, dir)));
+            candidates.extend(default_include_dirs(target));
+            candidates
+        }
+    };
+
+    match candidates.iter().find(|dir| dir.join("visa.h").is_file()) {
+        Some(dir) => Ok(dir.clone()),
+        None => Err(NotFound {
+            what: "VISA header (visa.h)",
+            tried: candidates,
+            overrides: INCLUDE_DIR_ENV,
+            explicit: explicit.is_some(),
+        }),
+    }
+}
+
+/// Finds the VISA library to link against.
+pub fn library(target: &Target) -> Result<VisaLibrary, NotFound> {
+    let explicit_dir = env::var_os(LIB_DIR_ENV).map(PathBuf::from);
+    let explicit_name = env::var(LIB_NAME_ENV).ok();
+    let explicit = explicit_dir.is_some() || explicit_name.is_some();
+
+    let names = match &explicit_name {
+        Some(name) => vec![name.clone()],
+        None => default_lib_names(target),
+    };
+
+    let mut tried = Vec::new();
+
+    // Framework binaries are linked by path and only make sense without overrides.
+    if !explicit {
+        for file in default_lib_files(target) {
+            if file.is_file() {
+                return Ok(VisaLibrary::Path(file));
+            }
+            tried.push(file);
+        }
+    }
+
+    let dirs = match explicit_dir {
+        Some(dir) => vec![dir],
+        None => {
+            let mut dirs = pkg_config().map(|lib| lib.link_paths).unwrap_or_default();
+            dirs.extend(vxipnp_dirs(target).iter().flat_map(|dir| vxipnp_lib_dirs(target, dir)));
+            dirs.extend(default_lib_dirs(target));
+            dirs
+        }
+    };
+
+    for dir in dirs {
+        for name in &names {
+            for file in lib_file_names(target, name) {
+                let path = dir.join(file);
+                if path.is_file() {
+                    return Ok(VisaLibrary::Search { dir, name: name.clone() });
+                }
+                tried.push(path);
+            }
+        }
+    }
+
+    Err(NotFound {
+        what: "VISA library",
+        tried,
+        overrides: "VISA_LIB_DIR and/or VISA_LIB_NAME",
+        explicit,
+    })
+}
+
+fn pkg_config() -> Option<pkg_config::Library> {
+    pkg_config::Config::new().cargo_metadata(false).probe("visa").ok()
+}
+
+fn vxipnp_dirs(target: &Target) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["VXIPNPPATH64", "VXIPNPPATH"]
+        .iter()
+        .filter_map(|var| env::var_os(var).map(PathBuf::from))
+        .collect();
+    if dirs.is_empty() && target.os != "windows" {
+        dirs.push(PathBuf::from("/usr/local/vxipnp"));
+    }
+    dirs
+}
+
+fn vxipnp_include_dirs(target: &Target, root: &Path) -> Vec<PathBuf> {
+    match target.os.as_str() {
+        "windows" => vec![root.join("Win64").join("Include"), root.join("WinNT").join("Include")],
+        _ => vec![root.join("linux").join("include")],
+    }
+}
+
+fn vxipnp_lib_dirs(target: &Target, root: &Path) -> Vec<PathBuf> {
+    match target.os.as_str() {
+        "windows" if target.pointer_width == 64 => vec![root.join("Win64").join("Lib_x64").join("msc")],
+        "windows" => vec![
+            root.join("WinNT").join("lib").join("msc"),
+            root.join("WinNT").join("Bin"),
+        ],
+        _ => vec![
+            root.join("linux").join("lib64"),
+            root.join("linux").join("lib"),
+            root.join("linux").join("bin"),
+        ],
+    }
+}
+
+fn default_include_dirs(target: &Target) -> Vec<PathBuf> {
+    let dirs: &[&str] = match target.os.as_str() {
+        "windows" => &[
+            "C:\\Program Files\\IVI Foundation\\VISA\\Win64\\Include",
+            "C:\\Program Files (x86)\\IVI Foundation\\VISA\\WinNT\\Include",
+        ],
+        "macos" => &[
+            "/Library/Frameworks/VISA.framework/Headers",
+            "/Library/Frameworks/RsVisa.framework/Headers",
+        ],
+        _ => &[
+            "/usr/include/ni-visa",
+            "/usr/include",
+            "/usr/local/include",
+            "/opt/keysight/iolibs/include",
+            "/usr/include/rsvisa",
+            "/opt/rsvisa/include",
+        ],
+    };
+    dirs.iter().map(PathBuf::from).collect()
+}
+
+fn default_lib_dirs(target: &Target) -> Vec<PathBuf> {
+    let dirs: &[&str] = match (target.os.as_str(), target.pointer_width) {
+        ("windows", 64) => &["C:\\Program Files\\IVI Foundation\\VISA\\Win64\\Lib_x64\\msc"],
+        ("windows", _) => &[
+            "C:\\Program Files (x86)\\IVI Foundation\\VISA\\WinNT\\lib\\msc",
+            "C:\\Program Files (x86)\\IVI Foundation\\VISA\\WinNT\\Bin",
+        ],
+        ("macos", _) => &["/usr/local/lib"],
+        (_, 64) => &[
+            "/usr/lib/x86_64-linux-gnu",
+            "/usr/lib/aarch64-linux-gnu",
+            "/usr/lib64",
+            "/usr/lib",
+            "/usr/local/lib",
+            "/opt/keysight/iolibs/lib",
+            "/opt/rsvisa/lib",
+        ],
+        _ => &[
+            "/usr/lib/i386-linux-gnu",
+            "/usr/lib/arm-linux-gnueabihf",
+            "/usr/lib",
+            "/usr/local/lib",
+            "/opt/keysight/iolibs/lib",
+            "/opt/rsvisa/lib",
+        ],
+    };
+    dirs.iter().map(PathBuf::from).collect()
+}
+
+fn default_lib_files(target: &Target) -> Vec<PathBuf> {
+    match target.os.as_str() {
+        "macos" => vec![
+            PathBuf::from("/Library/Frameworks/VISA.framework/Versions/Current/VISA"),
+            PathBuf::from("/Library/Frameworks/RsVisa.framework/Versions/Current/RsVisa"),
+        ],
+        _ => Vec::new(),
+    }
+}
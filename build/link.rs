@@ -0,0 +1,132 @@
+//! Link configuration for the build script, driven by the target cargo is
+//! building for rather than the host running the build script.
+//!
+//! This module has no dependencies so that it also builds on its own as the
+//! `build_link` test target, which runs the tests below for every target.
+
+// `Target::from_env` is only called by the build script.
+#![cfg_attr(test, allow(dead_code))]
+
+use std::env;
+use std::path::PathBuf;
+
+/// The platform being built for, as seen by cargo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub os: String,
+    pub pointer_width: u32,
+}
+
+impl Target {
+    pub fn new(os: &str, pointer_width: u32) -> Self {
+        Target {
+            os: os.to_string(),
+            pointer_width,
+        }
+    }
+
+    /// Reads the target from the `CARGO_CFG_TARGET_*` variables cargo sets
+    /// for build scripts.
+    pub fn from_env() -> Self {
+        Target::new(
+            &env::var("CARGO_CFG_TARGET_OS").unwrap(),
+            env::var("CARGO_CFG_TARGET_POINTER_WIDTH").unwrap().parse().unwrap(),
+        )
+    }
+}
+
+/// A VISA library that was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VisaLibrary {
+    /// `dir` contains the library `name`, to be linked with `-l name`.
+    Search { dir: PathBuf, name: String },
+    /// A library file linked by its full path, e.g. a macOS framework binary.
+    Path(PathBuf),
+}
+
+/// The library names VISA implementations use on `target`, most common first.
+pub fn default_lib_names(target: &Target) -> Vec<String> {
+    let names: &[&str] = match (target.os.as_str(), target.pointer_width) {
+        ("windows", 64) => &["visa64"],
+        ("windows", _) => &["visa32"],
+        _ => &["visa", "rsvisa"],
+    };
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+/// The files that make `name` linkable with `-l name` on `target`.
+pub fn lib_file_names(target: &Target, name: &str) -> Vec<String> {
+    match target.os.as_str() {
+        "windows" => vec![format!("{}.lib", name)],
+        "macos" => vec![format!("lib{}.dylib", name)],
+        _ => vec![format!("lib{}.so", name)],
+    }
+}
+
+/// The `cargo:` directives linking `library`.
+pub fn directives(library: &VisaLibrary) -> Vec<String> {
+    match library {
+        VisaLibrary::Search { dir, name } => vec![
+            format!("cargo:rustc-link-search=native={}", dir.display()),
+            format!("cargo:rustc-link-lib=dylib={}", name),
+        ],
+        // Tell the linker to use e.g. the VISA framework on macOS
+        VisaLibrary::Path(path) => vec![format!("cargo:rustc-link-arg={}", path.display())],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link_lib(target: &Target) -> Vec<String> {
+        let name = default_lib_names(target).remove(0);
+        directives(&VisaLibrary::Search {
+            dir: PathBuf::from("/visa/lib"),
+            name,
+        })
+    }
+
+    #[test]
+    fn test_linux_links_libvisa() {
+        let target = Target::new("linux", 64);
+        assert_eq!(
+            link_lib(&target),
+            ["cargo:rustc-link-search=native=/visa/lib", "cargo:rustc-link-lib=dylib=visa"]
+        );
+        assert_eq!(lib_file_names(&target, "visa"), ["libvisa.so"]);
+        assert_eq!(default_lib_names(&Target::new("linux", 32)), ["visa", "rsvisa"]);
+    }
+
+    #[test]
+    fn test_windows_links_visa64_or_visa32_by_pointer_width() {
+        assert_eq!(
+            link_lib(&Target::new("windows", 64)),
+            ["cargo:rustc-link-search=native=/visa/lib", "cargo:rustc-link-lib=dylib=visa64"]
+        );
+        assert_eq!(
+            link_lib(&Target::new("windows", 32)),
+            ["cargo:rustc-link-search=native=/visa/lib", "cargo:rustc-link-lib=dylib=visa32"]
+        );
+        assert_eq!(lib_file_names(&Target::new("windows", 64), "visa64"), ["visa64.lib"]);
+    }
+
+    #[test]
+    fn test_macos_links_framework_by_path() {
+        let framework = VisaLibrary::Path(PathBuf::from("/Library/Frameworks/VISA.framework/Versions/Current/VISA"));
+        assert_eq!(
+            directives(&framework),
+            ["cargo:rustc-link-arg=/Library/Frameworks/VISA.framework/Versions/Current/VISA"]
+        );
+        assert_eq!(lib_file_names(&Target::new("macos", 64), "visa"), ["libvisa.dylib"]);
+    }
+
+    #[test]
+    fn test_each_directive_is_emitted_once() {
+        for (os, width) in [("linux", 64), ("linux", 32), ("windows", 64), ("windows", 32), ("macos", 64)] {
+            let directives = link_lib(&Target::new(os, width));
+            let links = directives.iter().filter(|d| d.starts_with("cargo:rustc-link-lib=")).count();
+            assert_eq!(links, 1, "{}-{}: {:?}", os, width, directives);
+        }
+    }
+}
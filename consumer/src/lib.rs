@@ -0,0 +1,9 @@
+//! This crate is never published. It depends on `ni-visa-bindings` exactly the
+//! way a downstream project would, so that `cargo test --workspace` catches
+//! regressions in how the bindings can be consumed.
+//!
+//! The usage example of the readme is compiled as a doctest of this crate.
+
+#[doc = include_str!("../../readme.md")]
+#[cfg(doctest)]
+pub struct ReadmeDoctests;
@@ -0,0 +1,17 @@
+use ni_visa_bindings::ffi::*;
+
+#[test]
+fn test_ffi_types_are_reachable() {
+    let default_rm: ViSession = VI_NULL as ViSession;
+    let status: ViStatus = VI_SUCCESS as ViStatus;
+
+    assert_eq!(default_rm, 0);
+    assert_eq!(status, 0);
+    assert_eq!(std::mem::size_of::<ViSession>(), std::mem::size_of::<ViUInt32>());
+}
+
+#[test]
+fn test_ffi_constants_are_reachable() {
+    assert!((VI_ERROR_TMO as ViStatus) < VI_SUCCESS as ViStatus);
+    assert_eq!(VI_ATTR_TMO_VALUE, 0x3FFF001A);
+}
@@ -0,0 +1,321 @@
+//! IEEE 488.2 ASCII numbers and lists.
+//!
+//! Responses are parsed as `<NRf>`: `<NR1>` integers (`42`), `<NR2>` decimals
+//! (`-0.5`) and `<NR3>` exponentials (`+2.000000000000000E+04`), optionally
+//! followed by an SI prefix and a unit (`2mV`, `20kHz`, or `2MV` and `1MAHZ`
+//! in the upper case of IEEE 488.2 suffixes). The SCPI sentinels
+//! `9.9E37` and `-9.9E37` stand for positive and negative infinity (overflow)
+//! and `9.91E37` for not-a-number; `INF`, `NINF` and `NAN` are accepted as well.
+//! Integers may also be written in IEEE 488.2 non-decimal form (`#HFF`,
+//! `#Q17`, `#B101`).
+//!
+//! ```
+//! use ni_visa_bindings::ascii::{self, Nrf};
+//!
+//! assert_eq!(ascii::parse_number("+2.000000000000000E+04").unwrap(), 2e4);
+//! assert_eq!(ascii::parse_number("20kHz").unwrap(), 20e3);
+//! assert_eq!(ascii::parse_number("9.9E37").unwrap(), f64::INFINITY);
+//! assert_eq!(ascii::parse_values::<u16>("1,2,#H10", ',').unwrap(), [1, 2, 16]);
+//! assert_eq!(Nrf(0.002).to_string(), "2.0E-3");
+//! assert_eq!(Nrf(f64::INFINITY).to_string(), "9.9E37");
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+/// The SCPI representation of positive infinity.
+const INFINITY: f64 = 9.9e37;
+/// The SCPI representation of not-a-number.
+const NOT_A_NUMBER: f64 = 9.91e37;
+
+/// SI prefixes with their power of ten. They are case-sensitive, so `m` is
+/// milli and `M` mega.
+const PREFIXES: &[(&str, i32)] = &[
+    ("T", 12),
+    ("G", 9),
+    ("M", 6),
+    ("k", 3),
+    ("K", 3),
+    ("m", -3),
+    ("u", -6),
+    ("µ", -6),
+    ("n", -9),
+    ("p", -12),
+    ("f", -15),
+];
+
+/// The IEEE 488.2 prefixes of suffixes in upper case, the longer ones first:
+/// `M` is milli and `MA` mega, so `2MV` is 2E-3 and `2MAV` is 2E6.
+const UPPER_CASE_PREFIXES: &[(&str, i32)] = &[
+    ("EX", 18),
+    ("PE", 15),
+    ("MA", 6),
+    ("T", 12),
+    ("G", 9),
+    ("K", 3),
+    ("M", -3),
+    ("U", -6),
+    ("N", -9),
+    ("P", -12),
+    ("F", -15),
+];
+
+/// Upper-case suffixes IEEE 488.2 reads as mega despite the `M`.
+const MEGA_UNITS: &[&str] = &["MHZ", "MOHM"];
+
+/// The units a prefix is recognized before, compared case-insensitively. A
+/// suffix that is a prefix followed by anything else is a unit of its own,
+/// e.g. `F` (farad) or `PCT` (percent).
+const UNITS: &[&str] = &[
+    "A", "APP", "ARMS", "BAR", "C", "CEL", "DB", "DBM", "DBUV", "DBW", "DEG", "F", "FAR", "H", "HZ", "J", "OHM",
+    "PA", "PCT", "RAD", "S", "SIE", "V", "VPP", "VPK", "VRMS", "W",
+];
+
+/// A token that is not a valid IEEE 488.2 number for the requested type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNumberError {
+    token: String,
+}
+
+impl ParseNumberError {
+    fn new(token: &str) -> Self {
+        ParseNumberError {
+            token: token.to_string(),
+        }
+    }
+
+    /// The token that failed to parse.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl fmt::Display for ParseNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid IEEE 488.2 number {:?}", self.token)
+    }
+}
+
+impl Error for ParseNumberError {}
+
+/// Parses one `<NRf>` token with an optional SI prefix and unit.
+pub fn parse_number(token: &str) -> Result<f64, ParseNumberError> {
+    let trimmed = token.trim();
+    let error = || ParseNumberError::new(token);
+
+    match trimmed.to_ascii_uppercase().as_str() {
+        "INF" | "+INF" => return Ok(f64::INFINITY),
+        "NINF" | "-INF" => return Ok(f64::NEG_INFINITY),
+        "NAN" => return Ok(f64::NAN),
+        _ => {}
+    }
+    if let Some(value) = parse_non_decimal(trimmed) {
+        return value.map(|value| value as f64).ok_or_else(error);
+    }
+
+    let split = number_len(trimmed).ok_or_else(error)?;
+    let (number, suffix) = trimmed.split_at(split);
+    let value: f64 = number.replace(' ', "").parse().map_err(|_| error())?;
+    let value = if value == INFINITY {
+        f64::INFINITY
+    } else if value == -INFINITY {
+        f64::NEG_INFINITY
+    } else if value == NOT_A_NUMBER {
+        f64::NAN
+    } else {
+        value
+    };
+    // Dividing by an exact power of ten keeps e.g. `100n` equal to `100E-9`.
+    let exponent = prefix_exponent(suffix.trim_start()).ok_or_else(error)?;
+    if exponent < 0 {
+        Ok(value / 10f64.powi(-exponent))
+    } else {
+        Ok(value * 10f64.powi(exponent))
+    }
+}
+
+/// The length of the leading `[sign]digits[.digits][E[sign]digits]` of `text`.
+fn number_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        i += 1;
+    }
+    let int_start = i;
+    while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+        i += 1;
+    }
+    let mut digits = i - int_start;
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        let frac_start = i;
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        digits += i - frac_start;
+    }
+    if digits == 0 {
+        return None;
+    }
+
+    // The exponent, unless the `E` starts a unit such as `EV`.
+    let mut j = i;
+    while bytes.get(j) == Some(&b' ') {
+        j += 1;
+    }
+    if matches!(bytes.get(j), Some(b'e' | b'E')) {
+        let mut k = j + 1;
+        while bytes.get(k) == Some(&b' ') {
+            k += 1;
+        }
+        if matches!(bytes.get(k), Some(b'+' | b'-')) {
+            k += 1;
+        }
+        let exp_start = k;
+        while bytes.get(k).is_some_and(u8::is_ascii_digit) {
+            k += 1;
+        }
+        if k > exp_start {
+            i = k;
+        }
+    }
+    Some(i)
+}
+
+/// The power of ten of a suffix such as `""`, `"V"`, `"mV"` or `"kHz"`.
+fn prefix_exponent(suffix: &str) -> Option<i32> {
+    if suffix.is_empty() {
+        return Some(0);
+    }
+    // A lone `E` is an exponent missing its digits, not a unit.
+    if !suffix.chars().all(|c| c.is_alphabetic() || c == '/') || suffix.eq_ignore_ascii_case("E") {
+        return None;
+    }
+    let prefixes = if suffix.chars().any(char::is_lowercase) {
+        PREFIXES
+    } else if MEGA_UNITS.contains(&suffix) {
+        return Some(6);
+    } else {
+        UPPER_CASE_PREFIXES
+    };
+    let is_unit = |unit: &str| UNITS.iter().any(|known| unit.eq_ignore_ascii_case(known));
+    for &(prefix, exponent) in prefixes {
+        if suffix.strip_prefix(prefix).is_some_and(is_unit) {
+            return Some(exponent);
+        }
+    }
+    if is_unit(suffix) {
+        return Some(0);
+    }
+    // A prefix on its own, as in `100n`.
+    Some(prefixes.iter().find(|(prefix, _)| *prefix == suffix).map_or(0, |&(_, exponent)| exponent))
+}
+
+/// Parses `#H`, `#Q` and `#B` numbers. Returns `None` for other tokens and
+/// `Some(None)` for malformed ones.
+fn parse_non_decimal(token: &str) -> Option<Option<u64>> {
+    let rest = token.strip_prefix('#')?;
+    let mut chars = rest.chars();
+    let radix = match chars.next()?.to_ascii_uppercase() {
+        'H' => 16,
+        'Q' => 8,
+        'B' => 2,
+        _ => return None,
+    };
+    Some(u64::from_str_radix(chars.as_str(), radix).ok())
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A type ASCII values can be parsed into.
+pub trait AsciiValue: sealed::Sealed + Sized {
+    /// Parses one token.
+    fn parse_token(token: &str) -> Result<Self, ParseNumberError>;
+}
+
+macro_rules! float_values {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl AsciiValue for $ty {
+                fn parse_token(token: &str) -> Result<Self, ParseNumberError> {
+                    parse_number(token).map(|value| value as $ty)
+                }
+            }
+        )*
+    };
+}
+
+float_values!(f32, f64);
+
+macro_rules! integer_values {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl AsciiValue for $ty {
+                /// Accepts `<NR1>` and non-decimal tokens, and other `<NRf>`
+                /// tokens that are whole numbers in range, e.g. `1.0E+02`.
+                fn parse_token(token: &str) -> Result<Self, ParseNumberError> {
+                    let trimmed = token.trim();
+                    let error = || ParseNumberError::new(token);
+                    if let Ok(value) = trimmed.strip_prefix('+').unwrap_or(trimmed).parse::<$ty>() {
+                        return Ok(value);
+                    }
+                    if let Some(value) = parse_non_decimal(trimmed) {
+                        return value.and_then(|value| <$ty>::try_from(value).ok()).ok_or_else(error);
+                    }
+                    let value = parse_number(token)?;
+                    if value.fract() != 0.0 || value < <$ty>::MIN as f64 || value > <$ty>::MAX as f64 {
+                        return Err(error());
+                    }
+                    Ok(value as $ty)
+                }
+            }
+        )*
+    };
+}
+
+integer_values!(i8, u8, i16, u16, i32, u32, i64, u64);
+
+/// Parses a `separator`-separated list such as `1,2,3`. Whitespace around
+/// values is ignored and an empty response is an empty list.
+pub fn parse_values<T: AsciiValue>(text: &str, separator: char) -> Result<Vec<T>, ParseNumberError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    text.split(separator).map(T::parse_token).collect()
+}
+
+/// Formats a number as an SCPI-legal `<NRf>` token: `<NR1>` for whole
+/// numbers, `<NR3>` otherwise, and the SCPI sentinels for infinities and
+/// not-a-number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nrf(pub f64);
+
+impl fmt::Display for Nrf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0;
+        if value.is_nan() {
+            f.write_str("9.91E37")
+        } else if value == f64::INFINITY {
+            f.write_str("9.9E37")
+        } else if value == f64::NEG_INFINITY {
+            f.write_str("-9.9E37")
+        } else if value.fract() == 0.0 && value.abs() < 1e15 {
+            write!(f, "{}", value as i64)
+        } else {
+            // `{:E}` gives the shortest round-trip mantissa, which needs a
+            // decimal point to be `<NR3>`.
+            let text = format!("{:E}", value);
+            match text.split_once('E') {
+                Some((mantissa, exponent)) if !mantissa.contains('.') => write!(f, "{}.0E{}", mantissa, exponent),
+                _ => f.write_str(&text),
+            }
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! Futures over `viReadAsync` and `viWriteAsync`.
+//!
+//! An `AsyncSession` installs a handler for `VI_EVENT_IO_COMPLETION` that
+//! records the result of each job and wakes the future waiting for it, so the
+//! futures work with any executor. A future owns its buffer while VISA may
+//! write to or read from it; dropping it before completion calls
+//! `viTerminate` and keeps the buffer until VISA reports the job as done.
+//!
+//! ```no_run
+//! use ni_visa_bindings::{AsyncSession, ResourceManager};
+//!
+//! async fn identify(session: &AsyncSession<'_>) -> std::io::Result<Vec<u8>> {
+//!     session.write(b"*IDN?\n".to_vec()).await?;
+//!     session.read(vec![0; 256]).await
+//! }
+//!
+//! let rm = ResourceManager::new().unwrap();
+//! let session = AsyncSession::new(rm.open("GPIB0::5::INSTR").unwrap()).unwrap();
+//! let future = identify(&session);
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use crate::attr;
+use crate::backend::VisaBackend;
+use crate::event::{Event, EventKind};
+use crate::ffi::*;
+use crate::handler::InstalledHandler;
+use crate::session::Session;
+use crate::status::{Result, Status};
+
+/// The results of the asynchronous jobs of a session, filled in by the I/O
+/// completion handler.
+#[derive(Debug, Default)]
+pub(crate) struct Jobs(Mutex<HashMap<ViJobId, Job>>);
+
+#[derive(Debug, Default)]
+struct Job {
+    result: Option<(Status, usize)>,
+    waker: Option<Waker>,
+    /// The buffer of a cancelled job, freed once the job completes.
+    abandoned: Option<Vec<u8>>,
+}
+
+impl Jobs {
+    fn lock(&self) -> MutexGuard<'_, HashMap<ViJobId, Job>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records the result of `id`. The completion may arrive before the
+    /// future polls the job, even before `viReadAsync` returns.
+    pub(crate) fn complete(&self, id: ViJobId, status: Status, count: usize) {
+        let waker = {
+            let mut jobs = self.lock();
+            let job = jobs.entry(id).or_default();
+            if job.abandoned.is_some() {
+                jobs.remove(&id);
+                return;
+            }
+            job.result = Some((status, count));
+            job.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Records the job of an I/O completion event. A job whose status or
+    /// count cannot be read fails with that error rather than never
+    /// completing.
+    pub(crate) fn complete_event(&self, event: &Event) {
+        let Ok(id) = event.job_id() else {
+            return;
+        };
+        match event.status().and_then(|status| Ok((status, event.get::<attr::RetCount>()?))) {
+            Ok((status, count)) => self.complete(id, status, count as usize),
+            Err(e) => self.complete(id, Status::from_raw(e.raw()), 0),
+        }
+    }
+
+    /// Takes the result of `id`, or registers `waker` to be woken with it.
+    pub(crate) fn poll(&self, id: ViJobId, waker: &Waker) -> Poll<(Status, usize)> {
+        let mut jobs = self.lock();
+        let job = jobs.entry(id).or_default();
+        match job.result {
+            Some(result) => {
+                jobs.remove(&id);
+                Poll::Ready(result)
+            }
+            None => {
+                job.waker = Some(waker.clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Gives up on `id`, keeping `buffer` alive until the job completes.
+    pub(crate) fn abandon(&self, id: ViJobId, buffer: Vec<u8>) {
+        let mut jobs = self.lock();
+        let job = jobs.entry(id).or_default();
+        if job.result.is_some() {
+            jobs.remove(&id);
+        } else {
+            job.abandoned = Some(buffer);
+            job.waker = None;
+        }
+    }
+
+    /// The number of jobs with a result or buffer still held.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+}
+
+/// A session whose reads and writes are futures.
+///
+/// The session is closed when this is dropped, which also aborts the jobs of
+/// futures that were dropped before completing.
+#[derive(Debug)]
+pub struct AsyncSession<'rm> {
+    // Dropped in this order: pending jobs are terminated, the handler is
+    // uninstalled, the session closed, and only then are buffers freed.
+    #[cfg(feature = "tokio")]
+    reading: Option<Operation>,
+    #[cfg(feature = "tokio")]
+    leftover: io::Cursor<Vec<u8>>,
+    #[cfg(feature = "tokio")]
+    writing: Option<Operation>,
+    _handler: InstalledHandler<'rm>,
+    session: Session<'rm>,
+    jobs: Arc<Jobs>,
+}
+
+impl<'rm> AsyncSession<'rm> {
+    /// Installs the I/O completion handler on `session`.
+    pub fn new(session: Session<'rm>) -> Result<Self> {
+        let jobs = Arc::new(Jobs::default());
+        let completed = jobs.clone();
+        let handler = InstalledHandler::install(
+            session.resource_manager(),
+            session.as_raw(),
+            EventKind::IoCompletion,
+            Box::new(move |event| completed.complete_event(event)),
+        )?;
+        Ok(AsyncSession {
+            #[cfg(feature = "tokio")]
+            reading: None,
+            #[cfg(feature = "tokio")]
+            leftover: io::Cursor::new(Vec::new()),
+            #[cfg(feature = "tokio")]
+            writing: None,
+            _handler: handler,
+            session,
+            jobs,
+        })
+    }
+
+    /// The underlying session, e.g. for attributes.
+    pub fn session(&self) -> &Session<'rm> {
+        &self.session
+    }
+
+    pub fn session_mut(&mut self) -> &mut Session<'rm> {
+        &mut self.session
+    }
+
+    /// Reads up to `buffer.len()` bytes into `buffer` with `viReadAsync`,
+    /// returning it truncated to the bytes read. The read stops at the end of
+    /// a message like `viRead`.
+    pub fn read(&self, buffer: Vec<u8>) -> ReadFuture<'_> {
+        ReadFuture {
+            state: State::Start(&self.session, &self.jobs, buffer),
+        }
+    }
+
+    /// Writes `data` with `viWriteAsync`, returning the number of bytes
+    /// written.
+    pub fn write(&self, data: Vec<u8>) -> WriteFuture<'_> {
+        WriteFuture {
+            state: State::Start(&self.session, &self.jobs, data),
+        }
+    }
+}
+
+/// A job in progress, terminated with `viTerminate` if dropped before it
+/// completes.
+#[derive(Debug)]
+struct Operation {
+    backend: Arc<dyn VisaBackend>,
+    handle: ViSession,
+    jobs: Arc<Jobs>,
+    id: ViJobId,
+    buffer: Option<Vec<u8>>,
+}
+
+impl Operation {
+    fn read(session: &Session<'_>, jobs: &Arc<Jobs>, mut buffer: Vec<u8>) -> io::Result<Self> {
+        let backend = session.backend();
+        let id = unsafe { backend.read_async(session.as_raw(), buffer.as_mut_ptr(), buffer.len()) }?;
+        Ok(Operation::started(session, jobs, id, buffer))
+    }
+
+    fn write(session: &Session<'_>, jobs: &Arc<Jobs>, data: Vec<u8>) -> io::Result<Self> {
+        let backend = session.backend();
+        let id = unsafe { backend.write_async(session.as_raw(), data.as_ptr(), data.len()) }?;
+        Ok(Operation::started(session, jobs, id, data))
+    }
+
+    /// Only called once the job started, since one that failed to start
+    /// raises no completion event.
+    fn started(session: &Session<'_>, jobs: &Arc<Jobs>, id: ViJobId, buffer: Vec<u8>) -> Self {
+        Operation {
+            backend: session.backend().clone(),
+            handle: session.as_raw(),
+            jobs: jobs.clone(),
+            id,
+            buffer: Some(buffer),
+        }
+    }
+
+    /// The buffer and number of bytes transferred once the job completes.
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<(Vec<u8>, usize)>> {
+        let (status, count) = match self.jobs.poll(self.id, cx.waker()) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        let buffer = self.buffer.take().unwrap_or_default();
+        Poll::Ready(status.into_result().map(|_| (buffer, count)).map_err(io::Error::from))
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            let _ = self.backend.terminate(self.handle, VI_NULL as ViUInt16, self.id);
+            self.jobs.abandon(self.id, buffer);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum State<'a> {
+    Start(&'a Session<'a>, &'a Arc<Jobs>, Vec<u8>),
+    Running(Operation),
+    Done,
+}
+
+impl State<'_> {
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+        start: fn(&Session<'_>, &Arc<Jobs>, Vec<u8>) -> io::Result<Operation>,
+    ) -> Poll<io::Result<(Vec<u8>, usize)>> {
+        if let State::Start(..) = self {
+            let State::Start(session, jobs, buffer) = std::mem::replace(self, State::Done) else {
+                unreachable!()
+            };
+            *self = State::Running(start(session, jobs, buffer)?);
+        }
+        let State::Running(operation) = self else {
+            panic!("future polled after completion");
+        };
+        let result = std::task::ready!(operation.poll(cx));
+        *self = State::Done;
+        Poll::Ready(result)
+    }
+}
+
+/// The future returned by `AsyncSession::read`.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct ReadFuture<'a> {
+    state: State<'a>,
+}
+
+impl Future for ReadFuture<'_> {
+    type Output = io::Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.state.poll(cx, Operation::read).map_ok(|(mut buffer, count)| {
+            buffer.truncate(count);
+            buffer
+        })
+    }
+}
+
+/// The future returned by `AsyncSession::write`.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WriteFuture<'a> {
+    state: State<'a>,
+}
+
+impl Future for WriteFuture<'_> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.state.poll(cx, Operation::write).map_ok(|(_, count)| count)
+    }
+}
+
+/// Reads go through an internal buffer, since the `ReadBuf` of a pending read
+/// may change between polls.
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for AsyncSession<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.leftover.position() as usize == this.leftover.get_ref().len() {
+            if this.reading.is_none() {
+                let buffer = vec![0; buf.remaining().max(1)];
+                this.reading = Some(Operation::read(&this.session, &this.jobs, buffer)?);
+            }
+            let result = std::task::ready!(this.reading.as_mut().unwrap().poll(cx));
+            this.reading = None;
+            let (mut buffer, count) = result?;
+            buffer.truncate(count);
+            this.leftover = io::Cursor::new(buffer);
+        }
+        let start = this.leftover.position() as usize;
+        let available = &this.leftover.get_ref()[start..];
+        let n = available.len().min(buf.remaining());
+        buf.put_slice(&available[..n]);
+        this.leftover.set_position((start + n) as u64);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Each write is copied and started immediately; its outcome is reported by
+/// the next write or by `flush`.
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncWrite for AsyncSession<'_> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, data: &[u8]) -> Poll<io::Result<usize>> {
+        std::task::ready!(self.as_mut().poll_flush(cx))?;
+        let this = &mut *self;
+        this.writing = Some(Operation::write(&this.session, &this.jobs, data.to_vec())?);
+        Poll::Ready(Ok(data.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let Some(writing) = self.writing.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+        let result = std::task::ready!(writing.poll(cx));
+        self.writing = None;
+        let (data, count) = result?;
+        if count < data.len() {
+            return Poll::Ready(Err(io::Error::new(io::ErrorKind::WriteZero, "write was cut short")));
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
@@ -0,0 +1,540 @@
+//! Typed VISA attributes.
+//!
+//! Every `VI_ATTR_*` resource attribute has a marker type here carrying its
+//! ID, value type, access and scope, so `Session::get` returns the right type
+//! and `Session::set` only compiles for writable attributes:
+//!
+//! ```no_run
+//! use ni_visa_bindings::{attr, ResourceManager};
+//!
+//! # fn main() -> ni_visa_bindings::Result<()> {
+//! let rm = ResourceManager::new()?;
+//! let session = rm.open("TCPIP0::192.168.0.10::INSTR")?;
+//! session.set::<attr::TermChar>(b'\n')?;
+//! let timeout: u32 = session.get::<attr::TmoValue>()?;
+//! let model: String = session.get::<attr::ModelName>()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Read-only attributes cannot be set:
+//!
+//! ```compile_fail
+//! # fn f(session: &ni_visa_bindings::Session) {
+//! session.set::<ni_visa_bindings::attr::RsrcName>(String::new());
+//! # }
+//! ```
+
+use std::ffi::CStr;
+
+use crate::backend::VisaBackend;
+use crate::ffi::*;
+use crate::status::{error, Result};
+
+/// Whether an attribute can be set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    ReadOnly,
+    ReadWrite,
+}
+
+/// Whether an attribute belongs to one session (`Local`) or is shared by
+/// every session to the resource (`Global`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Local,
+    Global,
+}
+
+/// A VISA attribute, implemented by the marker types in this module.
+pub trait Attribute {
+    type Value: AttrValue;
+    /// The `VI_ATTR_*` ID.
+    const ID: ViAttr;
+    /// The `VI_ATTR_*` name.
+    const NAME: &'static str;
+    const ACCESS: Access;
+    const SCOPE: Scope;
+    /// The `VI_INTF_*` interface types the attribute applies to. Empty for
+    /// attributes every resource has.
+    const INTERFACES: &'static [u16];
+}
+
+/// An attribute that can be set with `Session::set`.
+pub trait Writable: Attribute
+where
+    Self::Value: SetAttrValue,
+{
+}
+
+/// Description of an attribute, for lookups by ID (see `info`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeInfo {
+    pub id: ViAttr,
+    pub name: &'static str,
+    pub value_type: ValueType,
+    pub access: Access,
+    pub scope: Scope,
+    pub interfaces: &'static [u16],
+}
+
+/// Looks up an attribute by its `VI_ATTR_*` ID.
+pub fn info(id: ViAttr) -> Option<&'static AttributeInfo> {
+    ALL.iter().find(|info| info.id == id)
+}
+
+/// The Rust type an attribute is read as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I16,
+    I32,
+    Usize,
+    Bool,
+    String,
+    Bytes,
+}
+
+/// The smallest buffer `viGetAttribute` can write `attr` of `object` into:
+/// the size of a number, `VI_ATTR_USB_RECV_INTR_SIZE` bytes for the USB
+/// interrupt data, and `VI_FIND_BUFLEN` for strings and unknown attributes.
+pub(crate) fn buffer_len(backend: &dyn VisaBackend, object: ViObject, attr: ViAttr) -> Result<usize> {
+    let Some(info) = info(attr) else {
+        return Ok(VI_FIND_BUFLEN as usize);
+    };
+    Ok(match info.value_type {
+        ValueType::U8 => 1,
+        ValueType::U16 | ValueType::I16 | ValueType::Bool => 2,
+        ValueType::U32 | ValueType::I32 => 4,
+        ValueType::U64 => 8,
+        ValueType::Usize => std::mem::size_of::<usize>(),
+        ValueType::Bytes => u16::get(backend, object, VI_ATTR_USB_RECV_INTR_SIZE as ViAttr)?.into(),
+        ValueType::String => VI_FIND_BUFLEN as usize,
+    })
+}
+
+/// Fails with `VI_ERROR_INV_PARAMETER` if `buffer` is smaller than
+/// `buffer_len`, before `viGetAttribute` could write past its end.
+pub(crate) fn check_buffer(backend: &dyn VisaBackend, object: ViObject, attr: ViAttr, buffer: &[u8]) -> Result<()> {
+    if buffer.len() < buffer_len(backend, object, attr)? {
+        return Err(error(VI_ERROR_INV_PARAMETER));
+    }
+    Ok(())
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A type attribute values are read into.
+pub trait AttrValue: sealed::Sealed + Sized {
+    /// The entry for this type in `AttributeInfo::value_type`.
+    const TYPE: ValueType;
+
+    #[doc(hidden)]
+    fn get(backend: &dyn VisaBackend, vi: ViObject, attr: ViAttr) -> Result<Self>;
+}
+
+/// A type attribute values can be set from.
+pub trait SetAttrValue: AttrValue {
+    #[doc(hidden)]
+    fn to_state(&self) -> ViAttrState;
+}
+
+macro_rules! numeric_values {
+    ($($ty:ty => $type:ident),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl AttrValue for $ty {
+                const TYPE: ValueType = ValueType::$type;
+
+                fn get(backend: &dyn VisaBackend, vi: ViObject, attr: ViAttr) -> Result<Self> {
+                    let mut value = [0; std::mem::size_of::<$ty>()];
+                    backend.get_attribute(vi, attr, &mut value)?;
+                    Ok(<$ty>::from_ne_bytes(value))
+                }
+            }
+        )*
+    };
+}
+
+numeric_values!(u8 => U8, u16 => U16, u32 => U32, u64 => U64, i16 => I16, i32 => I32, usize => Usize);
+
+macro_rules! settable_values {
+    ($($ty:ty),*) => {
+        $(
+            impl SetAttrValue for $ty {
+                fn to_state(&self) -> ViAttrState {
+                    // Signed values are sign-extended; VISA reads back the low bytes.
+                    *self as ViAttrState
+                }
+            }
+        )*
+    };
+}
+
+settable_values!(u8, u16, u32, i16, i32, usize);
+
+// `ViAttrState` is only 64 bits wide on 64-bit targets.
+#[cfg(target_pointer_width = "64")]
+settable_values!(u64);
+
+impl sealed::Sealed for bool {}
+
+impl AttrValue for bool {
+    const TYPE: ValueType = ValueType::Bool;
+
+    fn get(backend: &dyn VisaBackend, vi: ViObject, attr: ViAttr) -> Result<Self> {
+        let value = ViBoolean::get(backend, vi, attr)?;
+        Ok(value != VI_FALSE as ViBoolean)
+    }
+}
+
+impl SetAttrValue for bool {
+    fn to_state(&self) -> ViAttrState {
+        if *self {
+            VI_TRUE as ViAttrState
+        } else {
+            VI_FALSE as ViAttrState
+        }
+    }
+}
+
+impl sealed::Sealed for Vec<u8> {}
+
+/// A variable-length byte array. VISA has one, `VI_ATTR_USB_RECV_INTR_DATA`,
+/// which holds as many bytes as `VI_ATTR_USB_RECV_INTR_SIZE` says.
+impl AttrValue for Vec<u8> {
+    const TYPE: ValueType = ValueType::Bytes;
+
+    fn get(backend: &dyn VisaBackend, vi: ViObject, attr: ViAttr) -> Result<Self> {
+        let size = u16::get(backend, vi, VI_ATTR_USB_RECV_INTR_SIZE as ViAttr)?;
+        let mut data = vec![0; size as usize];
+        backend.get_attribute(vi, attr, &mut data)?;
+        Ok(data)
+    }
+}
+
+impl sealed::Sealed for String {}
+
+impl AttrValue for String {
+    const TYPE: ValueType = ValueType::String;
+
+    fn get(backend: &dyn VisaBackend, vi: ViObject, attr: ViAttr) -> Result<Self> {
+        let mut buffer = [0; VI_FIND_BUFLEN as usize];
+        backend.get_attribute(vi, attr, &mut buffer)?;
+        let value = CStr::from_bytes_until_nul(&buffer).map_or(&buffer[..], CStr::to_bytes);
+        Ok(String::from_utf8_lossy(value).into_owned())
+    }
+}
+
+pub(crate) fn get<A: Attribute>(backend: &dyn VisaBackend, vi: ViObject) -> Result<A::Value> {
+    A::Value::get(backend, vi, A::ID)
+}
+
+pub(crate) fn set<A: Writable>(backend: &dyn VisaBackend, vi: ViObject, value: A::Value) -> Result
+where
+    A::Value: SetAttrValue,
+{
+    backend.set_attribute(vi, A::ID, value.to_state())
+}
+
+const GPIB: u16 = VI_INTF_GPIB as u16;
+const VXI: u16 = VI_INTF_VXI as u16;
+const GPIB_VXI: u16 = VI_INTF_GPIB_VXI as u16;
+const ASRL: u16 = VI_INTF_ASRL as u16;
+const PXI: u16 = VI_INTF_PXI as u16;
+const TCPIP: u16 = VI_INTF_TCPIP as u16;
+const USB: u16 = VI_INTF_USB as u16;
+
+macro_rules! attributes {
+    ($($(#[$meta:meta])* $name:ident = $id:ident: $ty:ty, $access:ident, $scope:ident, [$($intf:ident),*];)*) => {
+        $(
+            $(#[$meta])*
+            #[doc = concat!("`", stringify!($id), "`")]
+            #[derive(Debug, Clone, Copy)]
+            pub enum $name {}
+
+            $(#[$meta])*
+            impl Attribute for $name {
+                type Value = $ty;
+                const ID: ViAttr = $id as ViAttr;
+                const NAME: &'static str = stringify!($id);
+                const ACCESS: Access = Access::$access;
+                const SCOPE: Scope = Scope::$scope;
+                const INTERFACES: &'static [u16] = &[$($intf),*];
+            }
+
+            attributes!(@writable $access $name $(#[$meta])*);
+        )*
+
+        /// Every attribute in this module.
+        pub const ALL: &[AttributeInfo] = &[$(
+            $(#[$meta])*
+            AttributeInfo {
+                id: $id as ViAttr,
+                name: stringify!($id),
+                value_type: <$ty as AttrValue>::TYPE,
+                access: Access::$access,
+                scope: Scope::$scope,
+                interfaces: &[$($intf),*],
+            },
+        )*];
+    };
+    (@writable ReadWrite $name:ident $(#[$meta:meta])*) => {
+        $(#[$meta])*
+        impl Writable for $name {}
+    };
+    (@writable ReadOnly $name:ident $(#[$meta:meta])*) => {};
+}
+
+/// Declares the attributes `visa.h` defines as their `_32` or `_64` variant
+/// depending on the pointer width, e.g. `VI_ATTR_MEM_BASE`.
+macro_rules! pointer_width_aliases {
+    ($($name:ident = $id:ident: $name32:ident | $name64:ident;)*) => {
+        $(
+            #[doc = concat!("`", stringify!($id), "`, the same attribute as [`", stringify!($name32), "`] on 32-bit")]
+            #[doc = concat!("targets and [`", stringify!($name64), "`] on 64-bit targets.")]
+            #[cfg(target_pointer_width = "32")]
+            pub type $name = $name32;
+
+            #[doc = concat!("`", stringify!($id), "`, the same attribute as [`", stringify!($name32), "`] on 32-bit")]
+            #[doc = concat!("targets and [`", stringify!($name64), "`] on 64-bit targets.")]
+            #[cfg(target_pointer_width = "64")]
+            pub type $name = $name64;
+        )*
+    };
+}
+
+attributes! {
+    // Every resource
+    RsrcClass = VI_ATTR_RSRC_CLASS: String, ReadOnly, Global, [];
+    RsrcName = VI_ATTR_RSRC_NAME: String, ReadOnly, Global, [];
+    RsrcImplVersion = VI_ATTR_RSRC_IMPL_VERSION: u32, ReadOnly, Global, [];
+    RsrcLockState = VI_ATTR_RSRC_LOCK_STATE: u32, ReadOnly, Global, [];
+    RsrcSpecVersion = VI_ATTR_RSRC_SPEC_VERSION: u32, ReadOnly, Global, [];
+    RsrcManfName = VI_ATTR_RSRC_MANF_NAME: String, ReadOnly, Global, [];
+    RsrcManfId = VI_ATTR_RSRC_MANF_ID: u16, ReadOnly, Global, [];
+    MaxQueueLength = VI_ATTR_MAX_QUEUE_LENGTH: u32, ReadWrite, Local, [];
+    UserData32 = VI_ATTR_USER_DATA_32: u32, ReadWrite, Local, [];
+    #[cfg(target_pointer_width = "64")]
+    UserData64 = VI_ATTR_USER_DATA_64: u64, ReadWrite, Local, [];
+    RmSession = VI_ATTR_RM_SESSION: u32, ReadOnly, Local, [];
+    IntfType = VI_ATTR_INTF_TYPE: u16, ReadOnly, Global, [];
+    IntfNum = VI_ATTR_INTF_NUM: u16, ReadOnly, Global, [];
+    IntfInstName = VI_ATTR_INTF_INST_NAME: String, ReadOnly, Global, [];
+    IntfParentNum = VI_ATTR_INTF_PARENT_NUM: u16, ReadOnly, Global, [];
+    TrigId = VI_ATTR_TRIG_ID: i16, ReadWrite, Local, [];
+
+    // Message based I/O
+    TmoValue = VI_ATTR_TMO_VALUE: u32, ReadWrite, Local, [];
+    TermChar = VI_ATTR_TERMCHAR: u8, ReadWrite, Local, [];
+    TermCharEn = VI_ATTR_TERMCHAR_EN: bool, ReadWrite, Local, [];
+    SendEndEn = VI_ATTR_SEND_END_EN: bool, ReadWrite, Local, [];
+    SuppressEndEn = VI_ATTR_SUPPRESS_END_EN: bool, ReadWrite, Local, [];
+    IoProt = VI_ATTR_IO_PROT: u16, ReadWrite, Local, [];
+    DmaAllowEn = VI_ATTR_DMA_ALLOW_EN: bool, ReadWrite, Local, [];
+    RdBufOperMode = VI_ATTR_RD_BUF_OPER_MODE: u16, ReadWrite, Local, [];
+    RdBufSize = VI_ATTR_RD_BUF_SIZE: u32, ReadOnly, Local, [];
+    WrBufOperMode = VI_ATTR_WR_BUF_OPER_MODE: u16, ReadWrite, Local, [];
+    WrBufSize = VI_ATTR_WR_BUF_SIZE: u32, ReadOnly, Local, [];
+    FileAppendEn = VI_ATTR_FILE_APPEND_EN: bool, ReadWrite, Local, [];
+    Is4882Compliant = VI_ATTR_4882_COMPLIANT: bool, ReadOnly, Global, [];
+    DevStatusByte = VI_ATTR_DEV_STATUS_BYTE: u8, ReadWrite, Global, [GPIB, VXI, GPIB_VXI, TCPIP];
+
+    // Device identification
+    ManfId = VI_ATTR_MANF_ID: u16, ReadOnly, Global, [VXI, GPIB_VXI, PXI, USB];
+    ManfName = VI_ATTR_MANF_NAME: String, ReadOnly, Global, [VXI, GPIB_VXI, PXI, USB, TCPIP];
+    ModelCode = VI_ATTR_MODEL_CODE: u16, ReadOnly, Global, [VXI, GPIB_VXI, PXI, USB];
+    ModelName = VI_ATTR_MODEL_NAME: String, ReadOnly, Global, [VXI, GPIB_VXI, PXI, USB, TCPIP];
+
+    // GPIB
+    GpibPrimaryAddr = VI_ATTR_GPIB_PRIMARY_ADDR: u16, ReadOnly, Global, [GPIB];
+    GpibSecondaryAddr = VI_ATTR_GPIB_SECONDARY_ADDR: u16, ReadOnly, Global, [GPIB];
+    GpibReaddrEn = VI_ATTR_GPIB_READDR_EN: bool, ReadWrite, Local, [GPIB];
+    GpibUnaddrEn = VI_ATTR_GPIB_UNADDR_EN: bool, ReadWrite, Local, [GPIB];
+    GpibRenState = VI_ATTR_GPIB_REN_STATE: i16, ReadOnly, Global, [GPIB];
+    GpibAtnState = VI_ATTR_GPIB_ATN_STATE: i16, ReadOnly, Global, [GPIB];
+    GpibAddrState = VI_ATTR_GPIB_ADDR_STATE: i16, ReadOnly, Global, [GPIB];
+    GpibCicState = VI_ATTR_GPIB_CIC_STATE: bool, ReadOnly, Global, [GPIB];
+    GpibNdacState = VI_ATTR_GPIB_NDAC_STATE: i16, ReadOnly, Global, [GPIB];
+    GpibSrqState = VI_ATTR_GPIB_SRQ_STATE: i16, ReadOnly, Global, [GPIB];
+    GpibSysCntrlState = VI_ATTR_GPIB_SYS_CNTRL_STATE: bool, ReadWrite, Global, [GPIB];
+    GpibHs488CblLen = VI_ATTR_GPIB_HS488_CBL_LEN: i16, ReadWrite, Global, [GPIB];
+
+    // Serial
+    AsrlBaud = VI_ATTR_ASRL_BAUD: u32, ReadWrite, Global, [ASRL];
+    AsrlDataBits = VI_ATTR_ASRL_DATA_BITS: u16, ReadWrite, Global, [ASRL];
+    AsrlParity = VI_ATTR_ASRL_PARITY: u16, ReadWrite, Global, [ASRL];
+    AsrlStopBits = VI_ATTR_ASRL_STOP_BITS: u16, ReadWrite, Global, [ASRL];
+    AsrlFlowCntrl = VI_ATTR_ASRL_FLOW_CNTRL: u16, ReadWrite, Global, [ASRL];
+    AsrlAvailNum = VI_ATTR_ASRL_AVAIL_NUM: u32, ReadOnly, Global, [ASRL];
+    AsrlCtsState = VI_ATTR_ASRL_CTS_STATE: i16, ReadOnly, Global, [ASRL];
+    AsrlDcdState = VI_ATTR_ASRL_DCD_STATE: i16, ReadOnly, Global, [ASRL];
+    AsrlDsrState = VI_ATTR_ASRL_DSR_STATE: i16, ReadOnly, Global, [ASRL];
+    AsrlDtrState = VI_ATTR_ASRL_DTR_STATE: i16, ReadWrite, Global, [ASRL];
+    AsrlRiState = VI_ATTR_ASRL_RI_STATE: i16, ReadOnly, Global, [ASRL];
+    AsrlRtsState = VI_ATTR_ASRL_RTS_STATE: i16, ReadWrite, Global, [ASRL];
+    AsrlEndIn = VI_ATTR_ASRL_END_IN: u16, ReadWrite, Local, [ASRL];
+    AsrlEndOut = VI_ATTR_ASRL_END_OUT: u16, ReadWrite, Local, [ASRL];
+    AsrlReplaceChar = VI_ATTR_ASRL_REPLACE_CHAR: u8, ReadWrite, Local, [ASRL];
+    AsrlXonChar = VI_ATTR_ASRL_XON_CHAR: u8, ReadWrite, Global, [ASRL];
+    AsrlXoffChar = VI_ATTR_ASRL_XOFF_CHAR: u8, ReadWrite, Global, [ASRL];
+
+    // TCPIP
+    TcpipAddr = VI_ATTR_TCPIP_ADDR: String, ReadOnly, Global, [TCPIP];
+    TcpipHostname = VI_ATTR_TCPIP_HOSTNAME: String, ReadOnly, Global, [TCPIP];
+    TcpipPort = VI_ATTR_TCPIP_PORT: u16, ReadOnly, Global, [TCPIP];
+    TcpipDeviceName = VI_ATTR_TCPIP_DEVICE_NAME: String, ReadOnly, Global, [TCPIP];
+    TcpipNodelay = VI_ATTR_TCPIP_NODELAY: bool, ReadWrite, Local, [TCPIP];
+    TcpipKeepalive = VI_ATTR_TCPIP_KEEPALIVE: bool, ReadWrite, Local, [TCPIP];
+    TcpipIsHislip = VI_ATTR_TCPIP_IS_HISLIP: bool, ReadOnly, Global, [TCPIP];
+    TcpipHislipVersion = VI_ATTR_TCPIP_HISLIP_VERSION: u32, ReadOnly, Global, [TCPIP];
+    TcpipHislipOverlapEn = VI_ATTR_TCPIP_HISLIP_OVERLAP_EN: bool, ReadWrite, Global, [TCPIP];
+    TcpipHislipMaxMessageKb = VI_ATTR_TCPIP_HISLIP_MAX_MESSAGE_KB: u32, ReadWrite, Global, [TCPIP];
+    TcpipHislipEncryptionEn = VI_ATTR_TCPIP_HISLIP_ENCRYPTION_EN: bool, ReadWrite, Global, [TCPIP];
+    TcpipServerCertIssuerName = VI_ATTR_TCPIP_SERVER_CERT_ISSUER_NAME: String, ReadOnly, Global, [TCPIP];
+    TcpipServerCertSubjectName = VI_ATTR_TCPIP_SERVER_CERT_SUBJECT_NAME: String, ReadOnly, Global, [TCPIP];
+    TcpipServerCertExpirationDate = VI_ATTR_TCPIP_SERVER_CERT_EXPIRATION_DATE: String, ReadOnly, Global, [TCPIP];
+    TcpipServerCertIsPerpetual = VI_ATTR_TCPIP_SERVER_CERT_IS_PERPETUAL: bool, ReadOnly, Global, [TCPIP];
+    TcpipSaslMechanism = VI_ATTR_TCPIP_SASL_MECHANISM: String, ReadOnly, Global, [TCPIP];
+    TcpipTlsCipherSuite = VI_ATTR_TCPIP_TLS_CIPHER_SUITE: String, ReadOnly, Global, [TCPIP];
+
+    // USB
+    UsbSerialNum = VI_ATTR_USB_SERIAL_NUM: String, ReadOnly, Global, [USB];
+    UsbIntfcNum = VI_ATTR_USB_INTFC_NUM: i16, ReadOnly, Global, [USB];
+    UsbProtocol = VI_ATTR_USB_PROTOCOL: i16, ReadOnly, Global, [USB];
+    UsbMaxIntrSize = VI_ATTR_USB_MAX_INTR_SIZE: u16, ReadWrite, Local, [USB];
+
+    // VXI fast data channels
+    FdcChnl = VI_ATTR_FDC_CHNL: u16, ReadWrite, Local, [VXI, GPIB_VXI];
+    FdcMode = VI_ATTR_FDC_MODE: u16, ReadWrite, Local, [VXI, GPIB_VXI];
+    FdcGenSignalEn = VI_ATTR_FDC_GEN_SIGNAL_EN: bool, ReadWrite, Local, [VXI, GPIB_VXI];
+    FdcUsePair = VI_ATTR_FDC_USE_PAIR: bool, ReadWrite, Local, [VXI, GPIB_VXI];
+
+    // VXI, GPIB-VXI and register based access
+    VxiLa = VI_ATTR_VXI_LA: i16, ReadOnly, Global, [VXI, GPIB_VXI];
+    CmdrLa = VI_ATTR_CMDR_LA: i16, ReadOnly, Global, [VXI, GPIB_VXI];
+    MainframeLa = VI_ATTR_MAINFRAME_LA: i16, ReadOnly, Global, [VXI, GPIB_VXI];
+    Slot = VI_ATTR_SLOT: i16, ReadOnly, Global, [VXI, GPIB_VXI, PXI];
+    VxiDevClass = VI_ATTR_VXI_DEV_CLASS: u16, ReadOnly, Global, [VXI, GPIB_VXI];
+    ImmediateServ = VI_ATTR_IMMEDIATE_SERV: bool, ReadOnly, Global, [VXI, GPIB_VXI];
+    VxiTrigSupport = VI_ATTR_VXI_TRIG_SUPPORT: u32, ReadOnly, Global, [VXI, GPIB_VXI];
+    VxiVmeIntrStatus = VI_ATTR_VXI_VME_INTR_STATUS: u16, ReadOnly, Global, [VXI, GPIB_VXI];
+    VxiTrigStatus = VI_ATTR_VXI_TRIG_STATUS: u32, ReadOnly, Global, [VXI, GPIB_VXI];
+    VxiVmeSysfailState = VI_ATTR_VXI_VME_SYSFAIL_STATE: i16, ReadOnly, Global, [VXI, GPIB_VXI];
+    MemSpace = VI_ATTR_MEM_SPACE: u16, ReadOnly, Global, [VXI, GPIB_VXI];
+    MemBase32 = VI_ATTR_MEM_BASE_32: u32, ReadOnly, Global, [VXI, GPIB_VXI];
+    MemSize32 = VI_ATTR_MEM_SIZE_32: u32, ReadOnly, Global, [VXI, GPIB_VXI];
+    MemBase64 = VI_ATTR_MEM_BASE_64: u64, ReadOnly, Global, [VXI, GPIB_VXI];
+    MemSize64 = VI_ATTR_MEM_SIZE_64: u64, ReadOnly, Global, [VXI, GPIB_VXI];
+    WinAccess = VI_ATTR_WIN_ACCESS: u16, ReadOnly, Local, [VXI, GPIB_VXI, PXI];
+    WinBaseAddr32 = VI_ATTR_WIN_BASE_ADDR_32: u32, ReadOnly, Local, [VXI, GPIB_VXI, PXI];
+    WinSize32 = VI_ATTR_WIN_SIZE_32: u32, ReadOnly, Local, [VXI, GPIB_VXI, PXI];
+    WinBaseAddr64 = VI_ATTR_WIN_BASE_ADDR_64: u64, ReadOnly, Local, [VXI, GPIB_VXI, PXI];
+    WinSize64 = VI_ATTR_WIN_SIZE_64: u64, ReadOnly, Local, [VXI, GPIB_VXI, PXI];
+    WinAccessPriv = VI_ATTR_WIN_ACCESS_PRIV: u16, ReadWrite, Local, [VXI, GPIB_VXI];
+    WinByteOrder = VI_ATTR_WIN_BYTE_ORDER: u16, ReadWrite, Local, [VXI, GPIB_VXI, PXI];
+    SrcAccessPriv = VI_ATTR_SRC_ACCESS_PRIV: u16, ReadWrite, Local, [VXI, GPIB_VXI];
+    SrcByteOrder = VI_ATTR_SRC_BYTE_ORDER: u16, ReadWrite, Local, [VXI, GPIB_VXI, PXI];
+    SrcIncrement = VI_ATTR_SRC_INCREMENT: i32, ReadWrite, Local, [VXI, GPIB_VXI, PXI];
+    DestAccessPriv = VI_ATTR_DEST_ACCESS_PRIV: u16, ReadWrite, Local, [VXI, GPIB_VXI];
+    DestByteOrder = VI_ATTR_DEST_BYTE_ORDER: u16, ReadWrite, Local, [VXI, GPIB_VXI, PXI];
+    DestIncrement = VI_ATTR_DEST_INCREMENT: i32, ReadWrite, Local, [VXI, GPIB_VXI, PXI];
+
+    // PXI
+    PxiDevNum = VI_ATTR_PXI_DEV_NUM: u16, ReadOnly, Global, [PXI];
+    PxiFuncNum = VI_ATTR_PXI_FUNC_NUM: u16, ReadOnly, Global, [PXI];
+    PxiBusNum = VI_ATTR_PXI_BUS_NUM: u16, ReadOnly, Global, [PXI];
+    PxiChassis = VI_ATTR_PXI_CHASSIS: i16, ReadOnly, Global, [PXI];
+    PxiSlotpath = VI_ATTR_PXI_SLOTPATH: String, ReadOnly, Global, [PXI];
+    PxiSlotLbusLeft = VI_ATTR_PXI_SLOT_LBUS_LEFT: i16, ReadOnly, Global, [PXI];
+    PxiSlotLbusRight = VI_ATTR_PXI_SLOT_LBUS_RIGHT: i16, ReadOnly, Global, [PXI];
+    PxiTrigBus = VI_ATTR_PXI_TRIG_BUS: i16, ReadWrite, Global, [PXI];
+    PxiStarTrigBus = VI_ATTR_PXI_STAR_TRIG_BUS: i16, ReadOnly, Global, [PXI];
+    PxiStarTrigLine = VI_ATTR_PXI_STAR_TRIG_LINE: i16, ReadOnly, Global, [PXI];
+    PxiSrcTrigBus = VI_ATTR_PXI_SRC_TRIG_BUS: i16, ReadWrite, Global, [PXI];
+    PxiDestTrigBus = VI_ATTR_PXI_DEST_TRIG_BUS: i16, ReadWrite, Global, [PXI];
+    PxiMemTypeBar0 = VI_ATTR_PXI_MEM_TYPE_BAR0: u16, ReadOnly, Global, [PXI];
+    PxiMemTypeBar1 = VI_ATTR_PXI_MEM_TYPE_BAR1: u16, ReadOnly, Global, [PXI];
+    PxiMemTypeBar2 = VI_ATTR_PXI_MEM_TYPE_BAR2: u16, ReadOnly, Global, [PXI];
+    PxiMemTypeBar3 = VI_ATTR_PXI_MEM_TYPE_BAR3: u16, ReadOnly, Global, [PXI];
+    PxiMemTypeBar4 = VI_ATTR_PXI_MEM_TYPE_BAR4: u16, ReadOnly, Global, [PXI];
+    PxiMemTypeBar5 = VI_ATTR_PXI_MEM_TYPE_BAR5: u16, ReadOnly, Global, [PXI];
+    PxiMemBaseBar0_32 = VI_ATTR_PXI_MEM_BASE_BAR0_32: u32, ReadOnly, Global, [PXI];
+    PxiMemBaseBar1_32 = VI_ATTR_PXI_MEM_BASE_BAR1_32: u32, ReadOnly, Global, [PXI];
+    PxiMemBaseBar2_32 = VI_ATTR_PXI_MEM_BASE_BAR2_32: u32, ReadOnly, Global, [PXI];
+    PxiMemBaseBar3_32 = VI_ATTR_PXI_MEM_BASE_BAR3_32: u32, ReadOnly, Global, [PXI];
+    PxiMemBaseBar4_32 = VI_ATTR_PXI_MEM_BASE_BAR4_32: u32, ReadOnly, Global, [PXI];
+    PxiMemBaseBar5_32 = VI_ATTR_PXI_MEM_BASE_BAR5_32: u32, ReadOnly, Global, [PXI];
+    PxiMemSizeBar0_32 = VI_ATTR_PXI_MEM_SIZE_BAR0_32: u32, ReadOnly, Global, [PXI];
+    PxiMemSizeBar1_32 = VI_ATTR_PXI_MEM_SIZE_BAR1_32: u32, ReadOnly, Global, [PXI];
+    PxiMemSizeBar2_32 = VI_ATTR_PXI_MEM_SIZE_BAR2_32: u32, ReadOnly, Global, [PXI];
+    PxiMemSizeBar3_32 = VI_ATTR_PXI_MEM_SIZE_BAR3_32: u32, ReadOnly, Global, [PXI];
+    PxiMemSizeBar4_32 = VI_ATTR_PXI_MEM_SIZE_BAR4_32: u32, ReadOnly, Global, [PXI];
+    PxiMemSizeBar5_32 = VI_ATTR_PXI_MEM_SIZE_BAR5_32: u32, ReadOnly, Global, [PXI];
+    PxiMemBaseBar0_64 = VI_ATTR_PXI_MEM_BASE_BAR0_64: u64, ReadOnly, Global, [PXI];
+    PxiMemBaseBar1_64 = VI_ATTR_PXI_MEM_BASE_BAR1_64: u64, ReadOnly, Global, [PXI];
+    PxiMemBaseBar2_64 = VI_ATTR_PXI_MEM_BASE_BAR2_64: u64, ReadOnly, Global, [PXI];
+    PxiMemBaseBar3_64 = VI_ATTR_PXI_MEM_BASE_BAR3_64: u64, ReadOnly, Global, [PXI];
+    PxiMemBaseBar4_64 = VI_ATTR_PXI_MEM_BASE_BAR4_64: u64, ReadOnly, Global, [PXI];
+    PxiMemBaseBar5_64 = VI_ATTR_PXI_MEM_BASE_BAR5_64: u64, ReadOnly, Global, [PXI];
+    PxiMemSizeBar0_64 = VI_ATTR_PXI_MEM_SIZE_BAR0_64: u64, ReadOnly, Global, [PXI];
+    PxiMemSizeBar1_64 = VI_ATTR_PXI_MEM_SIZE_BAR1_64: u64, ReadOnly, Global, [PXI];
+    PxiMemSizeBar2_64 = VI_ATTR_PXI_MEM_SIZE_BAR2_64: u64, ReadOnly, Global, [PXI];
+    PxiMemSizeBar3_64 = VI_ATTR_PXI_MEM_SIZE_BAR3_64: u64, ReadOnly, Global, [PXI];
+    PxiMemSizeBar4_64 = VI_ATTR_PXI_MEM_SIZE_BAR4_64: u64, ReadOnly, Global, [PXI];
+    PxiMemSizeBar5_64 = VI_ATTR_PXI_MEM_SIZE_BAR5_64: u64, ReadOnly, Global, [PXI];
+    PxiIsExpress = VI_ATTR_PXI_IS_EXPRESS: bool, ReadOnly, Global, [PXI];
+    PxiSlotLwidth = VI_ATTR_PXI_SLOT_LWIDTH: i16, ReadOnly, Global, [PXI];
+    PxiMaxLwidth = VI_ATTR_PXI_MAX_LWIDTH: i16, ReadOnly, Global, [PXI];
+    PxiActualLwidth = VI_ATTR_PXI_ACTUAL_LWIDTH: i16, ReadOnly, Global, [PXI];
+    PxiDstarBus = VI_ATTR_PXI_DSTAR_BUS: i16, ReadOnly, Global, [PXI];
+    PxiDstarSet = VI_ATTR_PXI_DSTAR_SET: i16, ReadOnly, Global, [PXI];
+    PxiAllowWriteCombine = VI_ATTR_PXI_ALLOW_WRITE_COMBINE: bool, ReadWrite, Local, [PXI];
+
+    // Event contexts, read with `Event::get`
+    EventType = VI_ATTR_EVENT_TYPE: u32, ReadOnly, Local, [];
+    Status = VI_ATTR_STATUS: i32, ReadOnly, Local, [];
+    JobId = VI_ATTR_JOB_ID: u32, ReadOnly, Local, [];
+    RetCount32 = VI_ATTR_RET_COUNT_32: u32, ReadOnly, Local, [];
+    #[cfg(target_pointer_width = "64")]
+    RetCount64 = VI_ATTR_RET_COUNT_64: u64, ReadOnly, Local, [];
+    Buffer = VI_ATTR_BUFFER: usize, ReadOnly, Local, [];
+    OperName = VI_ATTR_OPER_NAME: String, ReadOnly, Local, [];
+    RecvTrigId = VI_ATTR_RECV_TRIG_ID: i16, ReadOnly, Local, [];
+    RecvIntrLevel = VI_ATTR_RECV_INTR_LEVEL: i16, ReadOnly, Local, [VXI, GPIB_VXI];
+    SigpStatusId = VI_ATTR_SIGP_STATUS_ID: u16, ReadOnly, Local, [VXI, GPIB_VXI];
+    IntrStatusId = VI_ATTR_INTR_STATUS_ID: u32, ReadOnly, Local, [VXI, GPIB_VXI];
+    GpibRecvCicState = VI_ATTR_GPIB_RECV_CIC_STATE: bool, ReadOnly, Local, [GPIB];
+    RecvTcpipAddr = VI_ATTR_RECV_TCPIP_ADDR: String, ReadOnly, Local, [TCPIP];
+    UsbRecvIntrSize = VI_ATTR_USB_RECV_INTR_SIZE: u16, ReadOnly, Local, [USB];
+    UsbRecvIntrData = VI_ATTR_USB_RECV_INTR_DATA: Vec<u8>, ReadOnly, Local, [USB];
+    PxiRecvIntrSeq = VI_ATTR_PXI_RECV_INTR_SEQ: i16, ReadOnly, Local, [PXI];
+    PxiRecvIntrData = VI_ATTR_PXI_RECV_INTR_DATA: u32, ReadOnly, Local, [PXI];
+}
+
+pointer_width_aliases! {
+    UserData = VI_ATTR_USER_DATA: UserData32 | UserData64;
+    MemBase = VI_ATTR_MEM_BASE: MemBase32 | MemBase64;
+    MemSize = VI_ATTR_MEM_SIZE: MemSize32 | MemSize64;
+    WinBaseAddr = VI_ATTR_WIN_BASE_ADDR: WinBaseAddr32 | WinBaseAddr64;
+    WinSize = VI_ATTR_WIN_SIZE: WinSize32 | WinSize64;
+    PxiMemBaseBar0 = VI_ATTR_PXI_MEM_BASE_BAR0: PxiMemBaseBar0_32 | PxiMemBaseBar0_64;
+    PxiMemBaseBar1 = VI_ATTR_PXI_MEM_BASE_BAR1: PxiMemBaseBar1_32 | PxiMemBaseBar1_64;
+    PxiMemBaseBar2 = VI_ATTR_PXI_MEM_BASE_BAR2: PxiMemBaseBar2_32 | PxiMemBaseBar2_64;
+    PxiMemBaseBar3 = VI_ATTR_PXI_MEM_BASE_BAR3: PxiMemBaseBar3_32 | PxiMemBaseBar3_64;
+    PxiMemBaseBar4 = VI_ATTR_PXI_MEM_BASE_BAR4: PxiMemBaseBar4_32 | PxiMemBaseBar4_64;
+    PxiMemBaseBar5 = VI_ATTR_PXI_MEM_BASE_BAR5: PxiMemBaseBar5_32 | PxiMemBaseBar5_64;
+    PxiMemSizeBar0 = VI_ATTR_PXI_MEM_SIZE_BAR0: PxiMemSizeBar0_32 | PxiMemSizeBar0_64;
+    PxiMemSizeBar1 = VI_ATTR_PXI_MEM_SIZE_BAR1: PxiMemSizeBar1_32 | PxiMemSizeBar1_64;
+    PxiMemSizeBar2 = VI_ATTR_PXI_MEM_SIZE_BAR2: PxiMemSizeBar2_32 | PxiMemSizeBar2_64;
+    PxiMemSizeBar3 = VI_ATTR_PXI_MEM_SIZE_BAR3: PxiMemSizeBar3_32 | PxiMemSizeBar3_64;
+    PxiMemSizeBar4 = VI_ATTR_PXI_MEM_SIZE_BAR4: PxiMemSizeBar4_32 | PxiMemSizeBar4_64;
+    PxiMemSizeBar5 = VI_ATTR_PXI_MEM_SIZE_BAR5: PxiMemSizeBar5_32 | PxiMemSizeBar5_64;
+    RetCount = VI_ATTR_RET_COUNT: RetCount32 | RetCount64;
+}
@@ -0,0 +1,114 @@
+//! VISA locking for backends that implement it in-process.
+
+use std::collections::HashMap;
+
+use crate::ffi::*;
+use crate::status::{completion, error, Completion, Result};
+
+/// The exclusive and shared locks held on one resource, with their nesting
+/// depth per session.
+#[derive(Debug, Default)]
+pub(crate) struct Locks {
+    exclusive: Option<(ViSession, u32)>,
+    shared: Option<(String, HashMap<ViSession, u32>)>,
+}
+
+impl Locks {
+    /// Whether `vi` may use the resource despite the locks.
+    pub(crate) fn accessible(&self, vi: ViSession) -> bool {
+        match (&self.exclusive, &self.shared) {
+            (Some((owner, _)), _) => *owner == vi,
+            (None, Some((_, holders))) => holders.contains_key(&vi),
+            (None, None) => true,
+        }
+    }
+
+    /// Acquires a lock for `vi` if the locks held allow it, `None` if it has
+    /// to wait. `key` is the access key requested for a shared lock, or the
+    /// one to generate.
+    pub(crate) fn try_lock(
+        &mut self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        key: String,
+    ) -> Option<Result<(Completion, String)>> {
+        if lock_type == VI_EXCLUSIVE_LOCK as ViAccessMode {
+            let others_share = self
+                .shared
+                .as_ref()
+                .is_some_and(|(_, holders)| holders.keys().any(|holder| *holder != vi));
+            match &mut self.exclusive {
+                Some((owner, depth)) if *owner == vi => {
+                    *depth += 1;
+                    Some(Ok((completion(VI_SUCCESS_NESTED_EXCLUSIVE), String::new())))
+                }
+                None if !others_share => {
+                    self.exclusive = Some((vi, 1));
+                    Some(Ok((Completion::SUCCESS, String::new())))
+                }
+                _ => None,
+            }
+        } else if lock_type == VI_SHARED_LOCK as ViAccessMode {
+            if self.exclusive.is_some_and(|(owner, _)| owner != vi) {
+                return None;
+            }
+            match &mut self.shared {
+                Some((held_key, holders)) => {
+                    if let Some(depth) = holders.get_mut(&vi) {
+                        *depth += 1;
+                        return Some(Ok((completion(VI_SUCCESS_NESTED_SHARED), held_key.clone())));
+                    }
+                    if *held_key != key {
+                        return None;
+                    }
+                    holders.insert(vi, 1);
+                    Some(Ok((Completion::SUCCESS, key)))
+                }
+                None => {
+                    self.shared = Some((key.clone(), HashMap::from([(vi, 1)])));
+                    Some(Ok((Completion::SUCCESS, key)))
+                }
+            }
+        } else {
+            Some(Err(error(VI_ERROR_INV_LOCK_TYPE)))
+        }
+    }
+
+    /// Releases one level of the locks of `vi`, the exclusive lock first as
+    /// in VISA.
+    pub(crate) fn unlock(&mut self, vi: ViSession) -> Result {
+        match (&mut self.exclusive, &mut self.shared) {
+            (Some((owner, depth)), _) if *owner == vi => {
+                *depth -= 1;
+                if *depth == 0 {
+                    self.exclusive = None;
+                }
+            }
+            (_, Some((_, holders))) if holders.contains_key(&vi) => {
+                let depth = holders.get_mut(&vi).unwrap();
+                *depth -= 1;
+                if *depth == 0 {
+                    holders.remove(&vi);
+                }
+                if holders.is_empty() {
+                    self.shared = None;
+                }
+            }
+            _ => return Err(error(VI_ERROR_SESN_NLOCKED)),
+        }
+        Ok(Completion::SUCCESS)
+    }
+
+    /// Releases every lock of `vi`, when it is closed.
+    pub(crate) fn release(&mut self, vi: ViSession) {
+        if self.exclusive.is_some_and(|(owner, _)| owner == vi) {
+            self.exclusive = None;
+        }
+        if let Some((_, holders)) = &mut self.shared {
+            holders.remove(&vi);
+            if holders.is_empty() {
+                self.shared = None;
+            }
+        }
+    }
+}
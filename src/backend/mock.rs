@@ -0,0 +1,868 @@
+//! An in-memory `VisaBackend` for tests.
+//!
+//! Resources are registered with a `MockDevice` that receives every write
+//! and returns the response to queue for reading, e.g. a closure:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use ni_visa_bindings::backend::mock::MockBackend;
+//! use ni_visa_bindings::attr::{self, Attribute};
+//! use ni_visa_bindings::ResourceManager;
+//!
+//! let mock = MockBackend::new()
+//!     .with_resource("GPIB0::5::INSTR", |command: &[u8]| match command {
+//!         b"MEAS:VOLT?\n" => Some(b"+1.25E+00\n".to_vec()),
+//!         _ => None,
+//!     })
+//!     .with_attribute("GPIB0::5::INSTR", attr::ModelName::ID, "X1");
+//! let rm = ResourceManager::with_backend(Arc::new(mock)).unwrap();
+//! let mut session = rm.open("GPIB0::5::INSTR").unwrap();
+//! assert_eq!(session.get::<attr::ModelName>().unwrap(), "X1");
+//! assert_eq!(session.query_ascii_values::<f64>("MEAS:VOLT?").unwrap(), [1.25]);
+//! ```
+//!
+//! The mock answers at once: a read with no response queued fails with
+//! `VI_ERROR_TMO` without waiting. Reads honor `VI_ATTR_TERMCHAR_EN`, locks
+//! and events behave as in VISA, and asynchronous jobs complete before
+//! `viReadAsync`/`viWriteAsync` return. Search expressions support `?`, `*`,
+//! `+`, `[...]` and `|`; attribute filters are ignored.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use super::locks::Locks;
+use super::VisaBackend;
+use crate::attr::{self, Access, Scope};
+use crate::ffi::*;
+use crate::resource::ResourceName;
+use crate::status::{completion, error, Completion, Result};
+
+/// A simulated instrument.
+pub trait MockDevice: Send {
+    /// Handles the data of one `viWrite`, returning the response to queue
+    /// for reading, if any.
+    fn write(&mut self, data: &[u8]) -> Option<Vec<u8>>;
+
+    /// The status byte returned by `viReadSTB`.
+    fn status_byte(&mut self) -> u8 {
+        0
+    }
+
+    /// Whether the device requests service, checked after every write. If
+    /// so, `VI_EVENT_SERVICE_REQ` is raised on the sessions to it.
+    fn service_request(&mut self) -> bool {
+        false
+    }
+}
+
+impl<F> MockDevice for F
+where
+    F: FnMut(&[u8]) -> Option<Vec<u8>> + Send,
+{
+    fn write(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        self(data)
+    }
+}
+
+/// An attribute value held by the mock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockValue {
+    /// Any numeric or boolean attribute, truncated to its width when read.
+    Number(u64),
+    String(String),
+    /// A variable-length attribute such as `VI_ATTR_USB_RECV_INTR_DATA`.
+    Bytes(Vec<u8>),
+}
+
+macro_rules! number_values {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for MockValue {
+                fn from(value: $ty) -> Self {
+                    // Signed values are sign-extended, as by `viSetAttribute`.
+                    MockValue::Number(value as u64)
+                }
+            }
+        )*
+    };
+}
+
+number_values!(u8, u16, u32, u64, i16, i32, i64, usize);
+
+impl From<bool> for MockValue {
+    fn from(value: bool) -> Self {
+        MockValue::Number(value as u64)
+    }
+}
+
+impl From<&str> for MockValue {
+    fn from(value: &str) -> Self {
+        MockValue::String(value.to_string())
+    }
+}
+
+impl From<String> for MockValue {
+    fn from(value: String) -> Self {
+        MockValue::String(value)
+    }
+}
+
+impl From<Vec<u8>> for MockValue {
+    fn from(value: Vec<u8>) -> Self {
+        MockValue::Bytes(value)
+    }
+}
+
+impl MockValue {
+    fn write_to(&self, out: &mut [u8]) -> Result {
+        match self {
+            MockValue::Number(value) => match out.len() {
+                1 => out.copy_from_slice(&(*value as u8).to_ne_bytes()),
+                2 => out.copy_from_slice(&(*value as u16).to_ne_bytes()),
+                4 => out.copy_from_slice(&(*value as u32).to_ne_bytes()),
+                8 => out.copy_from_slice(&value.to_ne_bytes()),
+                _ => return Err(error(VI_ERROR_INV_PARAMETER)),
+            },
+            MockValue::String(value) => {
+                let len = value.len().min(out.len().saturating_sub(1));
+                out[..len].copy_from_slice(&value.as_bytes()[..len]);
+                if let Some(nul) = out.get_mut(len) {
+                    *nul = 0;
+                }
+            }
+            MockValue::Bytes(value) => {
+                let len = value.len().min(out.len());
+                out[..len].copy_from_slice(&value[..len]);
+            }
+        }
+        Ok(Completion::SUCCESS)
+    }
+}
+
+/// An in-memory VISA implementation, see the module documentation.
+pub struct MockBackend {
+    state: Mutex<State>,
+    /// Signalled when events are queued or locks released.
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    next_handle: ViObject,
+    next_job: ViJobId,
+    next_key: u32,
+    resources: Vec<Resource>,
+    objects: HashMap<ViObject, Object>,
+}
+
+struct Resource {
+    name: String,
+    device: Box<dyn MockDevice>,
+    attributes: HashMap<ViAttr, MockValue>,
+    /// Responses not read yet, the first one from `read_pos` on.
+    output: VecDeque<Vec<u8>>,
+    read_pos: usize,
+    locks: Locks,
+}
+
+enum Object {
+    Rm,
+    Session(Session),
+    FindList(VecDeque<String>),
+    Event(HashMap<ViAttr, MockValue>),
+}
+
+struct Session {
+    resource: usize,
+    attributes: HashMap<ViAttr, MockValue>,
+    /// Enabled mechanisms (`VI_QUEUE`, `VI_HNDLR`, `VI_SUSPEND_HNDLR`) by event type.
+    enabled: HashMap<ViEventType, ViUInt16>,
+    queue: VecDeque<(ViEventType, ViEvent)>,
+    /// Installed handlers, with the user handle as an address.
+    handlers: Vec<(ViEventType, ViHndlr, usize)>,
+}
+
+/// A handler to call once the state is unlocked.
+struct HandlerCall {
+    vi: ViSession,
+    event_type: ViEventType,
+    context: ViEvent,
+    handler: ViHndlr,
+    user_handle: usize,
+}
+
+fn canonical(name: &str) -> String {
+    name.parse::<ResourceName>().map_or_else(|_| name.to_string(), |name| name.to_string())
+}
+
+impl MockBackend {
+    /// A mock without resources.
+    pub fn new() -> Self {
+        MockBackend {
+            state: Mutex::new(State {
+                next_handle: 1,
+                next_job: 1,
+                ..State::default()
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Adds resource `name`, e.g. `"TCPIP0::10.0.0.2::INSTR"`, simulated by
+    /// `device`.
+    pub fn with_resource(self, name: &str, device: impl MockDevice + 'static) -> Self {
+        let name = canonical(name);
+        let mut attributes = HashMap::new();
+        attributes.insert(VI_ATTR_RSRC_NAME as ViAttr, MockValue::from(name.as_str()));
+        if let Ok(parsed) = name.parse::<ResourceName>() {
+            if let Some(intf_type) = parsed.interface_type() {
+                attributes.insert(VI_ATTR_INTF_TYPE as ViAttr, intf_type.into());
+            }
+            if let Some(board) = parsed.board() {
+                attributes.insert(VI_ATTR_INTF_NUM as ViAttr, board.into());
+            }
+            if let Some(class) = parsed.resource_class() {
+                attributes.insert(VI_ATTR_RSRC_CLASS as ViAttr, class.as_str().into());
+            }
+        }
+        self.lock_state().resources.push(Resource {
+            name,
+            device: Box::new(device),
+            attributes,
+            output: VecDeque::new(),
+            read_pos: 0,
+            locks: Locks::default(),
+        });
+        self
+    }
+
+    /// Sets an attribute of resource `name` seen by every session to it,
+    /// e.g. a read-only one such as `VI_ATTR_MODEL_NAME`.
+    ///
+    /// # Panics
+    ///
+    /// If `name` was not added with `with_resource`.
+    pub fn with_attribute(self, name: &str, attr: ViAttr, value: impl Into<MockValue>) -> Self {
+        {
+            let mut state = self.lock_state();
+            let index = state.find_resource(name).expect("unknown mock resource");
+            state.resources[index].attributes.insert(attr, value.into());
+        }
+        self
+    }
+
+    /// Raises `event_type` on the sessions to resource `name` that enabled
+    /// it, with `attributes` in the event context besides
+    /// `VI_ATTR_EVENT_TYPE`. Handlers run before this returns.
+    pub fn raise_event(&self, name: &str, event_type: ViEventType, attributes: &[(ViAttr, MockValue)]) -> Result {
+        let calls = {
+            let mut state = self.lock_state();
+            let index = state.find_resource(name).ok_or_else(|| error(VI_ERROR_RSRC_NFOUND))?;
+            state.raise(|_, session| session.resource == index, event_type, attributes)
+        };
+        self.call_handlers(calls);
+        Ok(Completion::SUCCESS)
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Calls handlers outside the lock, as they may call back into the mock,
+    /// then closes their contexts.
+    fn call_handlers(&self, calls: Vec<HandlerCall>) {
+        self.changed.notify_all();
+        for call in calls {
+            if let Some(handler) = call.handler {
+                unsafe {
+                    handler(call.vi, call.event_type, call.context, call.user_handle as ViAddr);
+                }
+            }
+            self.lock_state().objects.remove(&call.context);
+        }
+    }
+
+    /// Waits on the condition variable until `ready` returns a value or
+    /// `timeout` milliseconds pass.
+    fn wait_until<T>(
+        &self,
+        timeout: ViUInt32,
+        mut ready: impl FnMut(&mut State) -> Option<Result<T>>,
+    ) -> Result<T> {
+        let deadline = Instant::now() + Duration::from_millis(timeout.into());
+        let mut state = self.lock_state();
+        loop {
+            if let Some(result) = ready(&mut state) {
+                return result;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(error(VI_ERROR_TMO));
+            }
+            state = self
+                .changed
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Acquires a lock on `index` for `vi` if the locks held allow it.
+    fn try_lock(
+        state: &mut State,
+        vi: ViSession,
+        index: usize,
+        lock_type: ViAccessMode,
+        requested_key: Option<&str>,
+    ) -> Option<Result<(Completion, String)>> {
+        let key = requested_key.map(str::to_string).unwrap_or_else(|| {
+            state.next_key += 1;
+            format!("mock-key-{}", state.next_key)
+        });
+        state.resources[index].locks.try_lock(vi, lock_type, key)
+    }
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        MockBackend::new()
+    }
+}
+
+impl fmt::Debug for MockBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock_state();
+        let names: Vec<&str> = state.resources.iter().map(|resource| resource.name.as_str()).collect();
+        f.debug_struct("MockBackend").field("resources", &names).finish_non_exhaustive()
+    }
+}
+
+impl State {
+    fn handle(&mut self, object: Object) -> ViObject {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.objects.insert(handle, object);
+        handle
+    }
+
+    fn find_resource(&self, name: &str) -> Option<usize> {
+        let name = canonical(name);
+        self.resources.iter().position(|resource| resource.name.eq_ignore_ascii_case(&name))
+    }
+
+    fn session(&mut self, vi: ViSession) -> Result<&mut Session> {
+        match self.objects.get_mut(&vi) {
+            Some(Object::Session(session)) => Ok(session),
+            _ => Err(error(VI_ERROR_INV_OBJECT)),
+        }
+    }
+
+    /// The resource of `vi`, if `vi` may access it.
+    fn accessible_resource(&mut self, vi: ViSession) -> Result<usize> {
+        let index = self.session(vi)?.resource;
+        if !self.resources[index].locks.accessible(vi) {
+            return Err(error(VI_ERROR_RSRC_LOCKED));
+        }
+        Ok(index)
+    }
+
+    fn session_number(&mut self, vi: ViSession, attr: u32) -> Result<u64> {
+        let session = self.session(vi)?;
+        match session.attributes.get(&(attr as ViAttr)) {
+            Some(MockValue::Number(value)) => Ok(*value),
+            _ => Err(error(VI_ERROR_NSUP_ATTR)),
+        }
+    }
+
+    /// Queues the event on, or prepares the handler calls for, the sessions
+    /// `target` selects.
+    fn raise(
+        &mut self,
+        target: impl Fn(ViSession, &Session) -> bool,
+        event_type: ViEventType,
+        attributes: &[(ViAttr, MockValue)],
+    ) -> Vec<HandlerCall> {
+        let mut context: HashMap<ViAttr, MockValue> = attributes.iter().cloned().collect();
+        context.insert(VI_ATTR_EVENT_TYPE as ViAttr, event_type.into());
+
+        let mut deliveries = Vec::new();
+        for (&vi, object) in &self.objects {
+            let Object::Session(session) = object else {
+                continue;
+            };
+            if !target(vi, session) {
+                continue;
+            }
+            let enabled = session.enabled.get(&event_type).copied().unwrap_or(0);
+            if enabled & VI_QUEUE as ViUInt16 != 0 {
+                deliveries.push((vi, None));
+            }
+            if enabled & VI_HNDLR as ViUInt16 != 0 {
+                for &(handler_type, handler, user_handle) in &session.handlers {
+                    if handler_type == event_type {
+                        deliveries.push((vi, Some((handler, user_handle))));
+                    }
+                }
+            }
+        }
+
+        let mut calls = Vec::new();
+        for (vi, handler) in deliveries {
+            let handle = self.handle(Object::Event(context.clone()));
+            match handler {
+                None => {
+                    if let Ok(session) = self.session(vi) {
+                        session.queue.push_back((event_type, handle));
+                    }
+                }
+                Some((handler, user_handle)) => calls.push(HandlerCall {
+                    vi,
+                    event_type,
+                    context: handle,
+                    handler,
+                    user_handle,
+                }),
+            }
+        }
+        calls
+    }
+
+    fn read(&mut self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let index = self.accessible_resource(vi)?;
+        let term_char = match self.session_number(vi, VI_ATTR_TERMCHAR_EN) {
+            Ok(enabled) if enabled != 0 => Some(self.session_number(vi, VI_ATTR_TERMCHAR)? as u8),
+            _ => None,
+        };
+        let resource = &mut self.resources[index];
+        let Some(message) = resource.output.front() else {
+            return Err(error(VI_ERROR_TMO));
+        };
+        let available = &message[resource.read_pos..];
+        let window = &available[..available.len().min(buf.len())];
+        let (count, status) = match term_char.and_then(|c| window.iter().position(|&b| b == c)) {
+            Some(position) => (position + 1, VI_SUCCESS_TERM_CHAR),
+            None if available.len() <= buf.len() => (available.len(), VI_SUCCESS),
+            None => (buf.len(), VI_SUCCESS_MAX_CNT),
+        };
+        buf[..count].copy_from_slice(&available[..count]);
+        resource.read_pos += count;
+        if resource.read_pos == message.len() {
+            resource.output.pop_front();
+            resource.read_pos = 0;
+        }
+        Ok((count, completion(status)))
+    }
+
+    /// Writes to the device, returning whether it requests service.
+    fn write(&mut self, vi: ViSession, data: &[u8]) -> Result<bool> {
+        let index = self.accessible_resource(vi)?;
+        let resource = &mut self.resources[index];
+        if let Some(response) = resource.device.write(data) {
+            resource.output.push_back(response);
+        }
+        Ok(resource.device.service_request())
+    }
+
+    /// Completes a new job of `vi` with `result`, raising
+    /// `VI_EVENT_IO_COMPLETION`.
+    fn complete_job(&mut self, vi: ViSession, result: Result<usize>) -> (ViJobId, Vec<HandlerCall>) {
+        let job_id = self.next_job;
+        self.next_job += 1;
+        let (status, count) = match result {
+            Ok(count) => (VI_SUCCESS as ViStatus, count),
+            Err(e) => (e.raw(), 0),
+        };
+        let attributes = [
+            (VI_ATTR_JOB_ID as ViAttr, MockValue::from(job_id)),
+            (VI_ATTR_STATUS as ViAttr, MockValue::from(status)),
+            (VI_ATTR_RET_COUNT as ViAttr, MockValue::from(count)),
+        ];
+        let calls = self.raise(|target, _| target == vi, VI_EVENT_IO_COMPLETION, &attributes);
+        (job_id, calls)
+    }
+}
+
+/// Matches a VISA search expression against a resource name.
+fn matches(expr: &str, name: &str) -> bool {
+    let pattern = expr.split('{').next().unwrap_or_default().trim();
+    let name: Vec<char> = name.to_ascii_uppercase().chars().collect();
+    pattern.split('|').any(|alternative| {
+        let tokens = tokenize(&alternative.to_ascii_uppercase());
+        match_tokens(&tokens, &name)
+    })
+}
+
+#[derive(Debug)]
+enum Atom {
+    Any,
+    Char(char),
+    Class(Vec<char>, bool),
+}
+
+impl Atom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Atom::Any => true,
+            Atom::Char(expected) => *expected == c,
+            Atom::Class(chars, negated) => {
+                let mut found = false;
+                let mut i = 0;
+                while i < chars.len() {
+                    if i + 2 < chars.len() && chars[i + 1] == '-' {
+                        found |= (chars[i]..=chars[i + 2]).contains(&c);
+                        i += 3;
+                    } else {
+                        found |= chars[i] == c;
+                        i += 1;
+                    }
+                }
+                found != *negated
+            }
+        }
+    }
+}
+
+/// An atom and the least and most times it repeats.
+type Token = (Atom, usize, usize);
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let mut chars = pattern.chars().peekable();
+    let mut tokens: Vec<Token> = Vec::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '+' if !tokens.is_empty() => {
+                let token = tokens.last_mut().unwrap();
+                token.1 = if c == '*' { 0 } else { 1 };
+                token.2 = usize::MAX;
+            }
+            '?' => tokens.push((Atom::Any, 1, 1)),
+            '[' => {
+                let negated = chars.next_if_eq(&'^').is_some();
+                let class: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                tokens.push((Atom::Class(class, negated), 1, 1));
+            }
+            '\\' => tokens.push((Atom::Char(chars.next().unwrap_or('\\')), 1, 1)),
+            c => tokens.push((Atom::Char(c), 1, 1)),
+        }
+    }
+    tokens
+}
+
+fn match_tokens(tokens: &[Token], name: &[char]) -> bool {
+    let Some(((atom, min, max), rest)) = tokens.split_first() else {
+        return name.is_empty();
+    };
+    let mut count = 0;
+    loop {
+        if count >= *min && match_tokens(rest, &name[count..]) {
+            return true;
+        }
+        if count == *max || count == name.len() || !atom.matches(name[count]) {
+            return false;
+        }
+        count += 1;
+    }
+}
+
+impl VisaBackend for MockBackend {
+    fn open_default_rm(&self) -> Result<ViSession> {
+        Ok(self.lock_state().handle(Object::Rm))
+    }
+
+    fn open(&self, rm: ViSession, resource: &str, access_mode: ViAccessMode, timeout: ViUInt32) -> Result<ViSession> {
+        let vi = {
+            let mut state = self.lock_state();
+            if !matches!(state.objects.get(&rm), Some(Object::Rm)) {
+                return Err(error(VI_ERROR_INV_OBJECT));
+            }
+            let index = state.find_resource(resource).ok_or_else(|| error(VI_ERROR_RSRC_NFOUND))?;
+            let attributes = HashMap::from([
+                (VI_ATTR_TMO_VALUE as ViAttr, MockValue::from(2000u32)),
+                (VI_ATTR_TERMCHAR as ViAttr, MockValue::from(b'\n')),
+                (VI_ATTR_TERMCHAR_EN as ViAttr, MockValue::from(false)),
+                (VI_ATTR_SEND_END_EN as ViAttr, MockValue::from(true)),
+            ]);
+            state.handle(Object::Session(Session {
+                resource: index,
+                attributes,
+                enabled: HashMap::new(),
+                queue: VecDeque::new(),
+                handlers: Vec::new(),
+            }))
+        };
+        if access_mode & VI_EXCLUSIVE_LOCK as ViAccessMode != 0 {
+            if let Err(e) = self.lock(vi, VI_EXCLUSIVE_LOCK as ViAccessMode, timeout, None) {
+                self.lock_state().objects.remove(&vi);
+                return Err(e);
+            }
+        }
+        Ok(vi)
+    }
+
+    fn close(&self, object: ViObject) -> Result {
+        let mut state = self.lock_state();
+        match state.objects.remove(&object) {
+            Some(Object::Session(session)) => {
+                state.resources[session.resource].locks.release(object);
+                for (_, context) in session.queue {
+                    state.objects.remove(&context);
+                }
+                drop(state);
+                self.changed.notify_all();
+                Ok(Completion::SUCCESS)
+            }
+            Some(_) => Ok(Completion::SUCCESS),
+            None => Err(error(VI_ERROR_INV_OBJECT)),
+        }
+    }
+
+    fn find_rsrc(&self, rm: ViSession, expr: &str) -> Result<(ViFindList, ViUInt32, String)> {
+        let mut state = self.lock_state();
+        if !matches!(state.objects.get(&rm), Some(Object::Rm)) {
+            return Err(error(VI_ERROR_INV_OBJECT));
+        }
+        let mut found: VecDeque<String> = state
+            .resources
+            .iter()
+            .filter(|resource| matches(expr, &resource.name))
+            .map(|resource| resource.name.clone())
+            .collect();
+        let count = found.len() as ViUInt32;
+        let first = found.pop_front().ok_or_else(|| error(VI_ERROR_RSRC_NFOUND))?;
+        let list = state.handle(Object::FindList(found));
+        Ok((list, count, first))
+    }
+
+    fn find_next(&self, list: ViFindList) -> Result<String> {
+        match self.lock_state().objects.get_mut(&list) {
+            Some(Object::FindList(found)) => found.pop_front().ok_or_else(|| error(VI_ERROR_RSRC_NFOUND)),
+            _ => Err(error(VI_ERROR_INV_OBJECT)),
+        }
+    }
+
+    fn get_attribute(&self, object: ViObject, attr: ViAttr, value: &mut [u8]) -> Result {
+        let state = self.lock_state();
+        let found = match state.objects.get(&object) {
+            Some(Object::Session(session)) => session
+                .attributes
+                .get(&attr)
+                .or_else(|| state.resources[session.resource].attributes.get(&attr)),
+            Some(Object::Event(attributes)) => attributes.get(&attr),
+            Some(_) => None,
+            None => return Err(error(VI_ERROR_INV_OBJECT)),
+        };
+        found.ok_or_else(|| error(VI_ERROR_NSUP_ATTR))?.write_to(value)
+    }
+
+    fn set_attribute(&self, object: ViObject, attr: ViAttr, value: ViAttrState) -> Result {
+        let info = attr::info(attr);
+        if info.is_some_and(|info| info.access == Access::ReadOnly) {
+            return Err(error(VI_ERROR_ATTR_READONLY));
+        }
+        let mut state = self.lock_state();
+        let index = state.session(object)?.resource;
+        let value = MockValue::Number(value);
+        if info.is_some_and(|info| info.scope == Scope::Global) {
+            state.resources[index].attributes.insert(attr, value);
+        } else {
+            state.session(object)?.attributes.insert(attr, value);
+        }
+        Ok(Completion::SUCCESS)
+    }
+
+    fn read(&self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        self.lock_state().read(vi, buf)
+    }
+
+    fn write(&self, vi: ViSession, data: &[u8]) -> Result<usize> {
+        let calls = {
+            let mut state = self.lock_state();
+            let index = state.session(vi)?.resource;
+            if !state.write(vi, data)? {
+                return Ok(data.len());
+            }
+            state.raise(|_, session| session.resource == index, VI_EVENT_SERVICE_REQ, &[])
+        };
+        self.call_handlers(calls);
+        Ok(data.len())
+    }
+
+    fn read_stb(&self, vi: ViSession) -> Result<ViUInt16> {
+        let mut state = self.lock_state();
+        let index = state.accessible_resource(vi)?;
+        Ok(state.resources[index].device.status_byte().into())
+    }
+
+    fn enable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        let mut state = self.lock_state();
+        let session = state.session(vi)?;
+        let enabled = session.enabled.entry(event_type).or_default();
+        if *enabled & mechanism == mechanism {
+            return Ok(completion(VI_SUCCESS_EVENT_EN));
+        }
+        if mechanism & (VI_HNDLR | VI_SUSPEND_HNDLR) as ViUInt16 != 0 {
+            *enabled &= !((VI_HNDLR | VI_SUSPEND_HNDLR) as ViUInt16);
+        }
+        *enabled |= mechanism;
+        Ok(Completion::SUCCESS)
+    }
+
+    fn disable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        let mut state = self.lock_state();
+        let session = state.session(vi)?;
+        let mut disabled = false;
+        for (&kind, enabled) in session.enabled.iter_mut() {
+            if (event_type == VI_ALL_ENABLED_EVENTS || kind == event_type) && *enabled & mechanism != 0 {
+                *enabled &= !mechanism;
+                disabled = true;
+            }
+        }
+        if disabled {
+            Ok(Completion::SUCCESS)
+        } else {
+            Ok(completion(VI_SUCCESS_EVENT_DIS))
+        }
+    }
+
+    fn discard_events(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        let mut state = self.lock_state();
+        if mechanism & VI_QUEUE as ViUInt16 == 0 {
+            return Ok(Completion::SUCCESS);
+        }
+        let session = state.session(vi)?;
+        let (discarded, kept) = session
+            .queue
+            .drain(..)
+            .partition(|(kind, _)| event_type == VI_ALL_ENABLED_EVENTS || *kind == event_type);
+        session.queue = kept;
+        let discarded: Vec<(ViEventType, ViEvent)> = discarded.into_iter().collect();
+        if discarded.is_empty() {
+            return Ok(completion(VI_SUCCESS_QUEUE_EMPTY));
+        }
+        for (_, context) in discarded {
+            state.objects.remove(&context);
+        }
+        Ok(Completion::SUCCESS)
+    }
+
+    fn wait_on_event(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        timeout: ViUInt32,
+    ) -> Result<(ViEventType, ViEvent)> {
+        self.wait_until(timeout, |state| {
+            let session = match state.session(vi) {
+                Ok(session) => session,
+                Err(e) => return Some(Err(e)),
+            };
+            let any = event_type == VI_ALL_ENABLED_EVENTS;
+            let queued = session.enabled.get(&event_type).is_some_and(|m| m & VI_QUEUE as ViUInt16 != 0);
+            if !any && !queued {
+                return Some(Err(error(VI_ERROR_NENABLED)));
+            }
+            let position = session.queue.iter().position(|(kind, _)| any || *kind == event_type)?;
+            session.queue.remove(position).map(Ok)
+        })
+    }
+
+    unsafe fn install_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result {
+        let mut state = self.lock_state();
+        state.session(vi)?.handlers.push((event_type, handler, user_handle as usize));
+        Ok(Completion::SUCCESS)
+    }
+
+    unsafe fn uninstall_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result {
+        let mut state = self.lock_state();
+        let session = state.session(vi)?;
+        let position = session
+            .handlers
+            .iter()
+            .position(|&(kind, installed, user)| {
+                let same_handler = installed.map(|f| f as usize) == handler.map(|f| f as usize);
+                kind == event_type && same_handler && user == user_handle as usize
+            })
+            .ok_or_else(|| error(VI_ERROR_INV_HNDLR_REF))?;
+        session.handlers.remove(position);
+        Ok(Completion::SUCCESS)
+    }
+
+    fn lock(
+        &self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<&str>,
+    ) -> Result<(Completion, String)> {
+        self.wait_until(timeout, |state| {
+            let index = match state.session(vi) {
+                Ok(session) => session.resource,
+                Err(e) => return Some(Err(e)),
+            };
+            MockBackend::try_lock(state, vi, index, lock_type, requested_key)
+        })
+    }
+
+    fn unlock(&self, vi: ViSession) -> Result {
+        let mut state = self.lock_state();
+        let index = state.session(vi)?.resource;
+        let completion = state.resources[index].locks.unlock(vi)?;
+        drop(state);
+        self.changed.notify_all();
+        Ok(completion)
+    }
+
+    unsafe fn read_async(&self, vi: ViSession, buf: *mut u8, len: usize) -> Result<ViJobId> {
+        let buf = unsafe { std::slice::from_raw_parts_mut(buf, len) };
+        let (job_id, calls) = {
+            let mut state = self.lock_state();
+            state.session(vi)?;
+            let result = state.read(vi, buf).map(|(count, _)| count);
+            state.complete_job(vi, result)
+        };
+        self.call_handlers(calls);
+        Ok(job_id)
+    }
+
+    unsafe fn write_async(&self, vi: ViSession, data: *const u8, len: usize) -> Result<ViJobId> {
+        let data = unsafe { std::slice::from_raw_parts(data, len) };
+        let (job_id, calls) = {
+            let mut state = self.lock_state();
+            let index = state.session(vi)?.resource;
+            let (result, mut calls) = match state.write(vi, data) {
+                Ok(true) => {
+                    let calls = state.raise(|_, session| session.resource == index, VI_EVENT_SERVICE_REQ, &[]);
+                    (Ok(len), calls)
+                }
+                Ok(false) => (Ok(len), Vec::new()),
+                Err(e) => (Err(e), Vec::new()),
+            };
+            let (job_id, completed) = state.complete_job(vi, result);
+            calls.extend(completed);
+            (job_id, calls)
+        };
+        self.call_handlers(calls);
+        Ok(job_id)
+    }
+
+    fn terminate(&self, vi: ViSession, _degree: ViUInt16, _job_id: ViJobId) -> Result {
+        // Jobs complete before they are returned, so there is nothing left
+        // to abort.
+        self.lock_state().session(vi)?;
+        Ok(Completion::SUCCESS)
+    }
+}
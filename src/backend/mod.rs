@@ -0,0 +1,312 @@
+//! The VISA calls behind the safe API.
+//!
+//! Every safe wrapper goes through the `VisaBackend` of its resource
+//! manager. `FfiBackend`, used by `ResourceManager::new`, forwards to the
+//! VISA library; `mock::MockBackend` keeps instruments in memory so drivers
+//! can be tested without hardware:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use ni_visa_bindings::backend::mock::MockBackend;
+//! use ni_visa_bindings::ResourceManager;
+//!
+//! let mock = MockBackend::new().with_resource("TCPIP0::10.0.0.2::INSTR", |command: &[u8]| {
+//!     (command == b"*IDN?\n").then(|| b"ACME,X1,123,1.0\n".to_vec())
+//! });
+//! let rm = ResourceManager::with_backend(Arc::new(mock)).unwrap();
+//! let mut session = rm.open("TCPIP::10.0.0.2::INSTR").unwrap();
+//! assert_eq!(session.query("*IDN?").unwrap(), "ACME,X1,123,1.0");
+//! ```
+//!
+//! Handles are plain numbers chosen by the backend, so the raw handles of a
+//! session opened on another backend must not be passed to `ffi`.
+
+mod locks;
+pub mod mock;
+#[cfg(feature = "record")]
+pub mod record;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "socket")]
+pub mod socket;
+
+use std::ffi::{c_void, CStr, CString};
+use std::fmt;
+
+use crate::attr;
+use crate::ffi::*;
+use crate::status::{check, error, Completion, Result};
+
+/// The VISA operations used by the safe API, with Rust types in place of
+/// out-parameters.
+///
+/// Statuses keep their VISA meaning: e.g. `read` must report
+/// `VI_SUCCESS_MAX_CNT` when it stops on the count, and `lock` the
+/// `VI_SUCCESS_NESTED_*` codes.
+pub trait VisaBackend: fmt::Debug + Send + Sync {
+    /// `viOpenDefaultRM`
+    fn open_default_rm(&self) -> Result<ViSession>;
+
+    /// `viOpen`
+    fn open(&self, rm: ViSession, resource: &str, access_mode: ViAccessMode, timeout: ViUInt32) -> Result<ViSession>;
+
+    /// `viClose`, for sessions, find lists and event contexts.
+    fn close(&self, object: ViObject) -> Result;
+
+    /// `viFindRsrc`: the find list, the number of matches and the first one.
+    fn find_rsrc(&self, rm: ViSession, expr: &str) -> Result<(ViFindList, ViUInt32, String)>;
+
+    /// `viFindNext`
+    fn find_next(&self, list: ViFindList) -> Result<String>;
+
+    /// `viGetAttribute`. `value` holds the attribute in its native
+    /// representation: 1, 2, 4 or 8 bytes for numbers, a `VI_FIND_BUFLEN`
+    /// buffer for strings, or the buffer of a variable-length attribute.
+    /// `FfiBackend` fails with `VI_ERROR_INV_PARAMETER` on a smaller buffer
+    /// rather than let VISA write past its end.
+    fn get_attribute(&self, object: ViObject, attr: ViAttr, value: &mut [u8]) -> Result;
+
+    /// `viSetAttribute`
+    fn set_attribute(&self, object: ViObject, attr: ViAttr, value: ViAttrState) -> Result;
+
+    /// `viRead`: the number of bytes read and the completion code.
+    fn read(&self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)>;
+
+    /// `viWrite`: the number of bytes written.
+    fn write(&self, vi: ViSession, data: &[u8]) -> Result<usize>;
+
+    /// `viReadSTB`
+    fn read_stb(&self, vi: ViSession) -> Result<ViUInt16>;
+
+    /// `viEnableEvent`
+    fn enable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result;
+
+    /// `viDisableEvent`
+    fn disable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result;
+
+    /// `viDiscardEvents`
+    fn discard_events(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result;
+
+    /// `viWaitOnEvent`: the type and context of the event.
+    fn wait_on_event(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        timeout: ViUInt32,
+    ) -> Result<(ViEventType, ViEvent)>;
+
+    /// `viInstallHandler`
+    ///
+    /// # Safety
+    ///
+    /// `handler` is called with `user_handle`, so both must stay valid until
+    /// the handler is uninstalled.
+    unsafe fn install_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result;
+
+    /// `viUninstallHandler`
+    ///
+    /// # Safety
+    ///
+    /// A handler may still be running when this returns; `user_handle` must
+    /// stay valid until it finishes.
+    unsafe fn uninstall_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result;
+
+    /// `viLock`: the completion code and, for shared locks, the access key.
+    fn lock(
+        &self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<&str>,
+    ) -> Result<(Completion, String)>;
+
+    /// `viUnlock`
+    fn unlock(&self, vi: ViSession) -> Result;
+
+    /// `viReadAsync`
+    ///
+    /// # Safety
+    ///
+    /// `buf` must be valid for `len` bytes until the job completes.
+    unsafe fn read_async(&self, vi: ViSession, buf: *mut u8, len: usize) -> Result<ViJobId>;
+
+    /// `viWriteAsync`
+    ///
+    /// # Safety
+    ///
+    /// `data` must be valid for `len` bytes until the job completes.
+    unsafe fn write_async(&self, vi: ViSession, data: *const u8, len: usize) -> Result<ViJobId>;
+
+    /// `viTerminate`
+    fn terminate(&self, vi: ViSession, degree: ViUInt16, job_id: ViJobId) -> Result;
+}
+
+/// The VISA library, linked or loaded with the `dynamic-loading` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FfiBackend;
+
+fn count(len: usize) -> ViUInt32 {
+    len.min(ViUInt32::MAX as usize) as ViUInt32
+}
+
+impl VisaBackend for FfiBackend {
+    fn open_default_rm(&self) -> Result<ViSession> {
+        let mut handle: ViSession = VI_NULL;
+        check(unsafe { viOpenDefaultRM(&mut handle) })?;
+        Ok(handle)
+    }
+
+    fn open(&self, rm: ViSession, resource: &str, access_mode: ViAccessMode, timeout: ViUInt32) -> Result<ViSession> {
+        let name = CString::new(resource).map_err(|_| error(VI_ERROR_INV_RSRC_NAME))?;
+        let mut handle: ViSession = VI_NULL;
+        check(unsafe { viOpen(rm, name.as_ptr(), access_mode, timeout, &mut handle) })?;
+        Ok(handle)
+    }
+
+    fn close(&self, object: ViObject) -> Result {
+        check(unsafe { viClose(object) })
+    }
+
+    fn find_rsrc(&self, rm: ViSession, expr: &str) -> Result<(ViFindList, ViUInt32, String)> {
+        let expr = CString::new(expr).map_err(|_| error(VI_ERROR_INV_EXPR))?;
+        let mut list: ViFindList = VI_NULL;
+        let mut count: ViUInt32 = 0;
+        let mut desc = [0 as ViChar; VI_FIND_BUFLEN as usize];
+        check(unsafe { viFindRsrc(rm, expr.as_ptr(), &mut list, &mut count, desc.as_mut_ptr()) })?;
+        Ok((list, count, string_from_buffer(&desc)))
+    }
+
+    fn find_next(&self, list: ViFindList) -> Result<String> {
+        let mut desc = [0 as ViChar; VI_FIND_BUFLEN as usize];
+        check(unsafe { viFindNext(list, desc.as_mut_ptr()) })?;
+        Ok(string_from_buffer(&desc))
+    }
+
+    fn get_attribute(&self, object: ViObject, attr: ViAttr, value: &mut [u8]) -> Result {
+        attr::check_buffer(self, object, attr, value)?;
+        check(unsafe { viGetAttribute(object, attr, value.as_mut_ptr() as *mut c_void) })
+    }
+
+    fn set_attribute(&self, object: ViObject, attr: ViAttr, value: ViAttrState) -> Result {
+        check(unsafe { viSetAttribute(object, attr, value) })
+    }
+
+    fn read(&self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let mut read: ViUInt32 = 0;
+        let completion = check(unsafe { viRead(vi, buf.as_mut_ptr(), count(buf.len()), &mut read) })?;
+        Ok((read as usize, completion))
+    }
+
+    fn write(&self, vi: ViSession, data: &[u8]) -> Result<usize> {
+        let mut written: ViUInt32 = 0;
+        check(unsafe { viWrite(vi, data.as_ptr(), count(data.len()), &mut written) })?;
+        Ok(written as usize)
+    }
+
+    fn read_stb(&self, vi: ViSession) -> Result<ViUInt16> {
+        let mut status: ViUInt16 = 0;
+        check(unsafe { viReadSTB(vi, &mut status) })?;
+        Ok(status)
+    }
+
+    fn enable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        check(unsafe { viEnableEvent(vi, event_type, mechanism, VI_NULL) })
+    }
+
+    fn disable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        check(unsafe { viDisableEvent(vi, event_type, mechanism) })
+    }
+
+    fn discard_events(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        check(unsafe { viDiscardEvents(vi, event_type, mechanism) })
+    }
+
+    fn wait_on_event(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        timeout: ViUInt32,
+    ) -> Result<(ViEventType, ViEvent)> {
+        let mut out_type: ViEventType = 0;
+        let mut context: ViEvent = VI_NULL;
+        check(unsafe { viWaitOnEvent(vi, event_type, timeout, &mut out_type, &mut context) })?;
+        Ok((out_type, context))
+    }
+
+    unsafe fn install_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result {
+        check(unsafe { viInstallHandler(vi, event_type, handler, user_handle) })
+    }
+
+    unsafe fn uninstall_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result {
+        check(unsafe { viUninstallHandler(vi, event_type, handler, user_handle) })
+    }
+
+    fn lock(
+        &self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<&str>,
+    ) -> Result<(Completion, String)> {
+        let requested_key = requested_key
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| error(VI_ERROR_INV_ACCESS_KEY))?;
+        let requested_key = requested_key.as_ref().map_or(std::ptr::null(), |key| key.as_ptr());
+        if lock_type != VI_SHARED_LOCK as ViAccessMode {
+            let completion = check(unsafe { viLock(vi, lock_type, timeout, requested_key, std::ptr::null_mut()) })?;
+            return Ok((completion, String::new()));
+        }
+        let mut access_key = [0 as ViChar; VI_FIND_BUFLEN as usize];
+        let completion = check(unsafe { viLock(vi, lock_type, timeout, requested_key, access_key.as_mut_ptr()) })?;
+        Ok((completion, string_from_buffer(&access_key)))
+    }
+
+    fn unlock(&self, vi: ViSession) -> Result {
+        check(unsafe { viUnlock(vi) })
+    }
+
+    unsafe fn read_async(&self, vi: ViSession, buf: *mut u8, len: usize) -> Result<ViJobId> {
+        let mut job_id: ViJobId = 0;
+        check(unsafe { viReadAsync(vi, buf, count(len), &mut job_id) })?;
+        Ok(job_id)
+    }
+
+    unsafe fn write_async(&self, vi: ViSession, data: *const u8, len: usize) -> Result<ViJobId> {
+        let mut job_id: ViJobId = 0;
+        check(unsafe { viWriteAsync(vi, data, count(len), &mut job_id) })?;
+        Ok(job_id)
+    }
+
+    fn terminate(&self, vi: ViSession, degree: ViUInt16, job_id: ViJobId) -> Result {
+        check(unsafe { viTerminate(vi, degree, job_id) })
+    }
+}
+
+fn string_from_buffer(buffer: &[ViChar]) -> String {
+    unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned()
+}
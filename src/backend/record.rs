@@ -0,0 +1,927 @@
+//! Recording sessions with real instruments and replaying them without.
+//! Requires the `record` feature.
+//!
+//! `RecordingBackend` wraps another backend and logs every call with its
+//! arguments, results, status and timing. The log is saved as JSON Lines,
+//! one call per line, e.g.
+//!
+//! ```text
+//! {"elapsed_us":5120,"duration_us":310,"status":0,"op":"write","vi":2,"data":"MEAS:VOLT?\n","count":11}
+//! ```
+//!
+//! `ReplayBackend` answers the same calls from the log, so the code that
+//! made the recording runs again in CI. Calls must come in the recorded order
+//! with the recorded arguments; the first one that does not panics with both
+//! calls, and `finish` reports recorded calls that were never made. Loose
+//! matching tolerates commands whose numbers or arguments change between
+//! runs:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use ni_visa_bindings::backend::record::{Recording, ReplayBackend, ReplayOptions};
+//! use ni_visa_bindings::ResourceManager;
+//!
+//! let recording = Recording::load("tests/data/dmm.jsonl").unwrap();
+//! let options = ReplayOptions::new().float_tolerance(1e-6).ignore_arguments("SYST:TIME");
+//! let replay = Arc::new(ReplayBackend::new(recording, options));
+//! let rm = ResourceManager::with_backend(replay.clone()).unwrap();
+//! let mut dmm = rm.open("GPIB0::5::INSTR").unwrap();
+//! assert_eq!(dmm.query("*IDN?").unwrap(), "ACME,DMM-1,42,1.0");
+//! drop(dmm);
+//! drop(rm);
+//! replay.finish().unwrap();
+//! ```
+//!
+//! Handles are replayed as recorded. Timing is recorded for reference only:
+//! replays answer at once. Asynchronous I/O and event handlers run
+//! concurrently with the session, so they cannot be replayed: `viReadAsync`
+//! and `viWriteAsync` fail with `VI_ERROR_NSUP_OPER`, and installed handlers
+//! are never called.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::VisaBackend;
+use crate::ffi::*;
+use crate::status::{error, Completion, Result, Status};
+
+/// Bytes, saved as a string if they are UTF-8 and as an array otherwise.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Data(pub Vec<u8>);
+
+impl fmt::Debug for Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"{}\"", self.0.escape_ascii())
+    }
+}
+
+impl From<&[u8]> for Data {
+    fn from(bytes: &[u8]) -> Self {
+        Data(bytes.to_vec())
+    }
+}
+
+impl Serialize for Data {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match std::str::from_utf8(&self.0) {
+            Ok(text) => serializer.serialize_str(text),
+            Err(_) => self.0.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Data {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Bytes(Vec<u8>),
+        }
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => Data(text.into_bytes()),
+            Repr::Bytes(bytes) => Data(bytes),
+        })
+    }
+}
+
+/// The value of `viGetAttribute`: numbers as such, strings and
+/// variable-length attributes as data without trailing NULs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AttrValue {
+    Number(u64),
+    Data(Data),
+}
+
+impl Default for AttrValue {
+    fn default() -> Self {
+        AttrValue::Number(0)
+    }
+}
+
+impl AttrValue {
+    fn from_buffer(buffer: &[u8]) -> Self {
+        if buffer.len() <= 8 {
+            let mut bytes = [0; 8];
+            let offset = if cfg!(target_endian = "big") { 8 - buffer.len() } else { 0 };
+            bytes[offset..offset + buffer.len()].copy_from_slice(buffer);
+            return AttrValue::Number(u64::from_ne_bytes(bytes));
+        }
+        let len = buffer.iter().rposition(|&b| b != 0).map_or(0, |last| last + 1);
+        AttrValue::Data(Data::from(&buffer[..len]))
+    }
+
+    fn write_to(&self, buffer: &mut [u8]) {
+        buffer.fill(0);
+        match self {
+            AttrValue::Number(value) => {
+                let bytes = value.to_ne_bytes();
+                let len = buffer.len().min(8);
+                let offset = if cfg!(target_endian = "big") { 8 - len } else { 0 };
+                buffer[..len].copy_from_slice(&bytes[offset..offset + len]);
+            }
+            AttrValue::Data(Data(data)) => {
+                let len = data.len().min(buffer.len());
+                buffer[..len].copy_from_slice(&data[..len]);
+            }
+        }
+    }
+}
+
+/// A backend call with its arguments and, after the arguments, its results.
+/// Results are zero or empty if the call failed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Call {
+    OpenDefaultRm {
+        handle: ViSession,
+    },
+    Open {
+        rm: ViSession,
+        resource: String,
+        access_mode: ViAccessMode,
+        timeout: ViUInt32,
+        handle: ViSession,
+    },
+    Close {
+        object: ViObject,
+    },
+    FindRsrc {
+        rm: ViSession,
+        expr: String,
+        list: ViFindList,
+        count: ViUInt32,
+        first: String,
+    },
+    FindNext {
+        list: ViFindList,
+        name: String,
+    },
+    GetAttribute {
+        object: ViObject,
+        attr: ViAttr,
+        value: AttrValue,
+    },
+    SetAttribute {
+        object: ViObject,
+        attr: ViAttr,
+        value: ViAttrState,
+    },
+    Read {
+        vi: ViSession,
+        len: usize,
+        data: Data,
+    },
+    Write {
+        vi: ViSession,
+        data: Data,
+        count: usize,
+    },
+    ReadStb {
+        vi: ViSession,
+        stb: ViUInt16,
+    },
+    EnableEvent {
+        vi: ViSession,
+        event_type: ViEventType,
+        mechanism: ViUInt16,
+    },
+    DisableEvent {
+        vi: ViSession,
+        event_type: ViEventType,
+        mechanism: ViUInt16,
+    },
+    DiscardEvents {
+        vi: ViSession,
+        event_type: ViEventType,
+        mechanism: ViUInt16,
+    },
+    WaitOnEvent {
+        vi: ViSession,
+        event_type: ViEventType,
+        timeout: ViUInt32,
+        out_type: ViEventType,
+        context: ViEvent,
+    },
+    InstallHandler {
+        vi: ViSession,
+        event_type: ViEventType,
+    },
+    UninstallHandler {
+        vi: ViSession,
+        event_type: ViEventType,
+    },
+    Lock {
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<String>,
+        key: String,
+    },
+    Unlock {
+        vi: ViSession,
+    },
+    Terminate {
+        vi: ViSession,
+        degree: ViUInt16,
+        job_id: ViJobId,
+    },
+}
+
+impl Call {
+    /// The call with its results cleared, i.e. what a replay is matched on.
+    fn arguments(&self) -> Call {
+        let mut call = self.clone();
+        match &mut call {
+            Call::OpenDefaultRm { handle } | Call::Open { handle, .. } => *handle = 0,
+            Call::FindRsrc { list, count, first, .. } => {
+                *list = 0;
+                *count = 0;
+                first.clear();
+            }
+            Call::FindNext { name, .. } => name.clear(),
+            Call::GetAttribute { value, .. } => *value = AttrValue::default(),
+            Call::Read { data, .. } => data.0.clear(),
+            Call::Write { count, .. } => *count = 0,
+            Call::ReadStb { stb, .. } => *stb = 0,
+            Call::WaitOnEvent { out_type, context, .. } => {
+                *out_type = 0;
+                *context = 0;
+            }
+            Call::Lock { key, .. } => key.clear(),
+            _ => {}
+        }
+        call
+    }
+}
+
+/// A recorded call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// When the call started, from the start of the recording.
+    pub elapsed_us: u64,
+    pub duration_us: u64,
+    /// The `ViStatus` returned: a completion code or an error.
+    pub status: ViStatus,
+    #[serde(flatten)]
+    pub call: Call,
+}
+
+/// The calls of a recorded session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    pub entries: Vec<Entry>,
+}
+
+impl Recording {
+    /// Reads a recording saved with `save`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Recording::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads JSON Lines, skipping blank lines.
+    pub fn read_from(reader: impl BufRead) -> io::Result<Self> {
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(Recording { entries })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        for entry in &self.entries {
+            serde_json::to_writer(&mut writer, entry)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// A backend that forwards to another one and records every call.
+#[derive(Debug)]
+pub struct RecordingBackend {
+    inner: Arc<dyn VisaBackend>,
+    started: Instant,
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl RecordingBackend {
+    /// Records the calls made to `inner`, typically `FfiBackend`.
+    pub fn new(inner: Arc<dyn VisaBackend>) -> Self {
+        RecordingBackend {
+            inner,
+            started: Instant::now(),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The calls recorded so far.
+    pub fn recording(&self) -> Recording {
+        Recording {
+            entries: self.entries.lock().unwrap_or_else(PoisonError::into_inner).clone(),
+        }
+    }
+
+    /// Saves the calls recorded so far, see `Recording::save`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.recording().save(path)
+    }
+
+    /// Records the call `describe` returns for `result`, made at `start`.
+    fn record<T>(
+        &self,
+        start: Instant,
+        result: Result<T>,
+        status: impl FnOnce(&T) -> ViStatus,
+        describe: impl FnOnce(Option<&T>) -> Call,
+    ) -> Result<T> {
+        let entry = Entry {
+            elapsed_us: start.duration_since(self.started).as_micros() as u64,
+            duration_us: start.elapsed().as_micros() as u64,
+            status: match &result {
+                Ok(value) => status(value),
+                Err(e) => e.raw(),
+            },
+            call: describe(result.as_ref().ok()),
+        };
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner).push(entry);
+        result
+    }
+}
+
+fn success<T>(_: &T) -> ViStatus {
+    VI_SUCCESS as ViStatus
+}
+
+impl VisaBackend for RecordingBackend {
+    fn open_default_rm(&self) -> Result<ViSession> {
+        let start = Instant::now();
+        let result = self.inner.open_default_rm();
+        self.record(start, result, success, |handle| Call::OpenDefaultRm {
+            handle: handle.copied().unwrap_or_default(),
+        })
+    }
+
+    fn open(&self, rm: ViSession, resource: &str, access_mode: ViAccessMode, timeout: ViUInt32) -> Result<ViSession> {
+        let start = Instant::now();
+        let result = self.inner.open(rm, resource, access_mode, timeout);
+        self.record(start, result, success, |handle| Call::Open {
+            rm,
+            resource: resource.to_string(),
+            access_mode,
+            timeout,
+            handle: handle.copied().unwrap_or_default(),
+        })
+    }
+
+    fn close(&self, object: ViObject) -> Result {
+        let start = Instant::now();
+        let result = self.inner.close(object);
+        self.record(start, result, Completion::raw, |_| Call::Close { object })
+    }
+
+    fn find_rsrc(&self, rm: ViSession, expr: &str) -> Result<(ViFindList, ViUInt32, String)> {
+        let start = Instant::now();
+        let result = self.inner.find_rsrc(rm, expr);
+        self.record(start, result, success, |found| {
+            let (list, count, first) = found.cloned().unwrap_or_default();
+            Call::FindRsrc {
+                rm,
+                expr: expr.to_string(),
+                list,
+                count,
+                first,
+            }
+        })
+    }
+
+    fn find_next(&self, list: ViFindList) -> Result<String> {
+        let start = Instant::now();
+        let result = self.inner.find_next(list);
+        self.record(start, result, success, |name| Call::FindNext {
+            list,
+            name: name.cloned().unwrap_or_default(),
+        })
+    }
+
+    fn get_attribute(&self, object: ViObject, attr: ViAttr, value: &mut [u8]) -> Result {
+        let start = Instant::now();
+        let result = self.inner.get_attribute(object, attr, value);
+        self.record(start, result, Completion::raw, |got| Call::GetAttribute {
+            object,
+            attr,
+            value: got.map_or_else(AttrValue::default, |_| AttrValue::from_buffer(value)),
+        })
+    }
+
+    fn set_attribute(&self, object: ViObject, attr: ViAttr, value: ViAttrState) -> Result {
+        let start = Instant::now();
+        let result = self.inner.set_attribute(object, attr, value);
+        self.record(start, result, Completion::raw, |_| Call::SetAttribute { object, attr, value })
+    }
+
+    fn read(&self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let start = Instant::now();
+        let result = self.inner.read(vi, buf);
+        self.record(start, result, |(_, completion)| completion.raw(), |read| Call::Read {
+            vi,
+            len: buf.len(),
+            data: read.map_or_else(Data::default, |(count, _)| Data::from(&buf[..*count])),
+        })
+    }
+
+    fn write(&self, vi: ViSession, data: &[u8]) -> Result<usize> {
+        let start = Instant::now();
+        let result = self.inner.write(vi, data);
+        self.record(start, result, success, |count| Call::Write {
+            vi,
+            data: Data::from(data),
+            count: count.copied().unwrap_or_default(),
+        })
+    }
+
+    fn read_stb(&self, vi: ViSession) -> Result<ViUInt16> {
+        let start = Instant::now();
+        let result = self.inner.read_stb(vi);
+        self.record(start, result, success, |stb| Call::ReadStb {
+            vi,
+            stb: stb.copied().unwrap_or_default(),
+        })
+    }
+
+    fn enable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        let start = Instant::now();
+        let result = self.inner.enable_event(vi, event_type, mechanism);
+        self.record(start, result, Completion::raw, |_| Call::EnableEvent { vi, event_type, mechanism })
+    }
+
+    fn disable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        let start = Instant::now();
+        let result = self.inner.disable_event(vi, event_type, mechanism);
+        self.record(start, result, Completion::raw, |_| Call::DisableEvent { vi, event_type, mechanism })
+    }
+
+    fn discard_events(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        let start = Instant::now();
+        let result = self.inner.discard_events(vi, event_type, mechanism);
+        self.record(start, result, Completion::raw, |_| Call::DiscardEvents { vi, event_type, mechanism })
+    }
+
+    fn wait_on_event(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        timeout: ViUInt32,
+    ) -> Result<(ViEventType, ViEvent)> {
+        let start = Instant::now();
+        let result = self.inner.wait_on_event(vi, event_type, timeout);
+        self.record(start, result, success, |event| {
+            let (out_type, context) = event.copied().unwrap_or_default();
+            Call::WaitOnEvent {
+                vi,
+                event_type,
+                timeout,
+                out_type,
+                context,
+            }
+        })
+    }
+
+    unsafe fn install_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result {
+        let start = Instant::now();
+        let result = unsafe { self.inner.install_handler(vi, event_type, handler, user_handle) };
+        self.record(start, result, Completion::raw, |_| Call::InstallHandler { vi, event_type })
+    }
+
+    unsafe fn uninstall_handler(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        handler: ViHndlr,
+        user_handle: ViAddr,
+    ) -> Result {
+        let start = Instant::now();
+        let result = unsafe { self.inner.uninstall_handler(vi, event_type, handler, user_handle) };
+        self.record(start, result, Completion::raw, |_| Call::UninstallHandler { vi, event_type })
+    }
+
+    fn lock(
+        &self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<&str>,
+    ) -> Result<(Completion, String)> {
+        let start = Instant::now();
+        let result = self.inner.lock(vi, lock_type, timeout, requested_key);
+        self.record(start, result, |(completion, _)| completion.raw(), |locked| Call::Lock {
+            vi,
+            lock_type,
+            timeout,
+            requested_key: requested_key.map(str::to_string),
+            key: locked.map(|(_, key)| key.clone()).unwrap_or_default(),
+        })
+    }
+
+    fn unlock(&self, vi: ViSession) -> Result {
+        let start = Instant::now();
+        let result = self.inner.unlock(vi);
+        self.record(start, result, Completion::raw, |_| Call::Unlock { vi })
+    }
+
+    unsafe fn read_async(&self, vi: ViSession, buf: *mut u8, len: usize) -> Result<ViJobId> {
+        unsafe { self.inner.read_async(vi, buf, len) }
+    }
+
+    unsafe fn write_async(&self, vi: ViSession, data: *const u8, len: usize) -> Result<ViJobId> {
+        unsafe { self.inner.write_async(vi, data, len) }
+    }
+
+    fn terminate(&self, vi: ViSession, degree: ViUInt16, job_id: ViJobId) -> Result {
+        let start = Instant::now();
+        let result = self.inner.terminate(vi, degree, job_id);
+        self.record(start, result, Completion::raw, |_| Call::Terminate { vi, degree, job_id })
+    }
+}
+
+/// How closely replayed calls must match the recording. The default is
+/// strict: every argument as recorded.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayOptions {
+    float_tolerance: Option<f64>,
+    ignore_arguments: Vec<String>,
+}
+
+impl ReplayOptions {
+    pub fn new() -> Self {
+        ReplayOptions::default()
+    }
+
+    /// Accepts written numbers within this relative tolerance of the
+    /// recorded ones, e.g. `1e-6`.
+    pub fn float_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Accepts any arguments after `header`, e.g. `"SYST:TIME"` for a clock
+    /// set from the host. Headers are compared case-insensitively.
+    pub fn ignore_arguments(mut self, header: &str) -> Self {
+        self.ignore_arguments.push(header.to_string());
+        self
+    }
+
+    fn matches(&self, expected: &Call, actual: &Call) -> bool {
+        match (expected, actual) {
+            (Call::Write { vi: a, data: expected, .. }, Call::Write { vi: b, data: actual, .. }) if a == b => {
+                match (std::str::from_utf8(&expected.0), std::str::from_utf8(&actual.0)) {
+                    (Ok(expected), Ok(actual)) => self.matches_command(expected, actual),
+                    _ => expected == actual,
+                }
+            }
+            _ => expected == actual,
+        }
+    }
+
+    fn matches_command(&self, expected: &str, actual: &str) -> bool {
+        let header = |command: &str| command.split_whitespace().next().unwrap_or("").to_string();
+        let (expected_header, actual_header) = (header(expected), header(actual));
+        if expected_header.eq_ignore_ascii_case(&actual_header)
+            && self.ignore_arguments.iter().any(|ignored| ignored.eq_ignore_ascii_case(&expected_header))
+        {
+            return true;
+        }
+        let separator = |c: char| c.is_whitespace() || c == ',' || c == ';';
+        let (mut expected, mut actual) = (expected.split(separator), actual.split(separator));
+        loop {
+            match (expected.next(), actual.next()) {
+                (None, None) => return true,
+                (Some(a), Some(b)) if a == b || self.close(a, b) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    fn close(&self, expected: &str, actual: &str) -> bool {
+        let Some(tolerance) = self.float_tolerance else { return false };
+        match (expected.parse::<f64>(), actual.parse::<f64>()) {
+            (Ok(a), Ok(b)) => (a - b).abs() <= tolerance * a.abs().max(b.abs()),
+            _ => false,
+        }
+    }
+}
+
+/// Where a replay left the recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Index of the call in the recording.
+    pub index: usize,
+    /// The recorded call, `None` if the recording had ended.
+    pub expected: Option<Box<Call>>,
+    /// The call made, `None` if the replay ended first.
+    pub actual: Option<Box<Call>>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.expected, &self.actual) {
+            (Some(expected), Some(actual)) => {
+                write!(f, "call {} diverged from the recording: expected {expected:?}, got {actual:?}", self.index)
+            }
+            (None, Some(actual)) => write!(f, "call {} was not recorded: {actual:?}", self.index),
+            (Some(expected), None) => write!(f, "recorded call {} was never made: {expected:?}", self.index),
+            (None, None) => write!(f, "call {} diverged from the recording", self.index),
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+#[derive(Debug, Default)]
+struct Cursor {
+    next: usize,
+    divergence: Option<Divergence>,
+}
+
+/// A backend that answers calls from a recording.
+#[derive(Debug)]
+pub struct ReplayBackend {
+    entries: Vec<Entry>,
+    options: ReplayOptions,
+    cursor: Mutex<Cursor>,
+}
+
+impl ReplayBackend {
+    pub fn new(recording: Recording, options: ReplayOptions) -> Self {
+        ReplayBackend {
+            entries: recording.entries,
+            options,
+            cursor: Mutex::new(Cursor::default()),
+        }
+    }
+
+    /// Checks that the replay made every recorded call. Call it after
+    /// closing the sessions and the resource manager.
+    pub fn finish(&self) -> std::result::Result<(), Divergence> {
+        let cursor = self.cursor();
+        if let Some(divergence) = &cursor.divergence {
+            return Err(divergence.clone());
+        }
+        match self.entries.get(cursor.next) {
+            Some(entry) => Err(Divergence {
+                index: cursor.next,
+                expected: Some(Box::new(entry.call.clone())),
+                actual: None,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn cursor(&self) -> MutexGuard<'_, Cursor> {
+        self.cursor.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Matches `actual` against the next recorded call and returns it. Panics
+    /// on the first divergence; later calls fail with `VI_ERROR_SYSTEM_ERROR`
+    /// so that sessions dropped while unwinding do not panic again.
+    fn replay(&self, actual: Call) -> Result<&Entry> {
+        let mut cursor = self.cursor();
+        if cursor.divergence.is_some() {
+            return Err(error(VI_ERROR_SYSTEM_ERROR));
+        }
+        let index = cursor.next;
+        let entry = self.entries.get(index);
+        if let Some(entry) = entry.filter(|entry| self.options.matches(&entry.call.arguments(), &actual)) {
+            cursor.next += 1;
+            return Ok(entry);
+        }
+        let divergence = Divergence {
+            index,
+            expected: entry.map(|entry| Box::new(entry.call.clone())),
+            actual: Some(Box::new(actual)),
+        };
+        let message = divergence.to_string();
+        cursor.divergence = Some(divergence);
+        drop(cursor);
+        if !std::thread::panicking() {
+            panic!("{message}");
+        }
+        Err(error(VI_ERROR_SYSTEM_ERROR))
+    }
+
+    /// Replays a call that returns nothing but its status.
+    fn replay_status(&self, actual: Call) -> Result {
+        Status::from_raw(self.replay(actual)?.status).into_result()
+    }
+
+    /// Replays a call, returning its recorded results if it succeeded.
+    fn replay_call(&self, actual: Call) -> Result<&Call> {
+        let entry = self.replay(actual)?;
+        Status::from_raw(entry.status).into_result()?;
+        Ok(&entry.call)
+    }
+}
+
+impl VisaBackend for ReplayBackend {
+    fn open_default_rm(&self) -> Result<ViSession> {
+        match self.replay_call(Call::OpenDefaultRm { handle: 0 })? {
+            Call::OpenDefaultRm { handle } => Ok(*handle),
+            _ => unreachable!(),
+        }
+    }
+
+    fn open(&self, rm: ViSession, resource: &str, access_mode: ViAccessMode, timeout: ViUInt32) -> Result<ViSession> {
+        let call = Call::Open {
+            rm,
+            resource: resource.to_string(),
+            access_mode,
+            timeout,
+            handle: 0,
+        };
+        match self.replay_call(call)? {
+            Call::Open { handle, .. } => Ok(*handle),
+            _ => unreachable!(),
+        }
+    }
+
+    fn close(&self, object: ViObject) -> Result {
+        self.replay_status(Call::Close { object })
+    }
+
+    fn find_rsrc(&self, rm: ViSession, expr: &str) -> Result<(ViFindList, ViUInt32, String)> {
+        let call = Call::FindRsrc {
+            rm,
+            expr: expr.to_string(),
+            list: 0,
+            count: 0,
+            first: String::new(),
+        };
+        match self.replay_call(call)? {
+            Call::FindRsrc { list, count, first, .. } => Ok((*list, *count, first.clone())),
+            _ => unreachable!(),
+        }
+    }
+
+    fn find_next(&self, list: ViFindList) -> Result<String> {
+        match self.replay_call(Call::FindNext { list, name: String::new() })? {
+            Call::FindNext { name, .. } => Ok(name.clone()),
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_attribute(&self, object: ViObject, attr: ViAttr, value: &mut [u8]) -> Result {
+        let call = Call::GetAttribute {
+            object,
+            attr,
+            value: AttrValue::default(),
+        };
+        let entry = self.replay(call)?;
+        let completion = Status::from_raw(entry.status).into_result()?;
+        if let Call::GetAttribute { value: recorded, .. } = &entry.call {
+            recorded.write_to(value);
+        }
+        Ok(completion)
+    }
+
+    fn set_attribute(&self, object: ViObject, attr: ViAttr, value: ViAttrState) -> Result {
+        self.replay_status(Call::SetAttribute { object, attr, value })
+    }
+
+    fn read(&self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let call = Call::Read {
+            vi,
+            len: buf.len(),
+            data: Data::default(),
+        };
+        let entry = self.replay(call)?;
+        let completion = Status::from_raw(entry.status).into_result()?;
+        match &entry.call {
+            Call::Read { data: Data(data), .. } => {
+                let count = data.len().min(buf.len());
+                buf[..count].copy_from_slice(&data[..count]);
+                Ok((count, completion))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn write(&self, vi: ViSession, data: &[u8]) -> Result<usize> {
+        let call = Call::Write {
+            vi,
+            data: Data::from(data),
+            count: 0,
+        };
+        match self.replay_call(call)? {
+            // A loosely matched command may differ in length from the recorded one.
+            Call::Write { data: recorded, count, .. } if *count == recorded.0.len() => Ok(data.len()),
+            Call::Write { count, .. } => Ok(*count),
+            _ => unreachable!(),
+        }
+    }
+
+    fn read_stb(&self, vi: ViSession) -> Result<ViUInt16> {
+        match self.replay_call(Call::ReadStb { vi, stb: 0 })? {
+            Call::ReadStb { stb, .. } => Ok(*stb),
+            _ => unreachable!(),
+        }
+    }
+
+    fn enable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        self.replay_status(Call::EnableEvent { vi, event_type, mechanism })
+    }
+
+    fn disable_event(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        self.replay_status(Call::DisableEvent { vi, event_type, mechanism })
+    }
+
+    fn discard_events(&self, vi: ViSession, event_type: ViEventType, mechanism: ViUInt16) -> Result {
+        self.replay_status(Call::DiscardEvents { vi, event_type, mechanism })
+    }
+
+    fn wait_on_event(
+        &self,
+        vi: ViSession,
+        event_type: ViEventType,
+        timeout: ViUInt32,
+    ) -> Result<(ViEventType, ViEvent)> {
+        let call = Call::WaitOnEvent {
+            vi,
+            event_type,
+            timeout,
+            out_type: 0,
+            context: 0,
+        };
+        match self.replay_call(call)? {
+            Call::WaitOnEvent { out_type, context, .. } => Ok((*out_type, *context)),
+            _ => unreachable!(),
+        }
+    }
+
+    unsafe fn install_handler(&self, vi: ViSession, event_type: ViEventType, _: ViHndlr, _: ViAddr) -> Result {
+        self.replay_status(Call::InstallHandler { vi, event_type })
+    }
+
+    unsafe fn uninstall_handler(&self, vi: ViSession, event_type: ViEventType, _: ViHndlr, _: ViAddr) -> Result {
+        self.replay_status(Call::UninstallHandler { vi, event_type })
+    }
+
+    fn lock(
+        &self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<&str>,
+    ) -> Result<(Completion, String)> {
+        let call = Call::Lock {
+            vi,
+            lock_type,
+            timeout,
+            requested_key: requested_key.map(str::to_string),
+            key: String::new(),
+        };
+        let entry = self.replay(call)?;
+        let completion = Status::from_raw(entry.status).into_result()?;
+        match &entry.call {
+            Call::Lock { key, .. } => Ok((completion, key.clone())),
+            _ => unreachable!(),
+        }
+    }
+
+    fn unlock(&self, vi: ViSession) -> Result {
+        self.replay_status(Call::Unlock { vi })
+    }
+
+    unsafe fn read_async(&self, _: ViSession, _: *mut u8, _: usize) -> Result<ViJobId> {
+        Err(error(VI_ERROR_NSUP_OPER))
+    }
+
+    unsafe fn write_async(&self, _: ViSession, _: *const u8, _: usize) -> Result<ViJobId> {
+        Err(error(VI_ERROR_NSUP_OPER))
+    }
+
+    fn terminate(&self, vi: ViSession, degree: ViUInt16, job_id: ViJobId) -> Result {
+        self.replay_status(Call::Terminate { vi, degree, job_id })
+    }
+}
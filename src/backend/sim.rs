@@ -0,0 +1,497 @@
+//! Simulated instruments described in YAML or TOML, in the spirit of
+//! pyvisa-sim. Requires the `sim` feature.
+//!
+//! A description lists device types and the resources that simulate them:
+//!
+//! ```yaml
+//! devices:
+//!   generator:
+//!     termination: { write: "\n", read: "\n" }
+//!     dialogues:
+//!       - { q: "*IDN?", r: "ACME,FG-1,1234,1.0" }
+//!       - { q: "*RST" }
+//!     properties:
+//!       frequency:
+//!         default: 1000.0
+//!         get: { q: "FREQ?" }
+//!         set: { q: "FREQ {}" }
+//!         min: 1
+//!         max: 1.0e6
+//!       shape:
+//!         default: SIN
+//!         get: { q: "FUNC?" }
+//!         set: { q: "FUNC {}" }
+//!         values: [SIN, SQU, RAMP]
+//! resources:
+//!   "TCPIP0::10.0.0.7::INSTR": { device: generator }
+//!   "GPIB0::10::INSTR": { device: generator }
+//! ```
+//!
+//! Commands are matched exactly after the write termination is removed;
+//! responses get the read termination appended. In a property's `set` query
+//! `{}` stands for the value, in its `get` response (by default `"{}"`) for
+//! the current one. Every resource has its own state.
+//!
+//! Each device also has a SCPI error queue, read with `errors.query`
+//! (`SYSTem:ERRor?` by default) and cleared by `*CLS`. Unknown commands queue
+//! `errors.command`, values that cannot be parsed or are not in `values`
+//! queue `errors.parameter`, and values outside `min`/`max` queue
+//! `errors.range`; the status byte reports a non-empty queue.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use ni_visa_bindings::backend::sim::Simulation;
+//! use ni_visa_bindings::ResourceManager;
+//!
+//! let simulation = Simulation::from_toml(r#"
+//!     [devices.generator]
+//!     dialogues = [{ q = "*IDN?", r = "ACME,FG-1,1234,1.0" }]
+//!     properties.frequency = { default = 1000.0, get = { q = "FREQ?" }, set = { q = "FREQ {}" }, max = 1.0e6 }
+//!
+//!     [resources."TCPIP0::10.0.0.7::INSTR"]
+//!     device = "generator"
+//! "#).unwrap();
+//! let rm = ResourceManager::with_backend(Arc::new(simulation.backend())).unwrap();
+//! for name in rm.find("?*INSTR") {
+//!     let mut session = rm.open(&name.unwrap().to_string()).unwrap();
+//!     session.write_str("FREQ 2500").unwrap();
+//!     assert_eq!(session.query("FREQ?").unwrap(), "2500");
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use super::mock::{MockBackend, MockDevice};
+use crate::registers::StatusByte;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    devices: HashMap<String, DeviceConfig>,
+    resources: BTreeMap<String, ResourceConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResourceConfig {
+    device: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeviceConfig {
+    #[serde(default)]
+    termination: Termination,
+    #[serde(default)]
+    errors: ErrorMessages,
+    #[serde(default)]
+    dialogues: Vec<Dialogue>,
+    #[serde(default)]
+    properties: BTreeMap<String, PropertyConfig>,
+}
+
+/// The terminations as seen by the host: commands end with `write`,
+/// responses with `read`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Termination {
+    write: String,
+    read: String,
+}
+
+impl Default for Termination {
+    fn default() -> Self {
+        Termination {
+            write: "\n".to_string(),
+            read: "\n".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ErrorMessages {
+    query: String,
+    none: String,
+    command: String,
+    parameter: String,
+    range: String,
+}
+
+impl Default for ErrorMessages {
+    fn default() -> Self {
+        ErrorMessages {
+            query: "SYSTem:ERRor?".to_string(),
+            none: "0,\"No error\"".to_string(),
+            command: "-113,\"Undefined header\"".to_string(),
+            parameter: "-224,\"Illegal parameter value\"".to_string(),
+            range: "-222,\"Data out of range\"".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Dialogue {
+    q: String,
+    r: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PropertyConfig {
+    default: Scalar,
+    #[serde(rename = "type")]
+    kind: Option<Kind>,
+    get: Option<Dialogue>,
+    set: Option<Dialogue>,
+    min: Option<f64>,
+    max: Option<f64>,
+    values: Option<Vec<Scalar>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Int,
+    Float,
+    String,
+}
+
+/// A property value, of the property's kind.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Value {
+    fn from_scalar(kind: Kind, scalar: &Scalar) -> Option<Self> {
+        match (kind, scalar) {
+            (Kind::Int, Scalar::Int(value)) => Some(Value::Int(*value)),
+            (Kind::Float, Scalar::Int(value)) => Some(Value::Float(*value as f64)),
+            (Kind::Float, Scalar::Float(value)) => Some(Value::Float(*value)),
+            (Kind::String, Scalar::String(value)) => Some(Value::String(value.clone())),
+            (Kind::String, Scalar::Int(value)) => Some(Value::String(value.to_string())),
+            (Kind::String, Scalar::Float(value)) => Some(Value::String(value.to_string())),
+            _ => None,
+        }
+    }
+
+    fn parse(kind: Kind, text: &str) -> Option<Self> {
+        let text = text.trim();
+        match kind {
+            Kind::Int => text.parse().ok().map(Value::Int),
+            Kind::Float => text.parse().ok().filter(|value: &f64| value.is_finite()).map(Value::Float),
+            Kind::String => {
+                let unquoted = text.strip_prefix('"').and_then(|text| text.strip_suffix('"'));
+                Some(Value::String(unquoted.unwrap_or(text).to_string()))
+            }
+        }
+    }
+
+    fn number(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            Value::String(_) => None,
+        }
+    }
+
+    /// Equality as instruments compare enumerated values: strings ignore
+    /// case.
+    fn matches(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(value) => value.fmt(f),
+            Value::Float(value) => value.fmt(f),
+            Value::String(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Why a setter value is rejected.
+enum Invalid {
+    Parameter,
+    Range,
+}
+
+#[derive(Debug)]
+struct Property {
+    kind: Kind,
+    default: Value,
+    get: Option<Dialogue>,
+    /// The parts of the `set` query before and after `{}`, and its response.
+    set: Option<(String, String, Option<String>)>,
+    min: Option<f64>,
+    max: Option<f64>,
+    values: Option<Vec<Value>>,
+}
+
+impl Property {
+    fn new(name: &str, config: PropertyConfig) -> Result<Self, String> {
+        let invalid = |message: &str| format!("property {name:?}: {message}");
+        let kind = config.kind.unwrap_or(match config.default {
+            Scalar::Int(_) | Scalar::Float(_) => Kind::Float,
+            Scalar::String(_) => Kind::String,
+        });
+        let default = Value::from_scalar(kind, &config.default).ok_or_else(|| invalid("default has the wrong type"))?;
+        let values = match &config.values {
+            Some(values) => Some(
+                values
+                    .iter()
+                    .map(|value| Value::from_scalar(kind, value))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid("values have the wrong type"))?,
+            ),
+            None => None,
+        };
+        let set = match config.set {
+            Some(Dialogue { q, r }) => {
+                let (prefix, suffix) = q.split_once("{}").ok_or_else(|| invalid("set query has no {}"))?;
+                Some((prefix.to_string(), suffix.to_string(), r))
+            }
+            None => None,
+        };
+        let property = Property {
+            kind,
+            default,
+            get: config.get,
+            set,
+            min: config.min,
+            max: config.max,
+            values,
+        };
+        if property.check(&property.default).is_err() {
+            return Err(invalid("default is not a valid value"));
+        }
+        Ok(property)
+    }
+
+    fn check(&self, value: &Value) -> Result<(), Invalid> {
+        if let Some(values) = &self.values {
+            if !values.iter().any(|allowed| allowed.matches(value)) {
+                return Err(Invalid::Parameter);
+            }
+        }
+        if let Some(number) = value.number() {
+            if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max) {
+                return Err(Invalid::Range);
+            }
+        }
+        Ok(())
+    }
+
+    /// The value of a `set` query, if `command` is one.
+    fn setter_argument<'a>(&self, command: &'a str) -> Option<&'a str> {
+        let (prefix, suffix, _) = self.set.as_ref()?;
+        command.strip_prefix(prefix.as_str())?.strip_suffix(suffix.as_str())
+    }
+}
+
+#[derive(Debug)]
+struct Device {
+    termination: Termination,
+    errors: ErrorMessages,
+    dialogues: Vec<Dialogue>,
+    properties: Vec<Property>,
+}
+
+/// The state of one simulated resource.
+struct SimulatedDevice {
+    device: Arc<Device>,
+    values: Vec<Value>,
+    errors: VecDeque<String>,
+}
+
+impl SimulatedDevice {
+    fn new(device: Arc<Device>) -> Self {
+        let values = device.properties.iter().map(|property| property.default.clone()).collect();
+        SimulatedDevice {
+            device,
+            values,
+            errors: VecDeque::new(),
+        }
+    }
+
+    fn respond(&mut self, command: &str) -> Option<String> {
+        let device = &self.device;
+        if command == device.errors.query {
+            return Some(self.errors.pop_front().unwrap_or_else(|| device.errors.none.clone()));
+        }
+        let clear = command.eq_ignore_ascii_case("*CLS");
+        if clear {
+            self.errors.clear();
+        }
+        if let Some(dialogue) = device.dialogues.iter().find(|dialogue| dialogue.q == command) {
+            return dialogue.r.clone();
+        }
+        for (property, value) in device.properties.iter().zip(&mut self.values) {
+            if let Some(get) = property.get.as_ref().filter(|get| get.q == command) {
+                return Some(get.r.as_deref().unwrap_or("{}").replace("{}", &value.to_string()));
+            }
+            let Some(argument) = property.setter_argument(command) else {
+                continue;
+            };
+            let parsed = Value::parse(property.kind, argument).ok_or(Invalid::Parameter);
+            match parsed.and_then(|parsed| property.check(&parsed).map(|_| parsed)) {
+                Ok(parsed) => *value = parsed,
+                Err(Invalid::Parameter) => self.errors.push_back(device.errors.parameter.clone()),
+                Err(Invalid::Range) => self.errors.push_back(device.errors.range.clone()),
+            }
+            return property.set.as_ref().and_then(|(_, _, response)| response.clone());
+        }
+        if !clear {
+            self.errors.push_back(device.errors.command.clone());
+        }
+        None
+    }
+}
+
+impl MockDevice for SimulatedDevice {
+    fn write(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        let text = String::from_utf8_lossy(data);
+        let command = text.strip_suffix(self.device.termination.write.as_str()).unwrap_or(&text);
+        let response = self.respond(command.trim())?;
+        Some(format!("{response}{}", self.device.termination.read).into_bytes())
+    }
+
+    fn status_byte(&mut self) -> u8 {
+        if self.errors.is_empty() {
+            0
+        } else {
+            StatusByte::ERROR_QUEUE.bits()
+        }
+    }
+}
+
+/// A validated description of simulated resources.
+#[derive(Debug)]
+pub struct Simulation {
+    /// Resource names and their devices.
+    resources: Vec<(String, Arc<Device>)>,
+}
+
+impl Simulation {
+    /// Parses a YAML description.
+    pub fn from_yaml(text: &str) -> Result<Self, SimulationError> {
+        let config = serde_yaml::from_str(text).map_err(|e| SimulationError::Parse(e.to_string()))?;
+        Simulation::new(config)
+    }
+
+    /// Parses a TOML description.
+    pub fn from_toml(text: &str) -> Result<Self, SimulationError> {
+        let config = toml::from_str(text).map_err(|e| SimulationError::Parse(e.to_string()))?;
+        Simulation::new(config)
+    }
+
+    /// Reads a description from a `.yaml`, `.yml` or `.toml` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SimulationError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(SimulationError::Io)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml" | "yml") => Simulation::from_yaml(&text),
+            Some("toml") => Simulation::from_toml(&text),
+            _ => Err(SimulationError::Invalid(format!("{} is neither YAML nor TOML", path.display()))),
+        }
+    }
+
+    fn new(config: Config) -> Result<Self, SimulationError> {
+        let mut devices = HashMap::new();
+        for (name, device) in config.devices {
+            let properties = device
+                .properties
+                .into_iter()
+                .map(|(property, config)| Property::new(&property, config))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| SimulationError::Invalid(format!("device {name:?}: {e}")))?;
+            let device = Device {
+                termination: device.termination,
+                errors: device.errors,
+                dialogues: device.dialogues,
+                properties,
+            };
+            devices.insert(name, Arc::new(device));
+        }
+        let resources = config
+            .resources
+            .into_iter()
+            .map(|(name, resource)| match devices.get(&resource.device) {
+                Some(device) => Ok((name, device.clone())),
+                None => Err(SimulationError::Invalid(format!(
+                    "resource {name:?}: unknown device {:?}",
+                    resource.device
+                ))),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Simulation { resources })
+    }
+
+    /// The names of the simulated resources.
+    pub fn resources(&self) -> impl Iterator<Item = &str> {
+        self.resources.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// A backend with every resource in its initial state.
+    pub fn backend(&self) -> MockBackend {
+        self.resources
+            .iter()
+            .fold(MockBackend::new(), |mock, (name, device)| {
+                mock.with_resource(name, SimulatedDevice::new(device.clone()))
+            })
+    }
+}
+
+/// A description that could not be read or is inconsistent.
+#[derive(Debug)]
+pub enum SimulationError {
+    Io(io::Error),
+    /// Not valid YAML or TOML, or not of the expected structure.
+    Parse(String),
+    /// E.g. a resource of an unknown device or a default out of range.
+    Invalid(String),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::Io(e) => write!(f, "cannot read simulation: {e}"),
+            SimulationError::Parse(message) => write!(f, "cannot parse simulation: {message}"),
+            SimulationError::Invalid(message) => write!(f, "invalid simulation: {message}"),
+        }
+    }
+}
+
+impl Error for SimulationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SimulationError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,534 @@
+//! A native backend for raw TCP sockets. Requires the `socket` feature.
+//!
+//! Many LXI instruments accept SCPI on a raw port, usually 5025. For
+//! `TCPIP<n>::<host>::<port>::SOCKET` resources, `SocketBackend` connects
+//! with `std::net` instead of going through VISA, so no VISA library has to
+//! be installed:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use ni_visa_bindings::backend::socket::SocketBackend;
+//! use ni_visa_bindings::{ResourceManager, TextOptions};
+//!
+//! let rm = ResourceManager::with_backend(Arc::new(SocketBackend::new())).unwrap();
+//! let mut scope = rm.open("TCPIP0::192.168.1.20::5025::SOCKET").unwrap();
+//! // Sets VI_ATTR_TERMCHAR_EN, so responses split across packets are read whole.
+//! scope.set_text_options(TextOptions::new()).unwrap();
+//! println!("{}", scope.query("*IDN?").unwrap());
+//! ```
+//!
+//! Reads follow NI-VISA for sockets. With `VI_ATTR_TERMCHAR_EN` set, a read
+//! ends at the termination character; otherwise it returns the data that
+//! arrived, unless `VI_ATTR_SUPPRESS_END_EN` makes it wait for the whole
+//! count. Both wait at most `VI_ATTR_TMO_VALUE`, which also bounds writes
+//! and connecting. `VI_ATTR_TCPIP_NODELAY` (on by default) and
+//! `VI_ATTR_TCPIP_KEEPALIVE` are applied to the connection, and
+//! `viReadSTB` sends `*STB?` when `VI_ATTR_IO_PROT` is
+//! `VI_PROT_4882_STRS`. Locks work between the sessions of one backend.
+//! `VI_ATTR_SEND_END_EN` is kept but has no effect, as a raw socket has no
+//! END indicator: writes send the bytes given and nothing else.
+//!
+//! Sockets cannot be discovered, so `find` matches nothing, and other
+//! resource classes are not found. Events and asynchronous I/O are not
+//! supported.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use socket2::SockRef;
+
+use super::locks::Locks;
+use super::VisaBackend;
+use crate::attr::{self, Access};
+use crate::ffi::*;
+use crate::resource::ResourceName;
+use crate::status::{completion, error, Completion, Result, VisaError};
+
+/// The default `VI_ATTR_TMO_VALUE`, in milliseconds.
+const DEFAULT_TIMEOUT: u32 = 2000;
+
+/// A `VisaBackend` for TCPIP SOCKET resources, see the module documentation.
+pub struct SocketBackend {
+    state: Mutex<State>,
+    /// Signalled when locks are released.
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    next_handle: ViObject,
+    next_key: u32,
+    objects: HashMap<ViObject, Object>,
+    /// Locks by canonical resource name.
+    locks: HashMap<String, Locks>,
+}
+
+enum Object {
+    Rm,
+    Session(Session),
+}
+
+/// A session: its resource and its connection, which is used outside the
+/// state lock so that a read does not block the other sessions.
+struct Session {
+    name: String,
+    connection: Arc<Mutex<Connection>>,
+}
+
+struct Connection {
+    stream: TcpStream,
+    host: String,
+    port: u16,
+    board: u16,
+    /// Received bytes not read yet.
+    pending: Vec<u8>,
+    timeout: ViUInt32,
+    term_char: u8,
+    term_char_en: bool,
+    /// Only read back, see the module documentation.
+    send_end_en: bool,
+    suppress_end_en: bool,
+    io_prot: ViUInt16,
+}
+
+/// `VI_ERROR_TMO` for timeouts, `VI_ERROR_CONN_LOST` if the peer closed the
+/// connection and `VI_ERROR_IO` otherwise.
+fn io_error(e: io::Error) -> VisaError {
+    match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => error(VI_ERROR_TMO),
+        io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::BrokenPipe
+        | io::ErrorKind::UnexpectedEof => error(VI_ERROR_CONN_LOST),
+        _ => error(VI_ERROR_IO),
+    }
+}
+
+/// `timeout` milliseconds from now, `None` for `VI_TMO_INFINITE`.
+fn deadline(timeout: ViUInt32) -> Option<Instant> {
+    (timeout != VI_TMO_INFINITE).then(|| Instant::now() + Duration::from_millis(timeout.into()))
+}
+
+/// The time left until `deadline`, at least a millisecond as sockets do not
+/// accept zero timeouts.
+fn remaining(deadline: Option<Instant>) -> Option<Duration> {
+    deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()).max(Duration::from_millis(1)))
+}
+
+fn write_number(value: u64, out: &mut [u8]) -> Result {
+    match out.len() {
+        1 => out.copy_from_slice(&(value as u8).to_ne_bytes()),
+        2 => out.copy_from_slice(&(value as u16).to_ne_bytes()),
+        4 => out.copy_from_slice(&(value as u32).to_ne_bytes()),
+        8 => out.copy_from_slice(&value.to_ne_bytes()),
+        _ => return Err(error(VI_ERROR_INV_PARAMETER)),
+    }
+    Ok(Completion::SUCCESS)
+}
+
+fn write_string(value: &str, out: &mut [u8]) -> Result {
+    let len = value.len().min(out.len().saturating_sub(1));
+    out[..len].copy_from_slice(&value.as_bytes()[..len]);
+    if let Some(nul) = out.get_mut(len) {
+        *nul = 0;
+    }
+    Ok(Completion::SUCCESS)
+}
+
+impl Connection {
+    fn open(board: u16, host: &str, port: u16) -> Result<Self> {
+        let addresses: Vec<SocketAddr> = (host, port)
+            .to_socket_addrs()
+            .map_err(|_| error(VI_ERROR_RSRC_NFOUND))?
+            .collect();
+        let timeout = Duration::from_millis(DEFAULT_TIMEOUT.into());
+        let stream = addresses
+            .iter()
+            .find_map(|address| TcpStream::connect_timeout(address, timeout).ok())
+            .ok_or_else(|| error(VI_ERROR_RSRC_NFOUND))?;
+        stream.set_nodelay(true).map_err(io_error)?;
+        Ok(Connection {
+            stream,
+            host: host.to_string(),
+            port,
+            board,
+            pending: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            term_char: b'\n',
+            term_char_en: false,
+            send_end_en: true,
+            suppress_end_en: false,
+            io_prot: VI_PROT_NORMAL as ViUInt16,
+        })
+    }
+
+    fn get_attribute(&self, attr: ViAttr, value: &mut [u8]) -> Result {
+        let name = ResourceName::TcpipSocket {
+            board: self.board,
+            host: self.host.clone(),
+            port: self.port,
+        };
+        match attr {
+            VI_ATTR_RSRC_NAME => write_string(&name.to_string(), value),
+            VI_ATTR_RSRC_CLASS => write_string("SOCKET", value),
+            VI_ATTR_INTF_TYPE => write_number(VI_INTF_TCPIP.into(), value),
+            VI_ATTR_INTF_NUM => write_number(self.board.into(), value),
+            VI_ATTR_TCPIP_ADDR => {
+                let address = self.stream.peer_addr().map_err(io_error)?;
+                write_string(&address.ip().to_string(), value)
+            }
+            VI_ATTR_TCPIP_HOSTNAME => write_string(&self.host, value),
+            VI_ATTR_TCPIP_PORT => write_number(self.port.into(), value),
+            VI_ATTR_TCPIP_NODELAY => write_number(self.stream.nodelay().map_err(io_error)?.into(), value),
+            VI_ATTR_TCPIP_KEEPALIVE => {
+                write_number(SockRef::from(&self.stream).keepalive().map_err(io_error)?.into(), value)
+            }
+            VI_ATTR_TMO_VALUE => write_number(self.timeout.into(), value),
+            VI_ATTR_TERMCHAR => write_number(self.term_char.into(), value),
+            VI_ATTR_TERMCHAR_EN => write_number(self.term_char_en.into(), value),
+            VI_ATTR_SEND_END_EN => write_number(self.send_end_en.into(), value),
+            VI_ATTR_SUPPRESS_END_EN => write_number(self.suppress_end_en.into(), value),
+            VI_ATTR_IO_PROT => write_number(self.io_prot.into(), value),
+            _ => Err(error(VI_ERROR_NSUP_ATTR)),
+        }
+    }
+
+    fn set_attribute(&mut self, attr: ViAttr, value: ViAttrState) -> Result {
+        let flag = value != 0;
+        match attr {
+            VI_ATTR_TCPIP_NODELAY => self.stream.set_nodelay(flag).map_err(io_error)?,
+            VI_ATTR_TCPIP_KEEPALIVE => SockRef::from(&self.stream).set_keepalive(flag).map_err(io_error)?,
+            VI_ATTR_TMO_VALUE => self.timeout = value as ViUInt32,
+            VI_ATTR_TERMCHAR => self.term_char = value as u8,
+            VI_ATTR_TERMCHAR_EN => self.term_char_en = flag,
+            VI_ATTR_SEND_END_EN => self.send_end_en = flag,
+            VI_ATTR_SUPPRESS_END_EN => self.suppress_end_en = flag,
+            VI_ATTR_IO_PROT => self.io_prot = value as ViUInt16,
+            _ => return Err(error(VI_ERROR_NSUP_ATTR)),
+        }
+        Ok(Completion::SUCCESS)
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let deadline = deadline(self.timeout);
+        let term_char = self.term_char_en.then_some(self.term_char);
+        let mut first = true;
+        loop {
+            let window = &self.pending[..self.pending.len().min(buf.len())];
+            let (count, status) = match term_char.and_then(|c| window.iter().position(|&b| b == c)) {
+                Some(position) => (position + 1, VI_SUCCESS_TERM_CHAR),
+                None if self.pending.len() >= buf.len() => (buf.len(), VI_SUCCESS_MAX_CNT),
+                None if !self.pending.is_empty() && term_char.is_none() && !self.suppress_end_en => {
+                    (self.pending.len(), VI_SUCCESS)
+                }
+                None => {
+                    if !first && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return Err(error(VI_ERROR_TMO));
+                    }
+                    first = false;
+                    self.receive(deadline)?;
+                    continue;
+                }
+            };
+            buf[..count].copy_from_slice(&self.pending[..count]);
+            self.pending.drain(..count);
+            return Ok((count, completion(status)));
+        }
+    }
+
+    /// Appends the next bytes received to `pending`.
+    fn receive(&mut self, deadline: Option<Instant>) -> Result<()> {
+        self.stream.set_read_timeout(remaining(deadline)).map_err(io_error)?;
+        let mut chunk = [0; 4096];
+        match self.stream.read(&mut chunk) {
+            Ok(0) => Err(error(VI_ERROR_CONN_LOST)),
+            Ok(count) => {
+                self.pending.extend_from_slice(&chunk[..count]);
+                Ok(())
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(()),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        self.stream.set_write_timeout(remaining(deadline(self.timeout))).map_err(io_error)?;
+        self.stream.write_all(data).map_err(io_error)?;
+        Ok(data.len())
+    }
+
+    /// Queries `*STB?`, as VISA does for `VI_PROT_4882_STRS`.
+    fn read_stb(&mut self) -> Result<ViUInt16> {
+        if self.io_prot != VI_PROT_4882_STRS as ViUInt16 {
+            return Err(error(VI_ERROR_NSUP_OPER));
+        }
+        self.write(b"*STB?\n")?;
+        let deadline = deadline(self.timeout);
+        loop {
+            if let Some(position) = self.pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=position).collect();
+                return std::str::from_utf8(&line)
+                    .ok()
+                    .and_then(|line| line.trim().trim_start_matches('+').parse().ok())
+                    .ok_or_else(|| error(VI_ERROR_IO));
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(error(VI_ERROR_TMO));
+            }
+            self.receive(deadline)?;
+        }
+    }
+}
+
+impl SocketBackend {
+    pub fn new() -> Self {
+        SocketBackend {
+            state: Mutex::new(State {
+                next_handle: 1,
+                ..State::default()
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The connection of `vi`, if `vi` may use it despite the locks.
+    fn connection(&self, vi: ViSession) -> Result<Arc<Mutex<Connection>>> {
+        let state = self.lock_state();
+        let session = state.session(vi)?;
+        if !state.locks.get(&session.name).is_none_or(|locks| locks.accessible(vi)) {
+            return Err(error(VI_ERROR_RSRC_LOCKED));
+        }
+        Ok(session.connection.clone())
+    }
+
+    /// Runs `f` on the connection of `vi`, whatever the locks.
+    fn with_connection<T>(&self, vi: ViSession, f: impl FnOnce(&mut Connection) -> Result<T>) -> Result<T> {
+        let connection = self.lock_state().session(vi)?.connection.clone();
+        let mut connection = connection.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut connection)
+    }
+}
+
+impl Default for SocketBackend {
+    fn default() -> Self {
+        SocketBackend::new()
+    }
+}
+
+impl fmt::Debug for SocketBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock_state();
+        let mut names: Vec<&str> = state
+            .objects
+            .values()
+            .filter_map(|object| match object {
+                Object::Session(session) => Some(session.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        names.sort();
+        f.debug_struct("SocketBackend").field("sessions", &names).finish_non_exhaustive()
+    }
+}
+
+impl State {
+    fn handle(&mut self, object: Object) -> ViObject {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.objects.insert(handle, object);
+        handle
+    }
+
+    fn session(&self, vi: ViSession) -> Result<&Session> {
+        match self.objects.get(&vi) {
+            Some(Object::Session(session)) => Ok(session),
+            _ => Err(error(VI_ERROR_INV_OBJECT)),
+        }
+    }
+
+    fn check_rm(&self, rm: ViSession) -> Result<()> {
+        match self.objects.get(&rm) {
+            Some(Object::Rm) => Ok(()),
+            _ => Err(error(VI_ERROR_INV_OBJECT)),
+        }
+    }
+}
+
+impl VisaBackend for SocketBackend {
+    fn open_default_rm(&self) -> Result<ViSession> {
+        Ok(self.lock_state().handle(Object::Rm))
+    }
+
+    fn open(&self, rm: ViSession, resource: &str, access_mode: ViAccessMode, timeout: ViUInt32) -> Result<ViSession> {
+        self.lock_state().check_rm(rm)?;
+        let name = resource.parse::<ResourceName>().map_err(|_| error(VI_ERROR_INV_RSRC_NAME))?;
+        let ResourceName::TcpipSocket { board, host, port } = &name else {
+            return Err(error(VI_ERROR_RSRC_NFOUND));
+        };
+        // Connect without holding the state lock.
+        let connection = Connection::open(*board, host, *port)?;
+        let vi = self.lock_state().handle(Object::Session(Session {
+            name: name.to_string(),
+            connection: Arc::new(Mutex::new(connection)),
+        }));
+        if access_mode & VI_EXCLUSIVE_LOCK as ViAccessMode != 0 {
+            if let Err(e) = self.lock(vi, VI_EXCLUSIVE_LOCK as ViAccessMode, timeout, None) {
+                self.lock_state().objects.remove(&vi);
+                return Err(e);
+            }
+        }
+        Ok(vi)
+    }
+
+    fn close(&self, object: ViObject) -> Result {
+        let mut state = self.lock_state();
+        match state.objects.remove(&object) {
+            Some(Object::Session(session)) => {
+                if let Some(locks) = state.locks.get_mut(&session.name) {
+                    locks.release(object);
+                }
+                drop(state);
+                self.changed.notify_all();
+                Ok(Completion::SUCCESS)
+            }
+            Some(Object::Rm) => Ok(Completion::SUCCESS),
+            None => Err(error(VI_ERROR_INV_OBJECT)),
+        }
+    }
+
+    fn find_rsrc(&self, rm: ViSession, _expr: &str) -> Result<(ViFindList, ViUInt32, String)> {
+        self.lock_state().check_rm(rm)?;
+        Err(error(VI_ERROR_RSRC_NFOUND))
+    }
+
+    fn find_next(&self, _list: ViFindList) -> Result<String> {
+        // `find_rsrc` never returns a list.
+        Err(error(VI_ERROR_INV_OBJECT))
+    }
+
+    fn get_attribute(&self, object: ViObject, attr: ViAttr, value: &mut [u8]) -> Result {
+        self.with_connection(object, |connection| connection.get_attribute(attr, value))
+    }
+
+    fn set_attribute(&self, object: ViObject, attr: ViAttr, value: ViAttrState) -> Result {
+        if attr::info(attr).is_some_and(|info| info.access == Access::ReadOnly) {
+            return Err(error(VI_ERROR_ATTR_READONLY));
+        }
+        self.with_connection(object, |connection| connection.set_attribute(attr, value))
+    }
+
+    fn read(&self, vi: ViSession, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let connection = self.connection(vi)?;
+        let mut connection = connection.lock().unwrap_or_else(PoisonError::into_inner);
+        connection.read(buf)
+    }
+
+    fn write(&self, vi: ViSession, data: &[u8]) -> Result<usize> {
+        let connection = self.connection(vi)?;
+        let mut connection = connection.lock().unwrap_or_else(PoisonError::into_inner);
+        connection.write(data)
+    }
+
+    fn read_stb(&self, vi: ViSession) -> Result<ViUInt16> {
+        let connection = self.connection(vi)?;
+        let mut connection = connection.lock().unwrap_or_else(PoisonError::into_inner);
+        connection.read_stb()
+    }
+
+    fn enable_event(&self, vi: ViSession, _event_type: ViEventType, _mechanism: ViUInt16) -> Result {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_EVENT))
+    }
+
+    fn disable_event(&self, vi: ViSession, _event_type: ViEventType, _mechanism: ViUInt16) -> Result {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_EVENT))
+    }
+
+    fn discard_events(&self, vi: ViSession, _event_type: ViEventType, _mechanism: ViUInt16) -> Result {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_EVENT))
+    }
+
+    fn wait_on_event(
+        &self,
+        vi: ViSession,
+        _event_type: ViEventType,
+        _timeout: ViUInt32,
+    ) -> Result<(ViEventType, ViEvent)> {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_EVENT))
+    }
+
+    unsafe fn install_handler(&self, vi: ViSession, _: ViEventType, _: ViHndlr, _: ViAddr) -> Result {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_EVENT))
+    }
+
+    unsafe fn uninstall_handler(&self, vi: ViSession, _: ViEventType, _: ViHndlr, _: ViAddr) -> Result {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_HNDLR_REF))
+    }
+
+    fn lock(
+        &self,
+        vi: ViSession,
+        lock_type: ViAccessMode,
+        timeout: ViUInt32,
+        requested_key: Option<&str>,
+    ) -> Result<(Completion, String)> {
+        let deadline = deadline(timeout);
+        let mut state = self.lock_state();
+        loop {
+            let name = state.session(vi)?.name.clone();
+            let key = requested_key.map(str::to_string).unwrap_or_else(|| {
+                state.next_key += 1;
+                format!("socket-key-{}", state.next_key)
+            });
+            if let Some(result) = state.locks.entry(name).or_default().try_lock(vi, lock_type, key) {
+                return result;
+            }
+            let now = Instant::now();
+            state = match deadline {
+                Some(deadline) if now >= deadline => return Err(error(VI_ERROR_TMO)),
+                Some(deadline) => {
+                    let waited = self.changed.wait_timeout(state, deadline - now);
+                    waited.unwrap_or_else(PoisonError::into_inner).0
+                }
+                None => self.changed.wait(state).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    fn unlock(&self, vi: ViSession) -> Result {
+        let mut state = self.lock_state();
+        let name = state.session(vi)?.name.clone();
+        let completion = state.locks.entry(name).or_default().unlock(vi)?;
+        drop(state);
+        self.changed.notify_all();
+        Ok(completion)
+    }
+
+    unsafe fn read_async(&self, vi: ViSession, _: *mut u8, _: usize) -> Result<ViJobId> {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_NSUP_OPER))
+    }
+
+    unsafe fn write_async(&self, vi: ViSession, _: *const u8, _: usize) -> Result<ViJobId> {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_NSUP_OPER))
+    }
+
+    fn terminate(&self, vi: ViSession, _degree: ViUInt16, _job_id: ViJobId) -> Result {
+        self.lock_state().session(vi)?;
+        Err(error(VI_ERROR_INV_JOB_ID))
+    }
+}
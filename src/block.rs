@@ -0,0 +1,285 @@
+//! IEEE 488.2 binary blocks.
+//!
+//! A definite-length block is `#<n><length><data>`, where `<n>` is the number
+//! of digits in `<length>`, e.g. `#3012` followed by 12 bytes. An indefinite
+//! block is `#0<data>` and runs until the end of the message. Either may be
+//! followed by a terminator, usually `\n`.
+//!
+//! `Session::query_binary_block` and `Session::write_binary_block` exchange
+//! blocks with an instrument; `parse` and `encode` work on bytes already in
+//! memory:
+//!
+//! ```
+//! use ni_visa_bindings::block::{self, ByteOrder};
+//!
+//! let bytes = block::encode(&[1i16, -2], ByteOrder::BigEndian).unwrap();
+//! assert_eq!(bytes, b"#14\x00\x01\xFF\xFE");
+//! assert_eq!(block::parse::<i16>(&bytes, ByteOrder::BigEndian).unwrap(), [1, -2]);
+//! ```
+
+use std::io::{self, BufRead};
+
+/// Byte order of multi-byte values in a block. IEEE 488.2 instruments default
+/// to big-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    #[default]
+    BigEndian,
+    LittleEndian,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A value type that can be sent and received in a block.
+pub trait BlockValue: sealed::Sealed + Copy {
+    /// Size of one value in bytes.
+    const SIZE: usize;
+
+    #[doc(hidden)]
+    fn from_bytes(bytes: &[u8], order: ByteOrder) -> Self;
+
+    #[doc(hidden)]
+    fn extend_bytes(self, out: &mut Vec<u8>, order: ByteOrder);
+}
+
+macro_rules! block_values {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl BlockValue for $ty {
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                fn from_bytes(bytes: &[u8], order: ByteOrder) -> Self {
+                    let bytes = bytes.try_into().unwrap();
+                    match order {
+                        ByteOrder::BigEndian => <$ty>::from_be_bytes(bytes),
+                        ByteOrder::LittleEndian => <$ty>::from_le_bytes(bytes),
+                    }
+                }
+
+                fn extend_bytes(self, out: &mut Vec<u8>, order: ByteOrder) {
+                    match order {
+                        ByteOrder::BigEndian => out.extend_from_slice(&self.to_be_bytes()),
+                        ByteOrder::LittleEndian => out.extend_from_slice(&self.to_le_bytes()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+block_values!(i8, u8, i16, u16, i32, u32, f32, f64);
+
+/// Bytes converted per read of a definite-length block. A multiple of every
+/// `BlockValue::SIZE`.
+const CHUNK: usize = 64 * 1024;
+
+/// The largest length a definite-length header can hold (9 digits).
+const MAX_LENGTH: usize = 999_999_999;
+
+/// Most values allocated up front for a definite-length block, see
+/// `reserved_values`.
+const MAX_RESERVED: usize = 1 << 20;
+
+/// A message source that knows where messages end.
+pub(crate) trait MessageRead: BufRead {
+    /// Whether everything up to the end of the current message was consumed.
+    fn message_done(&self) -> bool;
+
+    /// Appends the rest of the current message to `buf`.
+    fn read_rest(&mut self, buf: &mut Vec<u8>) -> io::Result<usize>;
+}
+
+impl MessageRead for &[u8] {
+    fn message_done(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn read_rest(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        io::Read::read_to_end(self, buf)
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// The `#<n><length>` header for `length` bytes of data.
+pub fn header(length: usize) -> io::Result<String> {
+    if length > MAX_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} bytes do not fit in a definite-length block", length),
+        ));
+    }
+    let digits = length.to_string();
+    Ok(format!("#{}{}", digits.len(), digits))
+}
+
+/// Encodes `values` as a definite-length block, without a terminator.
+pub fn encode<T: BlockValue>(values: &[T], order: ByteOrder) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    extend_block(&mut bytes, values, order)?;
+    Ok(bytes)
+}
+
+/// Appends the block for `values` to `out`, reserving its full size up front.
+pub(crate) fn extend_block<T: BlockValue>(out: &mut Vec<u8>, values: &[T], order: ByteOrder) -> io::Result<()> {
+    let length = values.len() * T::SIZE;
+    let header = header(length)?;
+    out.reserve(header.len() + length);
+    out.extend_from_slice(header.as_bytes());
+    for value in values {
+        value.extend_bytes(out, order);
+    }
+    Ok(())
+}
+
+/// Parses a complete response holding one block. Anything before the `#` is
+/// skipped, and a trailing `\n` or `\r\n` is accepted.
+pub fn parse<T: BlockValue>(mut bytes: &[u8], order: ByteOrder) -> io::Result<Vec<T>> {
+    read_block(&mut bytes, order)
+}
+
+/// Reads one block from the current message of `reader`, including the
+/// terminator.
+pub(crate) fn read_block<T: BlockValue, R: MessageRead>(reader: &mut R, order: ByteOrder) -> io::Result<Vec<T>> {
+    skip_to_hash(reader)?;
+    let digits = read_byte(reader)?;
+    let values = match digits {
+        b'0' => {
+            let mut data = Vec::new();
+            reader.read_rest(&mut data)?;
+            let data = strip_terminator(&data);
+            check_multiple::<T>(data.len())?;
+            data.chunks_exact(T::SIZE).map(|chunk| T::from_bytes(chunk, order)).collect()
+        }
+        b'1'..=b'9' => {
+            let mut length = 0usize;
+            for _ in 0..digits - b'0' {
+                let digit = read_byte(reader)?;
+                if !digit.is_ascii_digit() {
+                    return Err(invalid(format!("invalid block length digit {:?}", char::from(digit))));
+                }
+                length = length * 10 + usize::from(digit - b'0');
+            }
+            check_multiple::<T>(length)?;
+            let values = read_values(reader, length, order)?;
+            check_trailer(reader)?;
+            values
+        }
+        other => return Err(invalid(format!("invalid block header digit {:?}", char::from(other)))),
+    };
+    Ok(values)
+}
+
+fn check_multiple<T: BlockValue>(length: usize) -> io::Result<()> {
+    if !length.is_multiple_of(T::SIZE) {
+        return Err(invalid(format!(
+            "block of {} bytes is not a whole number of {}-byte values",
+            length,
+            T::SIZE
+        )));
+    }
+    Ok(())
+}
+
+fn skip_to_hash<R: MessageRead>(reader: &mut R) -> io::Result<()> {
+    // A session has consumed its previous message before the first read, so
+    // `message_done` only tells something once data was read.
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            if reader.message_done() {
+                return Err(no_block());
+            }
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        match available.iter().position(|&b| b == b'#') {
+            Some(pos) => {
+                reader.consume(pos + 1);
+                return Ok(());
+            }
+            None => {
+                let len = available.len();
+                reader.consume(len);
+                if reader.message_done() {
+                    return Err(no_block());
+                }
+            }
+        }
+    }
+}
+
+fn no_block() -> io::Error {
+    invalid("response holds no binary block".to_string())
+}
+
+fn read_byte<R: MessageRead>(reader: &mut R) -> io::Result<u8> {
+    if reader.message_done() {
+        return Err(invalid("binary block header is truncated".to_string()));
+    }
+    let mut byte = [0u8];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Values to reserve for a definite-length block of `length` bytes.
+///
+/// The whole block is reserved up front so that blocks of up to
+/// `MAX_RESERVED` values are read without reallocating. The header is not
+/// trusted beyond that: a corrupt or hostile length only grows the vector as
+/// data actually arrives.
+pub(crate) fn reserved_values<T: BlockValue>(length: usize) -> usize {
+    (length / T::SIZE).min(MAX_RESERVED)
+}
+
+/// Reads `length` bytes of values in chunks. A message that ends before
+/// `length` bytes arrived is an error as soon as its END is read, rather than
+/// a read that waits for the timeout.
+fn read_values<T: BlockValue, R: MessageRead>(reader: &mut R, length: usize, order: ByteOrder) -> io::Result<Vec<T>> {
+    let mut values = Vec::with_capacity(reserved_values::<T>(length));
+    let mut chunk = vec![0u8; CHUNK.min(length)];
+    let mut remaining = length;
+    while remaining > 0 {
+        let chunk = &mut chunk[..CHUNK.min(remaining)];
+        let mut filled = 0;
+        while filled < chunk.len() {
+            if reader.message_done() {
+                return Err(invalid(format!("binary block ended {} bytes early", remaining - filled)));
+            }
+            match reader.read(&mut chunk[filled..]) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(count) => filled += count,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        values.extend(chunk.chunks_exact(T::SIZE).map(|bytes| T::from_bytes(bytes, order)));
+        remaining -= chunk.len();
+    }
+    Ok(values)
+}
+
+/// Checks that only a terminator, if anything, follows a definite-length block.
+fn check_trailer<R: MessageRead>(reader: &mut R) -> io::Result<()> {
+    if reader.message_done() {
+        return Ok(());
+    }
+    let mut rest = Vec::new();
+    reader.read_rest(&mut rest)?;
+    if strip_terminator(&rest).is_empty() {
+        Ok(())
+    } else {
+        Err(invalid(format!("{} unexpected bytes after binary block", rest.len())))
+    }
+}
+
+fn strip_terminator(data: &[u8]) -> &[u8] {
+    data.strip_suffix(b"\r\n")
+        .or_else(|| data.strip_suffix(b"\n"))
+        .unwrap_or(data)
+}
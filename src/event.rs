@@ -0,0 +1,290 @@
+//! VISA events: `viEnableEvent`, `viWaitOnEvent` and event contexts.
+
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::attr::{self, Attribute};
+use crate::backend::VisaBackend;
+use crate::ffi::*;
+use crate::rm::ResourceManager;
+use crate::session::{timeout_ms, Session};
+use crate::status::{Completion, Result, Status};
+
+/// A VISA event type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// `VI_EVENT_SERVICE_REQ`
+    ServiceRequest,
+    /// `VI_EVENT_IO_COMPLETION`, raised when an asynchronous operation ends.
+    IoCompletion,
+    /// `VI_EVENT_TRIG`
+    Trigger,
+    /// `VI_EVENT_CLEAR`
+    Clear,
+    /// `VI_EVENT_USB_INTR`
+    UsbInterrupt,
+    /// `VI_EVENT_EXCEPTION`, only delivered to handlers.
+    Exception,
+    /// `VI_EVENT_PXI_INTR`
+    PxiInterrupt,
+    /// `VI_EVENT_GPIB_CIC`
+    GpibCic,
+    /// `VI_EVENT_GPIB_TALK`
+    GpibTalk,
+    /// `VI_EVENT_GPIB_LISTEN`
+    GpibListen,
+    /// `VI_EVENT_VXI_SIGP`
+    VxiSignal,
+    /// `VI_EVENT_VXI_VME_INTR`
+    VxiVmeInterrupt,
+    /// `VI_EVENT_VXI_VME_SYSFAIL`
+    VxiVmeSysfail,
+    /// `VI_EVENT_VXI_VME_SYSRESET`
+    VxiVmeSysreset,
+    /// `VI_EVENT_TCPIP_CONNECT`
+    TcpipConnect,
+    /// `VI_ALL_ENABLED_EVENTS`, to wait for or disable every enabled event.
+    AllEnabled,
+    /// Any other event type.
+    Other(ViEventType),
+}
+
+const KINDS: &[(EventKind, u32)] = &[
+    (EventKind::ServiceRequest, VI_EVENT_SERVICE_REQ),
+    (EventKind::IoCompletion, VI_EVENT_IO_COMPLETION),
+    (EventKind::Trigger, VI_EVENT_TRIG),
+    (EventKind::Clear, VI_EVENT_CLEAR),
+    (EventKind::UsbInterrupt, VI_EVENT_USB_INTR),
+    (EventKind::Exception, VI_EVENT_EXCEPTION),
+    (EventKind::PxiInterrupt, VI_EVENT_PXI_INTR),
+    (EventKind::GpibCic, VI_EVENT_GPIB_CIC),
+    (EventKind::GpibTalk, VI_EVENT_GPIB_TALK),
+    (EventKind::GpibListen, VI_EVENT_GPIB_LISTEN),
+    (EventKind::VxiSignal, VI_EVENT_VXI_SIGP),
+    (EventKind::VxiVmeInterrupt, VI_EVENT_VXI_VME_INTR),
+    (EventKind::VxiVmeSysfail, VI_EVENT_VXI_VME_SYSFAIL),
+    (EventKind::VxiVmeSysreset, VI_EVENT_VXI_VME_SYSRESET),
+    (EventKind::TcpipConnect, VI_EVENT_TCPIP_CONNECT),
+    (EventKind::AllEnabled, VI_ALL_ENABLED_EVENTS),
+];
+
+impl EventKind {
+    pub fn from_raw(event_type: ViEventType) -> Self {
+        KINDS
+            .iter()
+            .find(|&&(_, raw)| raw as ViEventType == event_type)
+            .map_or(EventKind::Other(event_type), |&(kind, _)| kind)
+    }
+
+    /// The `VI_EVENT_*` constant.
+    pub fn raw(&self) -> ViEventType {
+        match self {
+            EventKind::Other(raw) => *raw,
+            kind => KINDS.iter().find(|(k, _)| k == kind).map(|&(_, raw)| raw as ViEventType).unwrap(),
+        }
+    }
+}
+
+/// How events are delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mechanism {
+    /// Queued for `Session::wait_event` (`VI_QUEUE`).
+    Queue,
+    /// Passed to installed handlers (`VI_HNDLR`).
+    Handler,
+    /// Held back from installed handlers until re-enabled with `Handler`
+    /// (`VI_SUSPEND_HNDLR`).
+    SuspendHandler,
+}
+
+impl Mechanism {
+    pub(crate) fn raw(&self) -> ViUInt16 {
+        (match self {
+            Mechanism::Queue => VI_QUEUE,
+            Mechanism::Handler => VI_HNDLR,
+            Mechanism::SuspendHandler => VI_SUSPEND_HNDLR,
+        }) as ViUInt16
+    }
+}
+
+/// An enabled event, disabled (`viDisableEvent`) when dropped. If the event
+/// was already enabled (`VI_SUCCESS_EVENT_EN`), it is left enabled.
+///
+/// The guard borrows the session, which is used through it like through a
+/// lock guard, so it cannot outlive the session:
+///
+/// ```compile_fail
+/// # use ni_visa_bindings::{EventKind, ResourceManager};
+/// # let rm = ResourceManager::new().unwrap();
+/// let mut session = rm.open("GPIB0::5::INSTR").unwrap();
+/// let guard = session.enable_event(EventKind::ServiceRequest).unwrap();
+/// drop(session);
+/// drop(guard);
+/// ```
+#[derive(Debug)]
+#[must_use = "the event is disabled again when the guard is dropped"]
+pub struct EventGuard<'s, 'rm> {
+    session: &'s mut Session<'rm>,
+    enabled: EnabledEvent<'rm>,
+}
+
+impl<'s, 'rm> EventGuard<'s, 'rm> {
+    pub(crate) fn enable(session: &'s mut Session<'rm>, kind: EventKind, mechanism: Mechanism) -> Result<Self> {
+        let enabled = EnabledEvent::enable(session.resource_manager(), session.as_raw(), kind, mechanism)?;
+        Ok(EventGuard { session, enabled })
+    }
+
+    pub fn kind(&self) -> EventKind {
+        self.enabled.kind
+    }
+
+    pub fn mechanism(&self) -> Mechanism {
+        self.enabled.mechanism
+    }
+
+    /// Disables the event, reporting the status `Drop` would ignore.
+    pub fn disable(self) -> Result {
+        self.enabled.disable()
+    }
+}
+
+impl<'rm> Deref for EventGuard<'_, 'rm> {
+    type Target = Session<'rm>;
+
+    fn deref(&self) -> &Self::Target {
+        self.session
+    }
+}
+
+impl DerefMut for EventGuard<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.session
+    }
+}
+
+/// The event of an `EventGuard`, without the borrow, for the handlers that
+/// enable their events.
+#[derive(Debug)]
+pub(crate) struct EnabledEvent<'rm> {
+    handle: ViSession,
+    kind: EventKind,
+    mechanism: Mechanism,
+    /// Whether the event was enabled before, so dropping must not disable it.
+    was_enabled: bool,
+    rm: &'rm ResourceManager,
+}
+
+impl<'rm> EnabledEvent<'rm> {
+    pub(crate) fn enable(
+        rm: &'rm ResourceManager,
+        handle: ViSession,
+        kind: EventKind,
+        mechanism: Mechanism,
+    ) -> Result<Self> {
+        let completion = rm.backend().enable_event(handle, kind.raw(), mechanism.raw())?;
+        Ok(EnabledEvent {
+            handle,
+            kind,
+            mechanism,
+            was_enabled: completion.raw() == VI_SUCCESS_EVENT_EN as ViStatus,
+            rm,
+        })
+    }
+
+    fn disable(self) -> Result {
+        let event = ManuallyDrop::new(self);
+        if event.was_enabled {
+            return Ok(Completion::SUCCESS);
+        }
+        event.rm.backend().disable_event(event.handle, event.kind.raw(), event.mechanism.raw())
+    }
+}
+
+impl Drop for EnabledEvent<'_> {
+    fn drop(&mut self) {
+        if !self.was_enabled {
+            let _ = self.rm.backend().disable_event(self.handle, self.kind.raw(), self.mechanism.raw());
+        }
+    }
+}
+
+/// An event taken from the queue, with its context closed when dropped, or
+/// passed to a handler installed with `Session::on_event`.
+#[derive(Debug)]
+pub struct Event {
+    backend: Arc<dyn VisaBackend>,
+    context: ViEvent,
+    kind: EventKind,
+    /// Whether the context is closed on drop, i.e. not owned by a handler call.
+    owned: bool,
+}
+
+impl Event {
+    /// Waits on `handle`'s queue for an event of type `kind`.
+    pub(crate) fn wait(
+        backend: &Arc<dyn VisaBackend>,
+        handle: ViSession,
+        kind: EventKind,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let (event_type, context) = backend.wait_on_event(handle, kind.raw(), timeout_ms(timeout))?;
+        Ok(Event {
+            backend: backend.clone(),
+            context,
+            kind: EventKind::from_raw(event_type),
+            owned: true,
+        })
+    }
+
+    /// An event delivered to a handler, whose context VISA closes itself.
+    pub(crate) fn from_context(backend: Arc<dyn VisaBackend>, context: ViEvent, event_type: ViEventType) -> Self {
+        Event {
+            backend,
+            context,
+            kind: EventKind::from_raw(event_type),
+            owned: false,
+        }
+    }
+
+    /// The type of the event, which is useful after waiting for
+    /// `EventKind::AllEnabled`.
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    /// Reads an attribute of the event context, e.g. `attr::RecvTrigId`.
+    pub fn get<A: Attribute>(&self) -> Result<A::Value> {
+        attr::get::<A>(&*self.backend, self.context)
+    }
+
+    /// The status of the operation that raised an I/O completion or
+    /// exception event (`VI_ATTR_STATUS`).
+    pub fn status(&self) -> Result<Status> {
+        self.get::<attr::Status>().map(Status::from_raw)
+    }
+
+    /// The job of an I/O completion event (`VI_ATTR_JOB_ID`).
+    pub fn job_id(&self) -> Result<ViJobId> {
+        self.get::<attr::JobId>()
+    }
+
+    /// The data of a USB interrupt event (`VI_ATTR_USB_RECV_INTR_DATA`).
+    pub fn usb_interrupt_data(&self) -> Result<Vec<u8>> {
+        self.get::<attr::UsbRecvIntrData>()
+    }
+
+    /// The raw context handle. It must not be closed.
+    pub fn as_raw(&self) -> ViEvent {
+        self.context
+    }
+}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        if self.owned {
+            let _ = self.backend.close(self.context);
+        }
+    }
+}
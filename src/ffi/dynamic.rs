@@ -0,0 +1,351 @@
+//! Runtime resolution of the VISA library (`dynamic-loading` feature).
+//!
+//! Instead of linking against `visa` at build time, every function of this
+//! module looks up its symbol in a shared library opened on first use. The
+//! library is searched in this order:
+//!
+//! 1. the path passed to [`load_library`], if it was called before any VISA
+//!    function;
+//! 2. the path in the `VISA_LIBRARY` environment variable;
+//! 3. the default install locations of the platform (`libvisa.so`,
+//!    `visa64.dll`/`visa32.dll`, the `VISA.framework`, ...).
+//!
+//! When no library can be opened, functions returning a [`ViStatus`] return
+//! `VI_ERROR_LIBRARY_NFOUND`; when the library does not export a function,
+//! they return `VI_ERROR_NIMPL_OPER`. Call [`library`] to get the detailed
+//! [`LoadError`]. The formatted I/O functions taking variadic arguments or a
+//! `ViVAList` (`viPrintf`, `viVPrintf`, `viScanf`, ...) are not available in
+//! this mode; use `viWrite`/`viRead` or the buffered I/O functions instead.
+
+use std::env;
+use std::error::Error;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::sync::OnceLock;
+
+use super::*;
+
+/// Environment variable holding the path of the VISA library to load.
+pub const VISA_LIBRARY_ENV: &str = "VISA_LIBRARY";
+
+static LIBRARY: OnceLock<Result<Library, LoadError>> = OnceLock::new();
+
+/// A VISA shared library opened at runtime.
+pub struct Library {
+    path: OsString,
+    functions: Functions,
+    // Declared last so that the symbols above never outlive the library.
+    _library: libloading::Library,
+}
+
+impl Library {
+    /// The path or file name the library was opened from.
+    pub fn path(&self) -> &OsStr {
+        &self.path
+    }
+
+    /// Names of the VISA functions the library does not export.
+    pub fn missing_functions(&self) -> Vec<&'static str> {
+        self.functions.missing()
+    }
+
+    fn open(path: &OsStr) -> Result<Self, libloading::Error> {
+        unsafe {
+            let library = libloading::Library::new(path)?;
+            Ok(Library {
+                path: path.to_owned(),
+                functions: Functions::resolve(&library),
+                _library: library,
+            })
+        }
+    }
+}
+
+impl fmt::Debug for Library {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Library")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error returned when the VISA library cannot be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// None of the candidate libraries could be opened. Holds every path
+    /// that was tried together with the error reported by the loader.
+    NotFound { attempts: Vec<(OsString, String)> },
+    /// A library was already loaded (or tried) before [`load_library`] was
+    /// called. The library is selected once per process.
+    AlreadyLoaded { path: Option<OsString> },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::NotFound { attempts } => {
+                write!(f, "VISA library not found")?;
+                for (index, (path, reason)) in attempts.iter().enumerate() {
+                    let separator = if index == 0 { ", tried: " } else { "; " };
+                    write!(f, "{}{} ({})", separator, path.to_string_lossy(), reason)?;
+                }
+                Ok(())
+            }
+            LoadError::AlreadyLoaded { path: Some(path) } => {
+                write!(
+                    f,
+                    "VISA library already loaded from {}",
+                    path.to_string_lossy()
+                )
+            }
+            LoadError::AlreadyLoaded { path: None } => {
+                write!(f, "VISA library loading was already attempted and failed")
+            }
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Loads the VISA library from `path`.
+///
+/// Must be called before any other VISA function, otherwise the library
+/// found by the default search is kept and [`LoadError::AlreadyLoaded`] is
+/// returned. Calling it again with the same path is a no-op.
+pub fn load_library(path: impl AsRef<OsStr>) -> Result<&'static Library, LoadError> {
+    let path = path.as_ref();
+    let mut initialized = false;
+    let loaded = LIBRARY.get_or_init(|| {
+        initialized = true;
+        open_first(&[path.to_owned()])
+    });
+    match loaded {
+        Ok(library) if library.path == path => Ok(library),
+        Err(error) if initialized => Err(error.clone()),
+        Ok(library) => Err(LoadError::AlreadyLoaded {
+            path: Some(library.path.clone()),
+        }),
+        Err(_) => Err(LoadError::AlreadyLoaded { path: None }),
+    }
+}
+
+/// Returns the loaded VISA library, searching for it on first use.
+pub fn library() -> Result<&'static Library, LoadError> {
+    LIBRARY
+        .get_or_init(|| match env::var_os(VISA_LIBRARY_ENV) {
+            Some(path) => open_first(&[path]),
+            None => open_first(&default_candidates()),
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+fn open_first(candidates: &[OsString]) -> Result<Library, LoadError> {
+    let mut attempts = Vec::new();
+    for candidate in candidates {
+        match Library::open(candidate) {
+            Ok(library) => return Ok(library),
+            Err(error) => attempts.push((candidate.clone(), error.to_string())),
+        }
+    }
+    Err(LoadError::NotFound { attempts })
+}
+
+fn default_candidates() -> Vec<OsString> {
+    let names: &[&str] = if cfg!(target_os = "windows") {
+        if cfg!(target_pointer_width = "64") {
+            &["visa64.dll", "visa32.dll"]
+        } else {
+            &["visa32.dll"]
+        }
+    } else if cfg!(target_os = "macos") {
+        &[
+            "/Library/Frameworks/VISA.framework/VISA",
+            "/Library/Frameworks/VISA.framework/Versions/Current/VISA",
+            "/Library/Frameworks/RsVisa.framework/RsVisa",
+        ]
+    } else {
+        &["libvisa.so", "libvisa.so.0", "librsvisa.so"]
+    };
+    names.iter().map(OsString::from).collect()
+}
+
+/// Value returned by a function whose symbol is not available.
+trait Unavailable {
+    fn unavailable(function: &'static str, library_found: bool) -> Self;
+}
+
+impl Unavailable for ViStatus {
+    fn unavailable(_function: &'static str, library_found: bool) -> Self {
+        if library_found {
+            VI_ERROR_NIMPL_OPER as ViStatus
+        } else {
+            VI_ERROR_LIBRARY_NFOUND as ViStatus
+        }
+    }
+}
+
+impl Unavailable for () {
+    fn unavailable(function: &'static str, library_found: bool) {
+        match library() {
+            Err(error) => panic!("cannot call {}: {}", function, error),
+            Ok(library) if library_found => panic!(
+                "cannot call {}: not exported by {}",
+                function,
+                library.path.to_string_lossy()
+            ),
+            Ok(_) => unreachable!(),
+        }
+    }
+}
+
+macro_rules! dynamic_functions {
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+    ($(fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        /// Names of every function of the table, in declaration order.
+        pub(crate) const FUNCTIONS: &[&str] = &[$(stringify!($name)),*];
+
+        struct Functions {
+            $($name: Option<unsafe extern "system" fn($($ty),*) $(-> $ret)?>,)*
+        }
+
+        impl Functions {
+            unsafe fn resolve(library: &libloading::Library) -> Self {
+                Functions {
+                    $($name: library
+                        .get(concat!(stringify!($name), "\0").as_bytes())
+                        .ok()
+                        .map(|symbol| *symbol),)*
+                }
+            }
+
+            fn missing(&self) -> Vec<&'static str> {
+                let mut missing = Vec::new();
+                $(if self.$name.is_none() {
+                    missing.push(stringify!($name));
+                })*
+                missing
+            }
+        }
+
+        $(
+            #[allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                match library() {
+                    Ok(Library { functions: Functions { $name: Some(function), .. }, .. }) => {
+                        function($($arg),*)
+                    }
+                    Ok(_) => <dynamic_functions!(@ret $($ret)?)>::unavailable(stringify!($name), true),
+                    Err(_) => <dynamic_functions!(@ret $($ret)?)>::unavailable(stringify!($name), false),
+                }
+            }
+        )*
+    };
+}
+
+dynamic_functions! {
+    fn viOpenDefaultRM(vi: ViPSession) -> ViStatus;
+    fn viFindRsrc(sesn: ViSession, expr: ViConstString, vi: ViPFindList, retCnt: ViPUInt32, desc: *mut ViChar) -> ViStatus;
+    fn viFindNext(vi: ViFindList, desc: *mut ViChar) -> ViStatus;
+    fn viParseRsrc(rmSesn: ViSession, rsrcName: ViConstRsrc, intfType: ViPUInt16, intfNum: ViPUInt16) -> ViStatus;
+    fn viParseRsrcEx(rmSesn: ViSession, rsrcName: ViConstRsrc, intfType: ViPUInt16, intfNum: ViPUInt16, rsrcClass: *mut ViChar, expandedUnaliasedName: *mut ViChar, aliasIfExists: *mut ViChar) -> ViStatus;
+    fn viOpen(sesn: ViSession, name: ViConstRsrc, mode: ViAccessMode, timeout: ViUInt32, vi: ViPSession) -> ViStatus;
+
+    fn viClose(vi: ViObject) -> ViStatus;
+    fn viSetAttribute(vi: ViObject, attrName: ViAttr, attrValue: ViAttrState) -> ViStatus;
+    fn viGetAttribute(vi: ViObject, attrName: ViAttr, attrValue: *mut ::std::os::raw::c_void) -> ViStatus;
+    fn viStatusDesc(vi: ViObject, status: ViStatus, desc: *mut ViChar) -> ViStatus;
+    fn viTerminate(vi: ViObject, degree: ViUInt16, jobId: ViJobId) -> ViStatus;
+    fn viLock(vi: ViSession, lockType: ViAccessMode, timeout: ViUInt32, requestedKey: ViConstKeyId, accessKey: *mut ViChar) -> ViStatus;
+    fn viUnlock(vi: ViSession) -> ViStatus;
+    fn viEnableEvent(vi: ViSession, eventType: ViEventType, mechanism: ViUInt16, context: ViEventFilter) -> ViStatus;
+    fn viDisableEvent(vi: ViSession, eventType: ViEventType, mechanism: ViUInt16) -> ViStatus;
+    fn viDiscardEvents(vi: ViSession, eventType: ViEventType, mechanism: ViUInt16) -> ViStatus;
+    fn viWaitOnEvent(vi: ViSession, inEventType: ViEventType, timeout: ViUInt32, outEventType: ViPEventType, outContext: ViPEvent) -> ViStatus;
+    fn viInstallHandler(vi: ViSession, eventType: ViEventType, handler: ViHndlr, userHandle: ViAddr) -> ViStatus;
+    fn viUninstallHandler(vi: ViSession, eventType: ViEventType, handler: ViHndlr, userHandle: ViAddr) -> ViStatus;
+
+    fn viRead(vi: ViSession, buf: ViPBuf, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+    fn viReadAsync(vi: ViSession, buf: ViPBuf, cnt: ViUInt32, jobId: ViPJobId) -> ViStatus;
+    fn viReadToFile(vi: ViSession, filename: ViConstString, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+    fn viWrite(vi: ViSession, buf: ViConstBuf, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+    fn viWriteAsync(vi: ViSession, buf: ViConstBuf, cnt: ViUInt32, jobId: ViPJobId) -> ViStatus;
+    fn viWriteFromFile(vi: ViSession, filename: ViConstString, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+    fn viAssertTrigger(vi: ViSession, protocol: ViUInt16) -> ViStatus;
+    fn viReadSTB(vi: ViSession, status: ViPUInt16) -> ViStatus;
+    fn viClear(vi: ViSession) -> ViStatus;
+
+    fn viSetBuf(vi: ViSession, mask: ViUInt16, size: ViUInt32) -> ViStatus;
+    fn viFlush(vi: ViSession, mask: ViUInt16) -> ViStatus;
+    fn viBufWrite(vi: ViSession, buf: ViConstBuf, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+    fn viBufRead(vi: ViSession, buf: ViPBuf, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+
+    fn viIn8(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val8: ViPUInt8) -> ViStatus;
+    fn viOut8(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val8: ViUInt8) -> ViStatus;
+    fn viIn16(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val16: ViPUInt16) -> ViStatus;
+    fn viOut16(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val16: ViUInt16) -> ViStatus;
+    fn viIn32(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val32: ViPUInt32) -> ViStatus;
+    fn viOut32(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val32: ViUInt32) -> ViStatus;
+    fn viIn64(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val64: ViPUInt64) -> ViStatus;
+    fn viOut64(vi: ViSession, space: ViUInt16, offset: ViBusAddress, val64: ViUInt64) -> ViStatus;
+    fn viIn8Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val8: ViPUInt8) -> ViStatus;
+    fn viOut8Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val8: ViUInt8) -> ViStatus;
+    fn viIn16Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val16: ViPUInt16) -> ViStatus;
+    fn viOut16Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val16: ViUInt16) -> ViStatus;
+    fn viIn32Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val32: ViPUInt32) -> ViStatus;
+    fn viOut32Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val32: ViUInt32) -> ViStatus;
+    fn viIn64Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val64: ViPUInt64) -> ViStatus;
+    fn viOut64Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, val64: ViUInt64) -> ViStatus;
+
+    fn viMoveIn8(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf8: ViAUInt8) -> ViStatus;
+    fn viMoveOut8(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf8: ViAUInt8) -> ViStatus;
+    fn viMoveIn16(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf16: ViAUInt16) -> ViStatus;
+    fn viMoveOut16(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf16: ViAUInt16) -> ViStatus;
+    fn viMoveIn32(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf32: ViAUInt32) -> ViStatus;
+    fn viMoveOut32(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf32: ViAUInt32) -> ViStatus;
+    fn viMoveIn64(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf64: ViAUInt64) -> ViStatus;
+    fn viMoveOut64(vi: ViSession, space: ViUInt16, offset: ViBusAddress, length: ViBusSize, buf64: ViAUInt64) -> ViStatus;
+    fn viMoveIn8Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf8: ViAUInt8) -> ViStatus;
+    fn viMoveOut8Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf8: ViAUInt8) -> ViStatus;
+    fn viMoveIn16Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf16: ViAUInt16) -> ViStatus;
+    fn viMoveOut16Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf16: ViAUInt16) -> ViStatus;
+    fn viMoveIn32Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf32: ViAUInt32) -> ViStatus;
+    fn viMoveOut32Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf32: ViAUInt32) -> ViStatus;
+    fn viMoveIn64Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf64: ViAUInt64) -> ViStatus;
+    fn viMoveOut64Ex(vi: ViSession, space: ViUInt16, offset: ViBusAddress64, length: ViBusSize, buf64: ViAUInt64) -> ViStatus;
+    fn viMove(vi: ViSession, srcSpace: ViUInt16, srcOffset: ViBusAddress, srcWidth: ViUInt16, destSpace: ViUInt16, destOffset: ViBusAddress, destWidth: ViUInt16, srcLength: ViBusSize) -> ViStatus;
+    fn viMoveAsync(vi: ViSession, srcSpace: ViUInt16, srcOffset: ViBusAddress, srcWidth: ViUInt16, destSpace: ViUInt16, destOffset: ViBusAddress, destWidth: ViUInt16, srcLength: ViBusSize, jobId: ViPJobId) -> ViStatus;
+    fn viMoveEx(vi: ViSession, srcSpace: ViUInt16, srcOffset: ViBusAddress64, srcWidth: ViUInt16, destSpace: ViUInt16, destOffset: ViBusAddress64, destWidth: ViUInt16, srcLength: ViBusSize) -> ViStatus;
+    fn viMoveAsyncEx(vi: ViSession, srcSpace: ViUInt16, srcOffset: ViBusAddress64, srcWidth: ViUInt16, destSpace: ViUInt16, destOffset: ViBusAddress64, destWidth: ViUInt16, srcLength: ViBusSize, jobId: ViPJobId) -> ViStatus;
+    fn viMapAddress(vi: ViSession, mapSpace: ViUInt16, mapOffset: ViBusAddress, mapSize: ViBusSize, access: ViBoolean, suggested: ViAddr, address: ViPAddr) -> ViStatus;
+    fn viUnmapAddress(vi: ViSession) -> ViStatus;
+    fn viMapAddressEx(vi: ViSession, mapSpace: ViUInt16, mapOffset: ViBusAddress64, mapSize: ViBusSize, access: ViBoolean, suggested: ViAddr, address: ViPAddr) -> ViStatus;
+    fn viPeek8(vi: ViSession, address: ViAddr, val8: ViPUInt8);
+    fn viPoke8(vi: ViSession, address: ViAddr, val8: ViUInt8);
+    fn viPeek16(vi: ViSession, address: ViAddr, val16: ViPUInt16);
+    fn viPoke16(vi: ViSession, address: ViAddr, val16: ViUInt16);
+    fn viPeek32(vi: ViSession, address: ViAddr, val32: ViPUInt32);
+    fn viPoke32(vi: ViSession, address: ViAddr, val32: ViUInt32);
+    fn viPeek64(vi: ViSession, address: ViAddr, val64: ViPUInt64);
+    fn viPoke64(vi: ViSession, address: ViAddr, val64: ViUInt64);
+
+    fn viMemAlloc(vi: ViSession, size: ViBusSize, offset: ViPBusAddress) -> ViStatus;
+    fn viMemFree(vi: ViSession, offset: ViBusAddress) -> ViStatus;
+    fn viMemAllocEx(vi: ViSession, size: ViBusSize, offset: ViPBusAddress64) -> ViStatus;
+    fn viMemFreeEx(vi: ViSession, offset: ViBusAddress64) -> ViStatus;
+
+    fn viGpibControlREN(vi: ViSession, mode: ViUInt16) -> ViStatus;
+    fn viGpibControlATN(vi: ViSession, mode: ViUInt16) -> ViStatus;
+    fn viGpibSendIFC(vi: ViSession) -> ViStatus;
+    fn viGpibCommand(vi: ViSession, cmd: ViConstBuf, cnt: ViUInt32, retCnt: ViPUInt32) -> ViStatus;
+    fn viGpibPassControl(vi: ViSession, primAddr: ViUInt16, secAddr: ViUInt16) -> ViStatus;
+    fn viVxiCommandQuery(vi: ViSession, mode: ViUInt16, cmd: ViUInt32, response: ViPUInt32) -> ViStatus;
+    fn viAssertUtilSignal(vi: ViSession, line: ViUInt16) -> ViStatus;
+    fn viAssertIntrSignal(vi: ViSession, mode: ViInt16, statusID: ViUInt32) -> ViStatus;
+    fn viMapTrigger(vi: ViSession, trigSrc: ViInt16, trigDest: ViInt16, mode: ViUInt16) -> ViStatus;
+    fn viUnmapTrigger(vi: ViSession, trigSrc: ViInt16, trigDest: ViInt16) -> ViStatus;
+    fn viUsbControlOut(vi: ViSession, bmRequestType: ViInt16, bRequest: ViInt16, wValue: ViUInt16, wIndex: ViUInt16, wLength: ViUInt16, buf: ViConstBuf) -> ViStatus;
+    fn viUsbControlIn(vi: ViSession, bmRequestType: ViInt16, bRequest: ViInt16, wValue: ViUInt16, wIndex: ViUInt16, wLength: ViUInt16, buf: ViPBuf, retCnt: ViPUInt16) -> ViStatus;
+    fn viPxiReserveTriggers(vi: ViSession, cnt: ViInt16, trigBuses: ViAInt16, trigLines: ViAInt16, failureIndex: ViPInt16) -> ViStatus;
+}
@@ -0,0 +1,34 @@
+//! Raw bindings to the VISA C API.
+//!
+//! The bindings are pre-generated under `src/ffi/generated` for 32 and 64-bit
+//! targets, so building the crate needs neither `visa.h` nor libclang. Enable
+//! the `bindgen` feature to regenerate them from the installed header instead,
+//! e.g. for a target whose `va_list` or calling convention differs from the
+//! pre-generated ones.
+//!
+//! The 64-bit bindings declare `ViVAList` with the System V x86-64 layout, so
+//! the functions taking one (`viVPrintf`, `viVScanf`, ...) only match the C
+//! ABI on x86-64 Linux and Intel macOS. On 64-bit Windows, Apple silicon or
+//! aarch64 Linux every other function is usable, but those need bindings
+//! generated with `bindgen`. The 32-bit bindings, where `va_list` is a plain
+//! pointer, cover 32-bit Linux and Windows.
+//!
+//! Functions are declared `extern "system"`, which is stdcall on 32-bit
+//! Windows as `_VI_FUNC` and `_VI_FUNCH` are, and the C convention
+//! everywhere else. The variadic ones (`viPrintf`, `viScanf`, ...) are
+//! `_VI_FUNCC` and stay `extern "C"`.
+//!
+//! By default every function is linked against the VISA library at build time.
+//! With the `dynamic-loading` feature the same functions are resolved at runtime
+//! instead, see `load_library` and `library`.
+
+include!(concat!(env!("OUT_DIR"), "/types.rs"));
+
+#[cfg(not(feature = "dynamic-loading"))]
+include!(concat!(env!("OUT_DIR"), "/functions.rs"));
+
+#[cfg(feature = "dynamic-loading")]
+mod dynamic;
+
+#[cfg(feature = "dynamic-loading")]
+pub use dynamic::*;
@@ -0,0 +1,220 @@
+//! Resource discovery over `viFindRsrc` / `viFindNext`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ffi::*;
+use crate::resource::ResourceName;
+use crate::rm::ResourceManager;
+use crate::status::{error, Result, VisaError};
+
+/// Iterator over the resources matching a search expression, created by
+/// `ResourceManager::find`. Closes the find list when dropped.
+///
+/// A search that matches nothing (`VI_ERROR_RSRC_NFOUND`) yields no items;
+/// any other failure is yielded once as an error, after which the iterator
+/// ends.
+#[derive(Debug)]
+pub struct FindList<'rm> {
+    handle: ViFindList,
+    /// The name returned by `viFindRsrc`, or the error it reported.
+    first: Option<Result<ResourceName>>,
+    /// Names still to be fetched with `viFindNext`.
+    remaining: ViUInt32,
+    rm: &'rm ResourceManager,
+}
+
+impl<'rm> FindList<'rm> {
+    pub(crate) fn new(rm: &'rm ResourceManager, expr: &str) -> Self {
+        let mut list = FindList {
+            handle: VI_NULL,
+            first: None,
+            remaining: 0,
+            rm,
+        };
+
+        match rm.backend().find_rsrc(rm.as_raw(), expr) {
+            Ok((handle, count, first)) => {
+                list.handle = handle;
+                list.first = Some(Ok(parse_name(first)));
+                list.remaining = count.saturating_sub(1);
+            }
+            Err(e) if e.raw() == VI_ERROR_RSRC_NFOUND => {}
+            Err(e) => list.first = Some(Err(e)),
+        }
+        list
+    }
+}
+
+impl Iterator for FindList<'_> {
+    type Item = Result<ResourceName>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        if self.remaining == 0 {
+            return None;
+        }
+
+        match self.rm.backend().find_next(self.handle) {
+            Ok(name) => {
+                self.remaining -= 1;
+                Some(Ok(parse_name(name)))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize + self.first.is_some() as usize;
+        (0, Some(len))
+    }
+}
+
+impl Drop for FindList<'_> {
+    fn drop(&mut self) {
+        if self.handle != VI_NULL {
+            let _ = self.rm.backend().close(self.handle);
+        }
+    }
+}
+
+fn parse_name(name: String) -> ResourceName {
+    // VISA reports canonical names, so this only falls back for syntax the
+    // parser does not know.
+    name.parse().unwrap_or(ResourceName::Alias(name))
+}
+
+/// A value compared against in an attribute filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterValue {
+    /// Written in hex, e.g. `0x0957`, as IDs are usually quoted.
+    Unsigned(u32),
+    Signed(i64),
+    /// Written in double quotes, with embedded quotes doubled as in
+    /// IEEE 488.2 string data.
+    String(String),
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Unsigned(value) => write!(f, "0x{:04X}", value),
+            FilterValue::Signed(value) => write!(f, "{}", value),
+            FilterValue::String(value) => write!(f, "\"{}\"", value.replace('"', "\"\"")),
+        }
+    }
+}
+
+impl FromStr for FilterValue {
+    type Err = VisaError;
+
+    /// Parses a value as written by `Display`: `0x` hex, a signed decimal or
+    /// a double-quoted string whose doubled quotes are unescaped. Malformed
+    /// values are rejected with `VI_ERROR_INV_EXPR`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || error(VI_ERROR_INV_EXPR);
+        if let Some(quoted) = s.strip_prefix('"') {
+            let inner = quoted.strip_suffix('"').ok_or_else(invalid)?;
+            if inner.replace("\"\"", "").contains('"') {
+                return Err(invalid());
+            }
+            return Ok(FilterValue::String(inner.replace("\"\"", "\"")));
+        }
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u32::from_str_radix(hex, 16).map(FilterValue::Unsigned),
+            None => s.parse().map(FilterValue::Signed),
+        }
+        .map_err(|_| invalid())
+    }
+}
+
+impl From<u16> for FilterValue {
+    fn from(value: u16) -> Self {
+        FilterValue::Unsigned(value.into())
+    }
+}
+
+impl From<u32> for FilterValue {
+    fn from(value: u32) -> Self {
+        FilterValue::Unsigned(value)
+    }
+}
+
+impl From<i32> for FilterValue {
+    fn from(value: i32) -> Self {
+        FilterValue::Signed(value.into())
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        FilterValue::String(value.to_string())
+    }
+}
+
+/// Builder for VISA search expressions: a resource pattern, optionally
+/// followed by attribute filters, e.g. `USB?*INSTR{VI_ATTR_MANF_ID==0x0957}`.
+/// Filters are combined with `&&`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchExpr {
+    pattern: String,
+    filters: Vec<String>,
+}
+
+impl SearchExpr {
+    /// A search for `pattern`, e.g. `"?*INSTR"` or `"TCPIP?*"`.
+    pub fn new(pattern: &str) -> Self {
+        SearchExpr {
+            pattern: pattern.to_string(),
+            filters: Vec::new(),
+        }
+    }
+
+    /// Every `INSTR` resource.
+    pub fn instruments() -> Self {
+        SearchExpr::new("?*INSTR")
+    }
+
+    /// Every resource.
+    pub fn all() -> Self {
+        SearchExpr::new("?*")
+    }
+
+    /// Keeps resources whose attribute `attr` (e.g. `"VI_ATTR_MANF_ID"`)
+    /// equals `value`.
+    pub fn attr_eq(self, attr: &str, value: impl Into<FilterValue>) -> Self {
+        self.filter(&format!("{}=={}", attr, value.into()))
+    }
+
+    /// Keeps resources whose attribute `attr` differs from `value`.
+    pub fn attr_ne(self, attr: &str, value: impl Into<FilterValue>) -> Self {
+        self.filter(&format!("{}!={}", attr, value.into()))
+    }
+
+    /// Adds a raw attribute expression, e.g. `"VI_ATTR_GPIB_PRIMARY_ADDR > 4"`.
+    pub fn filter(mut self, expr: &str) -> Self {
+        self.filters.push(expr.to_string());
+        self
+    }
+}
+
+impl fmt::Display for SearchExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)?;
+        if !self.filters.is_empty() {
+            write!(f, "{{{}}}", self.filters.join(" && "))?;
+        }
+        Ok(())
+    }
+}
+
+impl From<SearchExpr> for String {
+    fn from(expr: SearchExpr) -> Self {
+        expr.to_string()
+    }
+}
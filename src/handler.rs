@@ -0,0 +1,244 @@
+//! Event handlers: Rust closures installed with `viInstallHandler`.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::backend::VisaBackend;
+use crate::event::{EnabledEvent, Event, EventKind, Mechanism};
+use crate::ffi::*;
+use crate::rm::ResourceManager;
+use crate::session::Session;
+use crate::status::Result;
+
+type Closure = Box<dyn FnMut(&Event) + Send>;
+
+/// The callbacks of the installed handlers by user handle. VISA only holds
+/// the handle, so a call racing with `EventHandler::drop` finds nothing
+/// instead of freed memory.
+static CALLBACKS: Mutex<BTreeMap<usize, Arc<Callback>>> = Mutex::new(BTreeMap::new());
+
+static NEXT_USER_HANDLE: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    /// The user handles whose closures this thread is calling.
+    static RUNNING: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+fn running_here(user_handle: usize) -> bool {
+    RUNNING.with(|running| running.borrow().contains(&user_handle))
+}
+
+/// The closure behind a user handle. The mutex serializes calls, as VISA
+/// may invoke handlers from several threads, and holds `None` once the
+/// handler is dropped.
+pub(crate) struct Callback {
+    backend: Arc<dyn VisaBackend>,
+    closure: Mutex<Option<Closure>>,
+    /// Set when the closure dropped its own handler, so the call running it
+    /// frees it on return.
+    unregistered: AtomicBool,
+}
+
+impl Callback {
+    pub(crate) fn new(backend: Arc<dyn VisaBackend>, closure: Closure) -> Self {
+        Callback {
+            backend,
+            closure: Mutex::new(Some(closure)),
+            unregistered: AtomicBool::new(false),
+        }
+    }
+
+    /// Makes the callback reachable from `trampoline`, returning its user
+    /// handle.
+    pub(crate) fn register(self: &Arc<Self>) -> usize {
+        let user_handle = NEXT_USER_HANDLE.fetch_add(1, Ordering::Relaxed);
+        callbacks().insert(user_handle, self.clone());
+        user_handle
+    }
+
+    /// Unregisters `user_handle` and frees the closure, after waiting for a
+    /// call still running. Calls that already looked the callback up find no
+    /// closure any more. From within the closure itself, which cannot be
+    /// waited for, freeing it is left to the call running it.
+    pub(crate) fn unregister(user_handle: usize) {
+        let callback = callbacks().remove(&user_handle);
+        if let Some(callback) = callback {
+            callback.unregistered.store(true, Ordering::Release);
+            if !running_here(user_handle) {
+                let closure = callback.closure.lock().unwrap_or_else(PoisonError::into_inner).take();
+                drop(closure);
+            }
+        }
+    }
+}
+
+fn callbacks() -> MutexGuard<'static, BTreeMap<usize, Arc<Callback>>> {
+    CALLBACKS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An installed handler, disabled and uninstalled (`viUninstallHandler`) when
+/// dropped.
+///
+/// The handler borrows the session, which is used through it like through a
+/// lock guard, so it cannot outlive the session:
+///
+/// ```compile_fail
+/// # use ni_visa_bindings::{EventKind, ResourceManager};
+/// # let rm = ResourceManager::new().unwrap();
+/// let mut session = rm.open("GPIB0::5::INSTR").unwrap();
+/// let handler = session.on_event(EventKind::ServiceRequest, |_| {}).unwrap();
+/// drop(session);
+/// drop(handler);
+/// ```
+#[derive(Debug)]
+#[must_use = "the handler is uninstalled when dropped"]
+pub struct EventHandler<'s, 'rm> {
+    session: &'s mut Session<'rm>,
+    installed: InstalledHandler<'rm>,
+}
+
+impl<'s, 'rm> EventHandler<'s, 'rm> {
+    pub(crate) fn install(session: &'s mut Session<'rm>, kind: EventKind, closure: Closure) -> Result<Self> {
+        let installed = InstalledHandler::install(session.resource_manager(), session.as_raw(), kind, closure)?;
+        Ok(EventHandler { session, installed })
+    }
+
+    pub fn kind(&self) -> EventKind {
+        self.installed.kind
+    }
+}
+
+impl<'rm> Deref for EventHandler<'_, 'rm> {
+    type Target = Session<'rm>;
+
+    fn deref(&self) -> &Self::Target {
+        self.session
+    }
+}
+
+impl DerefMut for EventHandler<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.session
+    }
+}
+
+/// The handler of an `EventHandler`, without the borrow. `AsyncSession`,
+/// which owns its session, holds one and drops it before the session.
+pub(crate) struct InstalledHandler<'rm> {
+    rm: &'rm ResourceManager,
+    handle: ViSession,
+    kind: EventKind,
+    user_handle: usize,
+    enabled: Option<EnabledEvent<'rm>>,
+}
+
+impl<'rm> InstalledHandler<'rm> {
+    pub(crate) fn install(
+        rm: &'rm ResourceManager,
+        handle: ViSession,
+        kind: EventKind,
+        closure: Closure,
+    ) -> Result<Self> {
+        let user_handle = Arc::new(Callback::new(rm.backend().clone(), closure)).register();
+        let mut handler = InstalledHandler {
+            rm,
+            handle,
+            kind,
+            user_handle,
+            enabled: None,
+        };
+        // On failure, dropping `handler` uninstalls what was installed.
+        unsafe { rm.backend().install_handler(handle, kind.raw(), Some(trampoline), user_handle as ViAddr) }?;
+        handler.enabled = Some(EnabledEvent::enable(rm, handle, kind, Mechanism::Handler)?);
+        Ok(handler)
+    }
+}
+
+impl std::fmt::Debug for InstalledHandler<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstalledHandler")
+            .field("handle", &self.handle)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for InstalledHandler<'_> {
+    fn drop(&mut self) {
+        // Stop new calls, then wait for one still running before freeing the
+        // closure.
+        drop(self.enabled.take());
+        unsafe {
+            let user_handle = self.user_handle as ViAddr;
+            let _ = self.rm.backend().uninstall_handler(self.handle, self.kind.raw(), Some(trampoline), user_handle);
+        }
+        Callback::unregister(self.user_handle);
+    }
+}
+
+/// The `ViHndlr` passed to VISA, `extern "system"` as `_VI_FUNCH` is stdcall
+/// on 32-bit Windows. Panics are caught here, since unwinding into VISA is
+/// undefined behavior; the event is then simply dropped, as are events
+/// arriving while the handler is dropped and those the closure raises for
+/// its own handler.
+pub(crate) unsafe extern "system" fn trampoline(
+    _vi: ViSession,
+    event_type: ViEventType,
+    context: ViEvent,
+    user_handle: ViAddr,
+) -> ViStatus {
+    let user_handle = user_handle as usize;
+    if running_here(user_handle) {
+        return VI_SUCCESS as ViStatus;
+    }
+    // The clone keeps the callback alive for this call.
+    let Some(callback) = callbacks().get(&user_handle).cloned() else {
+        return VI_SUCCESS as ViStatus;
+    };
+    let event = Event::from_context(callback.backend.clone(), context, event_type);
+    RUNNING.with(|running| running.borrow_mut().push(user_handle));
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut closure = callback.closure.lock().unwrap_or_else(PoisonError::into_inner);
+        if callback.unregistered.load(Ordering::Acquire) {
+            drop(closure.take());
+        } else if let Some(f) = closure.as_mut() {
+            f(&event);
+            if callback.unregistered.load(Ordering::Acquire) {
+                drop(closure.take());
+            }
+        }
+    }));
+    RUNNING.with(|running| running.borrow_mut().pop());
+    VI_SUCCESS as ViStatus
+}
+
+/// A channel events can be forwarded to with `Session::forward_events`.
+///
+/// Sending never blocks VISA's thread: events are dropped if the receiver is
+/// gone or a bounded channel is full.
+pub trait EventSender: Send + 'static {
+    fn send_event(&self, kind: EventKind);
+}
+
+impl EventSender for mpsc::Sender<EventKind> {
+    fn send_event(&self, kind: EventKind) {
+        let _ = self.send(kind);
+    }
+}
+
+impl EventSender for mpsc::SyncSender<EventKind> {
+    fn send_event(&self, kind: EventKind) {
+        let _ = self.try_send(kind);
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl EventSender for crossbeam_channel::Sender<EventKind> {
+    fn send_event(&self, kind: EventKind) {
+        let _ = self.try_send(kind);
+    }
+}
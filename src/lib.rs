@@ -1,7 +1,33 @@
 #[allow(non_camel_case_types, non_upper_case_globals, non_snake_case, dead_code, unused_imports)]
-pub mod ffi {
-  include!(concat!(env!("OUT_DIR"), "/visa_bindings.rs"));
-}
+pub mod ffi;
+
+pub mod ascii;
+mod async_session;
+pub mod attr;
+pub mod backend;
+pub mod block;
+mod event;
+mod find;
+mod handler;
+mod registers;
+mod resource;
+mod rm;
+pub mod scpi;
+mod session;
+mod status;
+
+pub use async_session::{AsyncSession, ReadFuture, WriteFuture};
+pub use event::{Event, EventGuard, EventKind, Mechanism};
+pub use find::{FilterValue, FindList, SearchExpr};
+pub use handler::{EventHandler, EventSender};
+pub use registers::{StandardEvent, StatusByte};
+pub use resource::{PxiAddress, ResourceClass, ResourceName};
+pub use rm::ResourceManager;
+pub use session::{
+  AccessMode, Encoding, ErrorCheck, ErrorQueueOptions, ErrorReport, LockGuard, OpcStrategy, OpenOptions, Session,
+  SharedLockGuard, StatusWait, TextOptions,
+};
+pub use status::{Completion, Result, Status, VisaError};
 
 #[cfg(test)]
-mod tests;
\ No newline at end of file
+mod tests;
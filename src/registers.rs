@@ -0,0 +1,52 @@
+//! The IEEE 488.2 status byte and standard event status register.
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// The status byte, as returned by `viReadSTB` or `*STB?` and enabled with
+    /// `*SRE`. Bits 0 and 1 are device-specific and kept as read.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct StatusByte: u8 {
+        /// The SCPI error/event queue is not empty (EAV).
+        const ERROR_QUEUE = 1 << 2;
+        /// Summary of the SCPI questionable status register.
+        const QUESTIONABLE = 1 << 3;
+        /// Message available: a response is waiting in the output queue.
+        const MAV = 1 << 4;
+        /// Event summary bit: an enabled standard event occurred.
+        const ESB = 1 << 5;
+        /// Request service, reported by a serial poll (`viReadSTB`).
+        const RQS = 1 << 6;
+        /// Master summary status, the same bit as reported by `*STB?`.
+        const MSS = 1 << 6;
+        /// Summary of the SCPI operation status register.
+        const OPERATION = 1 << 7;
+    }
+}
+
+bitflags! {
+    /// The standard event status register, as returned by `*ESR?` and enabled
+    /// with `*ESE`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct StandardEvent: u8 {
+        /// Operation complete, set by `*OPC`.
+        const OPC = 1 << 0;
+        /// Request control.
+        const RQC = 1 << 1;
+        /// Query error.
+        const QYE = 1 << 2;
+        /// Device-dependent error.
+        const DDE = 1 << 3;
+        /// Execution error.
+        const EXE = 1 << 4;
+        /// Command error.
+        const CME = 1 << 5;
+        /// User request.
+        const URQ = 1 << 6;
+        /// Power on.
+        const PON = 1 << 7;
+
+        /// Every error bit.
+        const ERRORS = Self::QYE.bits() | Self::DDE.bits() | Self::EXE.bits() | Self::CME.bits();
+    }
+}
@@ -0,0 +1,556 @@
+//! VISA resource names, parsed and formatted without a resource manager.
+//!
+//! Parsing follows the grammar in the VISA specification and canonicalizes
+//! the name the way `viParseRsrcEx` expands it: keywords are upper-cased,
+//! omitted board numbers become `0`, an omitted `::INSTR` is added, the
+//! default LAN device name `inst0` is filled in and USB IDs are written as
+//! four hex digits.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ffi::*;
+use crate::status::{error, VisaError};
+
+/// A PXI device address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PxiAddress {
+    /// `[bus-]device[.function]`
+    Bus {
+        bus: Option<u16>,
+        device: u16,
+        function: Option<u16>,
+    },
+    /// `CHASSISn::SLOTn[::FUNCn]`
+    Chassis {
+        chassis: u16,
+        slot: u16,
+        function: Option<u16>,
+    },
+}
+
+/// A VISA resource class, the last part of a resource name.
+///
+/// Parsed case-insensitively and displayed in the canonical upper case, e.g.
+/// `"intfc"` parses to `ResourceClass::Intfc`, displayed as `INTFC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceClass {
+    Instr,
+    Intfc,
+    Backplane,
+    Memacc,
+    Servant,
+    Raw,
+    Socket,
+}
+
+impl ResourceClass {
+    /// Every class, in the order of the VISA specification.
+    pub const ALL: [ResourceClass; 7] = [
+        ResourceClass::Instr,
+        ResourceClass::Intfc,
+        ResourceClass::Backplane,
+        ResourceClass::Memacc,
+        ResourceClass::Servant,
+        ResourceClass::Raw,
+        ResourceClass::Socket,
+    ];
+
+    /// The canonical spelling, e.g. `"INSTR"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResourceClass::Instr => "INSTR",
+            ResourceClass::Intfc => "INTFC",
+            ResourceClass::Backplane => "BACKPLANE",
+            ResourceClass::Memacc => "MEMACC",
+            ResourceClass::Servant => "SERVANT",
+            ResourceClass::Raw => "RAW",
+            ResourceClass::Socket => "SOCKET",
+        }
+    }
+}
+
+impl fmt::Display for ResourceClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ResourceClass {
+    type Err = VisaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ResourceClass::ALL
+            .into_iter()
+            .find(|class| class.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| error(VI_ERROR_INV_RSRC_NAME))
+    }
+}
+
+/// A VISA resource name such as `USB0::0x0957::0x5407::MY59002371::0::INSTR`.
+///
+/// `FromStr` and `Display` round-trip through the canonical form, e.g.
+/// `"tcpip::10.0.0.2"` parses to `TCPIP0::10.0.0.2::inst0::INSTR`. Malformed
+/// names are rejected with `VI_ERROR_INV_RSRC_NAME`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ResourceName {
+    /// `GPIB[board]::primary[::secondary][::INSTR]`
+    Gpib {
+        board: u16,
+        primary: u8,
+        secondary: Option<u8>,
+    },
+    /// `GPIB-VXI[board]::logical address[::INSTR]`
+    GpibVxi { board: u16, logical_address: u16 },
+    /// `VXI[board]::logical address[::INSTR]`
+    Vxi { board: u16, logical_address: u16 },
+    /// `ASRL[board][::INSTR]`
+    Asrl { board: u16 },
+    /// `TCPIP[board]::host[::device][::INSTR]`, a VXI-11 (`inst0`) or HiSLIP
+    /// (`hislip0`) device. IPv6 hosts are kept in brackets.
+    Tcpip {
+        board: u16,
+        host: String,
+        device: String,
+    },
+    /// `TCPIP[board]::host::port::SOCKET`
+    TcpipSocket { board: u16, host: String, port: u16 },
+    /// `USB[board]::manufacturer ID::model code::serial number[::interface][::INSTR]`
+    Usb {
+        board: u16,
+        manufacturer_id: u16,
+        model_code: u16,
+        serial_number: String,
+        interface: Option<u16>,
+    },
+    /// `USB[board]::manufacturer ID::model code::serial number[::interface]::RAW`
+    UsbRaw {
+        board: u16,
+        manufacturer_id: u16,
+        model_code: u16,
+        serial_number: String,
+        interface: Option<u16>,
+    },
+    /// `PXI[interface]::address[::INSTR]`
+    Pxi { interface: u16, address: PxiAddress },
+    /// A resource of a class this type does not model (e.g. `GPIB0::INTFC`
+    /// or `VXI0::1::BACKPLANE`). `name` has its interface keyword and class
+    /// upper-cased and its address kept as written.
+    Other {
+        interface_type: u16,
+        board: u16,
+        class: ResourceClass,
+        name: String,
+    },
+    /// An alias configured in the VISA implementation, e.g. `"MyScope"`.
+    Alias(String),
+}
+
+impl ResourceName {
+    /// `GPIB0::primary::INSTR`
+    pub fn gpib(primary: u8) -> Self {
+        ResourceName::Gpib {
+            board: 0,
+            primary,
+            secondary: None,
+        }
+    }
+
+    /// `ASRLboard::INSTR`
+    pub fn asrl(board: u16) -> Self {
+        ResourceName::Asrl { board }
+    }
+
+    /// `TCPIP0::host::inst0::INSTR`, a VXI-11 instrument.
+    pub fn vxi11(host: &str) -> Self {
+        ResourceName::Tcpip {
+            board: 0,
+            host: host.to_string(),
+            device: "inst0".to_string(),
+        }
+    }
+
+    /// `TCPIP0::host::hislip0::INSTR`
+    pub fn hislip(host: &str) -> Self {
+        ResourceName::Tcpip {
+            board: 0,
+            host: host.to_string(),
+            device: "hislip0".to_string(),
+        }
+    }
+
+    /// `TCPIP0::host::port::SOCKET`
+    pub fn socket(host: &str, port: u16) -> Self {
+        ResourceName::TcpipSocket {
+            board: 0,
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    /// `USB0::manufacturer ID::model code::serial number::INSTR`
+    pub fn usb(manufacturer_id: u16, model_code: u16, serial_number: &str) -> Self {
+        ResourceName::Usb {
+            board: 0,
+            manufacturer_id,
+            model_code,
+            serial_number: serial_number.to_string(),
+            interface: None,
+        }
+    }
+
+    /// The same resource on another board (interface number). Aliases are
+    /// returned unchanged.
+    pub fn with_board(mut self, new_board: u16) -> Self {
+        match &mut self {
+            ResourceName::Gpib { board, .. }
+            | ResourceName::GpibVxi { board, .. }
+            | ResourceName::Vxi { board, .. }
+            | ResourceName::Asrl { board }
+            | ResourceName::Tcpip { board, .. }
+            | ResourceName::TcpipSocket { board, .. }
+            | ResourceName::Usb { board, .. }
+            | ResourceName::UsbRaw { board, .. }
+            | ResourceName::Other { board, .. }
+            | ResourceName::Pxi { interface: board, .. } => *board = new_board,
+            ResourceName::Alias(_) => {}
+        }
+        self
+    }
+
+    /// The `VI_INTF_*` interface type, or `None` for aliases.
+    pub fn interface_type(&self) -> Option<u16> {
+        let intf = match self {
+            ResourceName::Gpib { .. } => VI_INTF_GPIB,
+            ResourceName::GpibVxi { .. } => VI_INTF_GPIB_VXI,
+            ResourceName::Vxi { .. } => VI_INTF_VXI,
+            ResourceName::Asrl { .. } => VI_INTF_ASRL,
+            ResourceName::Tcpip { .. } | ResourceName::TcpipSocket { .. } => VI_INTF_TCPIP,
+            ResourceName::Usb { .. } | ResourceName::UsbRaw { .. } => VI_INTF_USB,
+            ResourceName::Pxi { .. } => VI_INTF_PXI,
+            ResourceName::Other { interface_type, .. } => return Some(*interface_type),
+            ResourceName::Alias(_) => return None,
+        };
+        Some(intf as u16)
+    }
+
+    /// The board (interface number), or `None` for aliases.
+    pub fn board(&self) -> Option<u16> {
+        match self {
+            ResourceName::Gpib { board, .. }
+            | ResourceName::GpibVxi { board, .. }
+            | ResourceName::Vxi { board, .. }
+            | ResourceName::Asrl { board }
+            | ResourceName::Tcpip { board, .. }
+            | ResourceName::TcpipSocket { board, .. }
+            | ResourceName::Usb { board, .. }
+            | ResourceName::UsbRaw { board, .. }
+            | ResourceName::Other { board, .. }
+            | ResourceName::Pxi { interface: board, .. } => Some(*board),
+            ResourceName::Alias(_) => None,
+        }
+    }
+
+    /// The resource class, or `None` for aliases.
+    pub fn resource_class(&self) -> Option<ResourceClass> {
+        match self {
+            ResourceName::TcpipSocket { .. } => Some(ResourceClass::Socket),
+            ResourceName::UsbRaw { .. } => Some(ResourceClass::Raw),
+            ResourceName::Other { class, .. } => Some(*class),
+            ResourceName::Alias(_) => None,
+            _ => Some(ResourceClass::Instr),
+        }
+    }
+
+    /// Whether this is a HiSLIP `TCPIP` instrument.
+    pub fn is_hislip(&self) -> bool {
+        matches!(self, ResourceName::Tcpip { device, .. } if device.starts_with("hislip"))
+    }
+}
+
+impl fmt::Display for ResourceName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceName::Gpib {
+                board,
+                primary,
+                secondary,
+            } => {
+                write!(f, "GPIB{}::{}", board, primary)?;
+                if let Some(secondary) = secondary {
+                    write!(f, "::{}", secondary)?;
+                }
+                f.write_str("::INSTR")
+            }
+            ResourceName::GpibVxi { board, logical_address } => {
+                write!(f, "GPIB-VXI{}::{}::INSTR", board, logical_address)
+            }
+            ResourceName::Vxi { board, logical_address } => write!(f, "VXI{}::{}::INSTR", board, logical_address),
+            ResourceName::Asrl { board } => write!(f, "ASRL{}::INSTR", board),
+            ResourceName::Tcpip { board, host, device } => write!(f, "TCPIP{}::{}::{}::INSTR", board, host, device),
+            ResourceName::TcpipSocket { board, host, port } => write!(f, "TCPIP{}::{}::{}::SOCKET", board, host, port),
+            ResourceName::Usb {
+                board,
+                manufacturer_id,
+                model_code,
+                serial_number,
+                interface,
+            }
+            | ResourceName::UsbRaw {
+                board,
+                manufacturer_id,
+                model_code,
+                serial_number,
+                interface,
+            } => {
+                write!(
+                    f,
+                    "USB{}::0x{:04X}::0x{:04X}::{}",
+                    board, manufacturer_id, model_code, serial_number
+                )?;
+                if let Some(interface) = interface {
+                    write!(f, "::{}", interface)?;
+                }
+                f.write_str(if matches!(self, ResourceName::UsbRaw { .. }) { "::RAW" } else { "::INSTR" })
+            }
+            ResourceName::Pxi { interface, address } => {
+                write!(f, "PXI{}::", interface)?;
+                match address {
+                    PxiAddress::Bus { bus, device, function } => {
+                        if let Some(bus) = bus {
+                            write!(f, "{}-", bus)?;
+                        }
+                        write!(f, "{}", device)?;
+                        if let Some(function) = function {
+                            write!(f, ".{}", function)?;
+                        }
+                    }
+                    PxiAddress::Chassis { chassis, slot, function } => {
+                        write!(f, "CHASSIS{}::SLOT{}", chassis, slot)?;
+                        if let Some(function) = function {
+                            write!(f, "::FUNC{}", function)?;
+                        }
+                    }
+                }
+                f.write_str("::INSTR")
+            }
+            ResourceName::Other { name, .. } | ResourceName::Alias(name) => f.write_str(name),
+        }
+    }
+}
+
+impl FromStr for ResourceName {
+    type Err = VisaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).ok_or_else(|| error(VI_ERROR_INV_RSRC_NAME))
+    }
+}
+
+/// Interface keywords, longest first so `GPIB-VXI` is not taken for `GPIB`.
+const INTERFACES: [(&str, u32); 7] = [
+    ("GPIB-VXI", VI_INTF_GPIB_VXI),
+    ("GPIB", VI_INTF_GPIB),
+    ("VXI", VI_INTF_VXI),
+    ("ASRL", VI_INTF_ASRL),
+    ("TCPIP", VI_INTF_TCPIP),
+    ("USB", VI_INTF_USB),
+    ("PXI", VI_INTF_PXI),
+];
+
+fn parse(s: &str) -> Option<ResourceName> {
+    let tokens = split(s)?;
+    let first = tokens[0].to_ascii_uppercase();
+
+    let interface = INTERFACES.iter().find_map(|(keyword, intf)| {
+        let board = first.strip_prefix(keyword)?;
+        if board.is_empty() {
+            Some((*keyword, *intf, 0))
+        } else if board.bytes().all(|b| b.is_ascii_digit()) {
+            Some((*keyword, *intf, board.parse::<u16>().ok()?))
+        } else {
+            None
+        }
+    });
+    let Some((keyword, intf, board)) = interface else {
+        // Anything not starting with an interface keyword is an alias.
+        return if tokens.len() == 1 && !s.trim().is_empty() {
+            Some(ResourceName::Alias(s.to_string()))
+        } else {
+            None
+        };
+    };
+
+    let mut rest = &tokens[1..];
+    let class = match rest.last().and_then(|class| class.parse::<ResourceClass>().ok()) {
+        Some(class) => {
+            rest = &rest[..rest.len() - 1];
+            class
+        }
+        None => ResourceClass::Instr,
+    };
+    if !matches!(class, ResourceClass::Instr | ResourceClass::Raw | ResourceClass::Socket) {
+        return Some(other(keyword, intf, board, class, rest));
+    }
+
+    match (intf, class, rest) {
+        (VI_INTF_GPIB, ResourceClass::Instr, [primary]) => gpib(board, primary, None),
+        (VI_INTF_GPIB, ResourceClass::Instr, [primary, secondary]) => gpib(board, primary, Some(secondary)),
+        (VI_INTF_GPIB_VXI, ResourceClass::Instr, [la]) => Some(ResourceName::GpibVxi {
+            board,
+            logical_address: number(la)?,
+        }),
+        (VI_INTF_VXI, ResourceClass::Instr, [la]) => Some(ResourceName::Vxi {
+            board,
+            logical_address: number(la)?,
+        }),
+        (VI_INTF_ASRL, ResourceClass::Instr, []) => Some(ResourceName::Asrl { board }),
+        (VI_INTF_TCPIP, ResourceClass::Instr, [host]) => tcpip(board, host, "inst0"),
+        (VI_INTF_TCPIP, ResourceClass::Instr, [host, device]) => tcpip(board, host, device),
+        (VI_INTF_TCPIP, ResourceClass::Socket, [host, port]) => Some(ResourceName::TcpipSocket {
+            board,
+            host: host.to_string(),
+            port: number(port)?,
+        }),
+        (
+            VI_INTF_USB,
+            ResourceClass::Instr | ResourceClass::Raw,
+            [manufacturer_id, model_code, serial_number, interface @ ..],
+        ) => {
+            let manufacturer_id = number(manufacturer_id)?;
+            let model_code = number(model_code)?;
+            let serial_number = serial_number.to_string();
+            let interface = match interface {
+                [] => None,
+                [interface] => Some(number(interface)?),
+                _ => return None,
+            };
+            if class == ResourceClass::Raw {
+                Some(ResourceName::UsbRaw {
+                    board,
+                    manufacturer_id,
+                    model_code,
+                    serial_number,
+                    interface,
+                })
+            } else {
+                Some(ResourceName::Usb {
+                    board,
+                    manufacturer_id,
+                    model_code,
+                    serial_number,
+                    interface,
+                })
+            }
+        }
+        (VI_INTF_PXI, ResourceClass::Instr, address) => Some(ResourceName::Pxi {
+            interface: board,
+            address: pxi_address(address)?,
+        }),
+        _ => None,
+    }
+}
+
+/// A name of a class `ResourceName` does not model, with the interface
+/// keyword and class in canonical form.
+fn other(keyword: &str, intf: u32, board: u16, class: ResourceClass, address: &[&str]) -> ResourceName {
+    let mut name = format!("{}{}", keyword, board);
+    for token in address {
+        name.push_str("::");
+        name.push_str(token);
+    }
+    name.push_str("::");
+    name.push_str(class.as_str());
+    ResourceName::Other {
+        interface_type: intf as u16,
+        board,
+        class,
+        name,
+    }
+}
+
+/// Splits on `::`, keeping a bracketed IPv6 host such as `[fe80::1]` whole.
+fn split(s: &str) -> Option<Vec<&str>> {
+    let mut tokens = Vec::new();
+    let mut rest = s;
+    loop {
+        let end = if rest.starts_with('[') {
+            rest.find(']')? + 1
+        } else {
+            rest.find("::").unwrap_or(rest.len())
+        };
+        let (token, tail) = rest.split_at(end);
+        if token.is_empty() {
+            return None;
+        }
+        tokens.push(token);
+        if tail.is_empty() {
+            return Some(tokens);
+        }
+        rest = tail.strip_prefix("::")?;
+    }
+}
+
+/// A decimal or `0x` hex number.
+fn number<T: TryFrom<u64>>(s: &str) -> Option<T> {
+    let value = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None if s.bytes().all(|b| b.is_ascii_digit()) => s.parse().ok()?,
+        None => return None,
+    };
+    T::try_from(value).ok()
+}
+
+fn gpib(board: u16, primary: &str, secondary: Option<&str>) -> Option<ResourceName> {
+    let primary: u8 = number(primary).filter(|address| *address <= 30)?;
+    let secondary = match secondary {
+        Some(secondary) => Some(number::<u8>(secondary).filter(|address| *address <= 30)?),
+        None => None,
+    };
+    Some(ResourceName::Gpib {
+        board,
+        primary,
+        secondary,
+    })
+}
+
+fn tcpip(board: u16, host: &str, device: &str) -> Option<ResourceName> {
+    if host.contains(':') && !host.starts_with('[') {
+        return None;
+    }
+    Some(ResourceName::Tcpip {
+        board,
+        host: host.to_string(),
+        device: device.to_ascii_lowercase(),
+    })
+}
+
+fn pxi_address(tokens: &[&str]) -> Option<PxiAddress> {
+    let upper: Vec<String> = tokens.iter().map(|token| token.to_ascii_uppercase()).collect();
+    match upper.as_slice() {
+        [chassis, slot, function @ ..] if chassis.starts_with("CHASSIS") => {
+            let function = match function {
+                [] => None,
+                [function] => Some(number(function.strip_prefix("FUNC")?)?),
+                _ => return None,
+            };
+            Some(PxiAddress::Chassis {
+                chassis: number(chassis.strip_prefix("CHASSIS")?)?,
+                slot: number(slot.strip_prefix("SLOT")?)?,
+                function,
+            })
+        }
+        [address] => {
+            let (bus, rest) = match address.split_once('-') {
+                Some((bus, rest)) => (Some(number(bus)?), rest),
+                None => (None, address.as_str()),
+            };
+            let (device, function) = match rest.split_once('.') {
+                Some((device, function)) => (number(device)?, Some(number(function)?)),
+                None => (number(rest)?, None),
+            };
+            Some(PxiAddress::Bus { bus, device, function })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,93 @@
+//! The VISA resource manager.
+
+use std::mem;
+use std::sync::Arc;
+
+use crate::attr::{self, Attribute, SetAttrValue, Writable};
+use crate::backend::{FfiBackend, VisaBackend};
+use crate::ffi::*;
+use crate::find::FindList;
+use crate::session::{OpenOptions, Session};
+use crate::status::Result;
+
+/// The default VISA resource manager, closed when dropped.
+///
+/// Sessions opened through it borrow it, so it cannot be closed while any of
+/// them is still open.
+#[derive(Debug)]
+pub struct ResourceManager {
+    backend: Arc<dyn VisaBackend>,
+    handle: ViSession,
+}
+
+impl ResourceManager {
+    /// Opens the default resource manager (`viOpenDefaultRM`).
+    pub fn new() -> Result<Self> {
+        ResourceManager::with_backend(Arc::new(FfiBackend))
+    }
+
+    /// Opens the default resource manager of `backend`, e.g. a
+    /// `backend::mock::MockBackend`. Sessions opened through it use the same
+    /// backend.
+    pub fn with_backend(backend: Arc<dyn VisaBackend>) -> Result<Self> {
+        let handle = backend.open_default_rm()?;
+        Ok(ResourceManager { backend, handle })
+    }
+
+    /// Opens a session to `resource` with the default options.
+    pub fn open(&self, resource: &str) -> Result<Session<'_>> {
+        self.open_with(resource, &OpenOptions::new())
+    }
+
+    /// Opens a session to `resource` (`viOpen`).
+    pub fn open_with(&self, resource: &str, options: &OpenOptions) -> Result<Session<'_>> {
+        let handle = self
+            .backend
+            .open(self.handle, resource, options.access_mode_bits(), options.open_timeout_ms())?;
+        Ok(Session::new(self, handle))
+    }
+
+    /// Lists the resources matching `expr`, a search expression such as
+    /// `"?*INSTR"` or a `SearchExpr`.
+    pub fn find(&self, expr: impl Into<String>) -> FindList<'_> {
+        FindList::new(self, &expr.into())
+    }
+
+    /// Reads attribute `A` of the resource manager session.
+    pub fn get<A: Attribute>(&self) -> Result<A::Value> {
+        attr::get::<A>(&*self.backend, self.handle)
+    }
+
+    /// Sets attribute `A` of the resource manager session.
+    pub fn set<A: Writable>(&self, value: A::Value) -> Result
+    where
+        A::Value: SetAttrValue,
+    {
+        attr::set::<A>(&*self.backend, self.handle, value)
+    }
+
+    /// Closes the resource manager, reporting the status `Drop` would ignore.
+    pub fn close(mut self) -> Result {
+        let handle = mem::replace(&mut self.handle, VI_NULL);
+        self.backend.close(handle)
+    }
+
+    /// The raw handle, for calls not covered by the safe API. It must not be
+    /// closed.
+    pub fn as_raw(&self) -> ViSession {
+        self.handle
+    }
+
+    /// The backend the resource manager and its sessions use.
+    pub fn backend(&self) -> &Arc<dyn VisaBackend> {
+        &self.backend
+    }
+}
+
+impl Drop for ResourceManager {
+    fn drop(&mut self) {
+        if self.handle != VI_NULL {
+            let _ = self.backend.close(self.handle);
+        }
+    }
+}
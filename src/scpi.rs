@@ -0,0 +1,183 @@
+//! SCPI error queue entries.
+//!
+//! `SYSTem:ERRor?` returns `<code>,"<message>"`, e.g.
+//! `-113,"Undefined header"`, and `0,"No error"` once the queue is empty.
+//!
+//! ```
+//! use ni_visa_bindings::scpi::{ErrorClass, ScpiError};
+//!
+//! let error: ScpiError = "-222,\"Data out of range;FREQ 1E12\"".parse().unwrap();
+//! assert_eq!(error.code(), -222);
+//! assert_eq!(error.class(), ErrorClass::Execution);
+//! assert_eq!(error.to_string(), "-222, \"Data out of range;FREQ 1E12\" (Execution error)");
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The class of an error code, from the ranges defined by SCPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// Code 0.
+    NoError,
+    /// -100 to -199: the command could not be parsed.
+    Command,
+    /// -200 to -299: the command could not be executed.
+    Execution,
+    /// -300 to -399: the device failed while executing.
+    DeviceSpecific,
+    /// -400 to -499: the output queue was misused.
+    Query,
+    /// -500 to -599.
+    PowerOn,
+    /// -600 to -699.
+    UserRequest,
+    /// -700 to -799.
+    RequestControl,
+    /// -800 to -899.
+    OperationComplete,
+    /// Positive codes, defined by the instrument.
+    DeviceDefined,
+    /// Other negative codes, reserved by SCPI.
+    Reserved,
+}
+
+impl ErrorClass {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => ErrorClass::NoError,
+            1.. => ErrorClass::DeviceDefined,
+            -199..=-100 => ErrorClass::Command,
+            -299..=-200 => ErrorClass::Execution,
+            -399..=-300 => ErrorClass::DeviceSpecific,
+            -499..=-400 => ErrorClass::Query,
+            -599..=-500 => ErrorClass::PowerOn,
+            -699..=-600 => ErrorClass::UserRequest,
+            -799..=-700 => ErrorClass::RequestControl,
+            -899..=-800 => ErrorClass::OperationComplete,
+            _ => ErrorClass::Reserved,
+        }
+    }
+
+    /// The name SCPI gives the class, e.g. `"Command error"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorClass::NoError => "No error",
+            ErrorClass::Command => "Command error",
+            ErrorClass::Execution => "Execution error",
+            ErrorClass::DeviceSpecific => "Device-specific error",
+            ErrorClass::Query => "Query error",
+            ErrorClass::PowerOn => "Power on",
+            ErrorClass::UserRequest => "User request",
+            ErrorClass::RequestControl => "Request control",
+            ErrorClass::OperationComplete => "Operation complete",
+            ErrorClass::DeviceDefined => "Device-defined error",
+            ErrorClass::Reserved => "Reserved error",
+        }
+    }
+}
+
+impl fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One entry of the error queue.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScpiError {
+    code: i32,
+    message: String,
+}
+
+impl ScpiError {
+    pub fn new(code: i32, message: &str) -> Self {
+        ScpiError {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    /// The message as reported, including any device-dependent information
+    /// after a `;`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn class(&self) -> ErrorClass {
+        ErrorClass::from_code(self.code)
+    }
+
+    /// Whether this is the `0,"No error"` entry of an empty queue.
+    pub fn is_no_error(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl fmt::Display for ScpiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, \"{}\" ({})", self.code, self.message, self.class())
+    }
+}
+
+impl Error for ScpiError {}
+
+/// A response that is not a `<code>,"<message>"` error queue entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScpiError {
+    response: String,
+}
+
+impl fmt::Display for ParseScpiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid error queue entry {:?}", self.response)
+    }
+}
+
+impl Error for ParseScpiError {}
+
+impl FromStr for ScpiError {
+    type Err = ParseScpiError;
+
+    /// Parses `<code>,"<message>"`. Doubled quotes in the message are
+    /// unescaped, and a missing or unquoted message is accepted.
+    fn from_str(response: &str) -> Result<Self, Self::Err> {
+        let error = || ParseScpiError {
+            response: response.to_string(),
+        };
+        let response_trimmed = response.trim();
+        let (code, message) = response_trimmed.split_once(',').unwrap_or((response_trimmed, ""));
+        let code = code.trim();
+        let code: i32 = code.strip_prefix('+').unwrap_or(code).parse().map_err(|_| error())?;
+
+        let message = message.trim();
+        let message = match message.strip_prefix('"') {
+            Some(quoted) => quoted.strip_suffix('"').ok_or_else(error)?.replace("\"\"", "\""),
+            None => message.to_string(),
+        };
+        Ok(ScpiError { code, message })
+    }
+}
+
+/// The errors drained from the queue after a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScpiErrors(pub Vec<ScpiError>);
+
+impl fmt::Display for ScpiErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ScpiErrors {}
@@ -0,0 +1,68 @@
+//! Binary block exchanges.
+
+use std::io::{self, Write};
+
+use super::Session;
+use crate::attr;
+use crate::block::{self, BlockValue, ByteOrder};
+
+impl Session<'_> {
+    /// The byte order of binary blocks, big-endian unless changed.
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    /// Sets the byte order of binary blocks. Instruments usually need a
+    /// matching command, e.g. `FORMat:BORDer SWAPped`, to change theirs.
+    pub fn set_byte_order(&mut self, order: ByteOrder) {
+        self.byte_order = order;
+    }
+
+    /// Writes `header_cmd` (e.g. `"DATA:DAC VOLATILE, "`), then `values` as a
+    /// definite-length block, then the write termination, in a single write.
+    pub fn write_binary_block<T: BlockValue>(&mut self, header_cmd: &str, values: &[T]) -> io::Result<()> {
+        let order = self.byte_order;
+        let bytes = self
+            .text
+            .encode_command_with(header_cmd, |bytes| block::extend_block(bytes, values, order))?;
+        self.write_all(&bytes)?;
+        self.after_command(false)
+    }
+
+    /// Reads a response holding one definite or indefinite-length block.
+    /// Anything before the `#` is skipped; anything after the block other
+    /// than a `\n` or `\r\n` terminator is an `io::ErrorKind::InvalidData`
+    /// error.
+    ///
+    /// `VI_ATTR_TERMCHAR_EN` is turned off while the block is read, so that
+    /// its data may contain the termination character, and restored after.
+    pub fn read_binary_block<T: BlockValue>(&mut self) -> io::Result<Vec<T>> {
+        let order = self.byte_order;
+        let term_char_en = self.suspend_term_char()?;
+        let values = block::read_block(self, order);
+        if term_char_en {
+            self.set::<attr::TermCharEn>(true)?;
+        }
+        values
+    }
+
+    /// Turns `VI_ATTR_TERMCHAR_EN` off, returning whether it was on.
+    fn suspend_term_char(&mut self) -> io::Result<bool> {
+        // Resources without the attribute have no termination character.
+        if !self.get::<attr::TermCharEn>().unwrap_or(false) {
+            return Ok(false);
+        }
+        self.set::<attr::TermCharEn>(false)?;
+        self.read_buf.continue_after_term_char();
+        Ok(true)
+    }
+
+    /// Writes `command` and reads the block in the response, e.g.
+    /// `query_binary_block::<i16>("CURVe?")`.
+    pub fn query_binary_block<T: BlockValue>(&mut self, command: &str) -> io::Result<Vec<T>> {
+        self.send(command)?;
+        let values = self.read_binary_block()?;
+        self.after_command(true)?;
+        Ok(values)
+    }
+}
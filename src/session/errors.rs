@@ -0,0 +1,150 @@
+//! Automatic SCPI error queue checks.
+
+use std::io;
+
+use super::Session;
+use crate::scpi::{ScpiError, ScpiErrors};
+
+/// When a session reads the error queue on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorCheck {
+    /// Never, the default. `Session::check_errors` still drains the queue on
+    /// request.
+    #[default]
+    Disabled,
+    /// After every `write_str`, `query` and block or ASCII values exchange.
+    PerCommand,
+    /// After queries only, so the errors of a run of writes are collected in
+    /// one round trip by the next query.
+    Batched,
+}
+
+/// What happens to errors found by automatic checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorReport {
+    /// The call fails with an `io::ErrorKind::Other` error wrapping
+    /// `ScpiErrors`, the default. A query's response is discarded.
+    #[default]
+    Fail,
+    /// The call succeeds and the errors are kept for
+    /// `Session::take_scpi_warnings`.
+    Collect,
+}
+
+/// Options for the error queue, applied with
+/// `Session::set_error_queue_options`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorQueueOptions {
+    check: ErrorCheck,
+    report: ErrorReport,
+    query: String,
+    max_entries: usize,
+}
+
+impl ErrorQueueOptions {
+    /// Checks disabled, failing on errors, reading `SYSTem:ERRor?` up to 100
+    /// times.
+    pub fn new() -> Self {
+        ErrorQueueOptions {
+            check: ErrorCheck::Disabled,
+            report: ErrorReport::Fail,
+            query: "SYSTem:ERRor?".to_string(),
+            max_entries: 100,
+        }
+    }
+
+    pub fn check(mut self, check: ErrorCheck) -> Self {
+        self.check = check;
+        self
+    }
+
+    pub fn report(mut self, report: ErrorReport) -> Self {
+        self.report = report;
+        self
+    }
+
+    /// The query returning the next entry, e.g. `"SYST:ERR:NEXT?"`.
+    pub fn query(mut self, query: &str) -> Self {
+        self.query = query.to_string();
+        self
+    }
+
+    /// How many entries are read before giving up on an instrument that never
+    /// reports `0,"No error"`.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+}
+
+impl Default for ErrorQueueOptions {
+    fn default() -> Self {
+        ErrorQueueOptions::new()
+    }
+}
+
+impl Session<'_> {
+    pub fn error_queue_options(&self) -> &ErrorQueueOptions {
+        &self.errors
+    }
+
+    pub fn set_error_queue_options(&mut self, options: ErrorQueueOptions) {
+        self.errors = options;
+    }
+
+    /// Reads the error queue until it reports `0,"No error"` and returns the
+    /// entries before that, whatever the `ErrorCheck` policy.
+    pub fn check_errors(&mut self) -> io::Result<Vec<ScpiError>> {
+        let mut errors = Vec::new();
+        for _ in 0..self.errors.max_entries {
+            let query = self.errors.query.clone();
+            self.send(&query)?;
+            let response = self.read_string()?;
+            let error: ScpiError = response
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            if error.is_no_error() {
+                return Ok(errors);
+            }
+            errors.push(error);
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "error queue still not empty after {} entries: {}",
+                self.errors.max_entries,
+                ScpiErrors(errors)
+            ),
+        ))
+    }
+
+    /// The errors collected by automatic checks with `ErrorReport::Collect`
+    /// since the last call.
+    pub fn take_scpi_warnings(&mut self) -> Vec<ScpiError> {
+        std::mem::take(&mut self.scpi_warnings)
+    }
+
+    /// Runs the automatic check, if the policy asks for one after this kind
+    /// of command.
+    pub(super) fn after_command(&mut self, query: bool) -> io::Result<()> {
+        let check = match self.errors.check {
+            ErrorCheck::Disabled => false,
+            ErrorCheck::PerCommand => true,
+            ErrorCheck::Batched => query,
+        };
+        if !check {
+            return Ok(());
+        }
+        let errors = self.check_errors()?;
+        if errors.is_empty() {
+            return Ok(());
+        }
+        match self.errors.report {
+            ErrorReport::Fail => Err(io::Error::other(ScpiErrors(errors))),
+            ErrorReport::Collect => {
+                self.scpi_warnings.extend(errors);
+                Ok(())
+            }
+        }
+    }
+}
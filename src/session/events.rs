@@ -0,0 +1,45 @@
+//! Event queue access.
+
+use std::time::Duration;
+
+use super::Session;
+use crate::event::{Event, EventGuard, EventKind, Mechanism};
+use crate::handler::{EventHandler, EventSender};
+use crate::status::Result;
+
+impl<'rm> Session<'rm> {
+    /// Enables queuing of `kind` events for `wait_event` until the guard is
+    /// dropped, through which the session is used meanwhile.
+    pub fn enable_event(&mut self, kind: EventKind) -> Result<EventGuard<'_, 'rm>> {
+        EventGuard::enable(self, kind, Mechanism::Queue)
+    }
+
+    /// Waits for a queued `kind` event, or any enabled event with
+    /// `EventKind::AllEnabled`. Fails with `VI_ERROR_TMO` after `timeout`.
+    pub fn wait_event(&self, kind: EventKind, timeout: Duration) -> Result<Event> {
+        Event::wait(self.backend(), self.handle, kind, timeout)
+    }
+
+    /// Drops the queued `kind` events (`viDiscardEvents`).
+    pub fn discard_events(&self, kind: EventKind) -> Result {
+        self.backend().discard_events(self.handle, kind.raw(), Mechanism::Queue.raw())
+    }
+
+    /// Calls `handler` for every `kind` event until the returned
+    /// `EventHandler` is dropped, through which the session is used
+    /// meanwhile. The closure runs on a thread of VISA's choosing; a panic in
+    /// it is caught and the event ignored.
+    pub fn on_event<F>(&mut self, kind: EventKind, handler: F) -> Result<EventHandler<'_, 'rm>>
+    where
+        F: FnMut(&Event) + Send + 'static,
+    {
+        EventHandler::install(self, kind, Box::new(handler))
+    }
+
+    /// Sends the kind of every `kind` event to `sender`, e.g. an
+    /// `mpsc::Sender<EventKind>`, until the returned `EventHandler` is
+    /// dropped.
+    pub fn forward_events(&mut self, kind: EventKind, sender: impl EventSender) -> Result<EventHandler<'_, 'rm>> {
+        self.on_event(kind, move |event| sender.send_event(event.kind()))
+    }
+}
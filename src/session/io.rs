@@ -0,0 +1,158 @@
+//! `std::io` traits over `viRead` / `viWrite`.
+
+use std::io::{self, BufRead, Read, Write};
+
+use super::Session;
+use crate::block::MessageRead;
+use crate::ffi::*;
+use crate::status::{Completion, Result};
+
+/// Bytes asked for by each `viRead` issued by `BufRead` and `read_until_end`.
+const CHUNK: usize = 4096;
+
+/// Data read ahead by `BufRead::fill_buf`.
+#[derive(Debug, Default)]
+pub(super) struct ReadBuffer {
+    data: Vec<u8>,
+    pos: usize,
+    /// Whether the last `viRead` stopped on the count, i.e. the message it
+    /// read from has not ended yet.
+    in_message: bool,
+    /// Whether the last `viRead` stopped on the termination character.
+    term_char: bool,
+}
+
+impl ReadBuffer {
+    fn available(&self) -> &[u8] {
+        &self.data[self.pos..]
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = self.pos.saturating_add(amount).min(self.data.len());
+    }
+
+    /// Treats data still buffered from a read that stopped on the termination
+    /// character as part of a longer message, once `VI_ATTR_TERMCHAR_EN` is
+    /// turned off.
+    pub(super) fn continue_after_term_char(&mut self) {
+        if self.term_char && !self.available().is_empty() {
+            self.in_message = true;
+        }
+    }
+}
+
+impl Session<'_> {
+    /// One `viRead` into `buf`, bypassing the read buffer.
+    fn vi_read(&mut self, buf: &mut [u8]) -> Result<(usize, Completion)> {
+        let (count, completion) = self
+            .backend()
+            .read(self.handle, buf)
+            .inspect_err(|_| {
+                self.read_buf.in_message = false;
+                self.read_buf.term_char = false;
+            })?;
+        self.read_buf.in_message = completion.raw() == VI_SUCCESS_MAX_CNT as ViStatus;
+        self.read_buf.term_char = completion.raw() == VI_SUCCESS_TERM_CHAR as ViStatus;
+        Ok((count, completion))
+    }
+
+    /// Reads the rest of the current message into `buf` and returns the
+    /// number of bytes appended.
+    ///
+    /// Unlike `Read::read_to_end`, which would keep reading past the end of
+    /// the message until the timeout expires, this repeats `viRead` only while
+    /// it reports `VI_SUCCESS_MAX_CNT`, stopping at END or, if
+    /// `VI_ATTR_TERMCHAR_EN` is set, at the termination character.
+    pub fn read_until_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let start = buf.len();
+        if !self.read_buf.available().is_empty() {
+            buf.extend_from_slice(self.read_buf.available());
+            self.read_buf.consume(usize::MAX);
+            if !self.read_buf.in_message {
+                return Ok(buf.len() - start);
+            }
+        }
+
+        loop {
+            let len = buf.len();
+            buf.resize(len + CHUNK, 0);
+            match self.vi_read(&mut buf[len..]) {
+                Ok((count, _)) => {
+                    buf.truncate(len + count);
+                    if !self.read_buf.in_message {
+                        return Ok(buf.len() - start);
+                    }
+                }
+                Err(e) => {
+                    buf.truncate(len);
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+}
+
+impl MessageRead for Session<'_> {
+    fn message_done(&self) -> bool {
+        self.read_buf.available().is_empty() && !self.read_buf.in_message
+    }
+
+    fn read_rest(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.read_until_end(buf)
+    }
+}
+
+impl Read for Session<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Large reads skip the buffer when it is empty, as `BufReader` does.
+        if self.read_buf.available().is_empty() && buf.len() >= CHUNK {
+            return Ok(self.vi_read(buf)?.0);
+        }
+        let available = self.fill_buf()?;
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+/// Each refill is a single `viRead`, so the buffered data never extends past
+/// END or the termination character: with `VI_ATTR_TERMCHAR` set to `\n` and
+/// `VI_ATTR_TERMCHAR_EN` enabled, `read_line` and `lines` return one response
+/// at a time.
+impl BufRead for Session<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.read_buf.available().is_empty() {
+            let mut data = std::mem::take(&mut self.read_buf.data);
+            data.resize(CHUNK, 0);
+            let count = match self.vi_read(&mut data) {
+                Ok((count, _)) => count,
+                Err(e) => {
+                    data.clear();
+                    self.read_buf.data = data;
+                    self.read_buf.pos = 0;
+                    return Err(e.into());
+                }
+            };
+            data.truncate(count);
+            self.read_buf.data = data;
+            self.read_buf.pos = 0;
+        }
+        Ok(self.read_buf.available())
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.read_buf.consume(amount);
+    }
+}
+
+impl Write for Session<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.backend().write(self.handle, buf)?)
+    }
+
+    /// `viWrite` is unbuffered, so there is nothing to flush.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+//! Resource locking with `viLock` and `viUnlock`.
+//!
+//! Locks nest: every successful `viLock` needs its own `viUnlock`, which the
+//! guards issue when dropped. A guard dereferences to the session, so the
+//! locked session is used through it. `viUnlock` releases an exclusive lock
+//! before a shared one, so a shared lock cannot be taken under an exclusive
+//! guard; the other way round nests fine.
+
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use super::{timeout_ms, Session};
+use crate::ffi::*;
+use crate::status::{error, Result};
+
+impl<'rm> Session<'rm> {
+    /// Acquires an exclusive lock, waiting up to `timeout` for other sessions
+    /// to release theirs. Fails with `VI_ERROR_TMO` if they do not.
+    pub fn lock_exclusive(&mut self, timeout: Duration) -> Result<LockGuard<'_, 'rm>> {
+        let lock_type = VI_EXCLUSIVE_LOCK as ViAccessMode;
+        let (completion, _) = self.backend().lock(self.handle, lock_type, timeout_ms(timeout), None)?;
+        self.exclusive_locks += 1;
+        Ok(LockGuard {
+            session: self,
+            nested: completion.raw() == VI_SUCCESS_NESTED_EXCLUSIVE as ViStatus,
+        })
+    }
+
+    /// Acquires a shared lock with `key`, or with a key VISA generates if
+    /// `None`. Other sessions that lock with the same key share the resource.
+    ///
+    /// Fails with `VI_ERROR_INV_LOCK_TYPE` while an exclusive guard is held,
+    /// as releasing the shared lock would release the exclusive one instead.
+    pub fn lock_shared(&mut self, key: Option<&str>, timeout: Duration) -> Result<SharedLockGuard<'_, 'rm>> {
+        if self.exclusive_locks > 0 {
+            return Err(error(VI_ERROR_INV_LOCK_TYPE));
+        }
+        let lock_type = VI_SHARED_LOCK as ViAccessMode;
+        let (completion, key) = self.backend().lock(self.handle, lock_type, timeout_ms(timeout), key)?;
+        self.shared_locks += 1;
+        Ok(SharedLockGuard {
+            session: self,
+            key,
+            nested: completion.raw() == VI_SUCCESS_NESTED_SHARED as ViStatus,
+        })
+    }
+
+    /// Runs `f` while holding an exclusive lock, released afterwards even if
+    /// `f` panics.
+    pub fn with_lock<R>(&mut self, timeout: Duration, f: impl FnOnce(&mut Session<'rm>) -> R) -> Result<R> {
+        let mut guard = self.lock_exclusive(timeout)?;
+        Ok(f(&mut guard))
+    }
+
+    /// The number of exclusive locks held through guards, counting nested
+    /// ones.
+    pub fn exclusive_locks(&self) -> u32 {
+        self.exclusive_locks
+    }
+
+    /// The number of shared locks held through guards, counting nested ones.
+    pub fn shared_locks(&self) -> u32 {
+        self.shared_locks
+    }
+}
+
+/// An exclusive lock, released with `viUnlock` when dropped.
+#[derive(Debug)]
+#[must_use = "the lock is released when the guard is dropped"]
+pub struct LockGuard<'s, 'rm> {
+    session: &'s mut Session<'rm>,
+    nested: bool,
+}
+
+impl LockGuard<'_, '_> {
+    /// Whether the session already held an exclusive lock
+    /// (`VI_SUCCESS_NESTED_EXCLUSIVE`).
+    pub fn is_nested(&self) -> bool {
+        self.nested
+    }
+
+    /// Releases the lock, reporting the status `Drop` would ignore.
+    pub fn unlock(self) -> Result {
+        ManuallyDrop::new(self).release()
+    }
+
+    fn release(&mut self) -> Result {
+        self.session.exclusive_locks -= 1;
+        self.session.backend().unlock(self.session.handle)
+    }
+}
+
+impl<'rm> Deref for LockGuard<'_, 'rm> {
+    type Target = Session<'rm>;
+
+    fn deref(&self) -> &Self::Target {
+        self.session
+    }
+}
+
+impl DerefMut for LockGuard<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.session
+    }
+}
+
+impl Drop for LockGuard<'_, '_> {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+/// A shared lock, released with `viUnlock` when dropped.
+#[derive(Debug)]
+#[must_use = "the lock is released when the guard is dropped"]
+pub struct SharedLockGuard<'s, 'rm> {
+    session: &'s mut Session<'rm>,
+    key: String,
+    nested: bool,
+}
+
+impl SharedLockGuard<'_, '_> {
+    /// The access key other sessions pass to `lock_shared` to share the lock.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Whether the session already held a shared lock
+    /// (`VI_SUCCESS_NESTED_SHARED`).
+    pub fn is_nested(&self) -> bool {
+        self.nested
+    }
+
+    /// Releases the lock, reporting the status `Drop` would ignore.
+    pub fn unlock(self) -> Result {
+        let mut guard = ManuallyDrop::new(self);
+        drop(mem::take(&mut guard.key));
+        guard.release()
+    }
+
+    fn release(&mut self) -> Result {
+        self.session.shared_locks -= 1;
+        self.session.backend().unlock(self.session.handle)
+    }
+}
+
+impl<'rm> Deref for SharedLockGuard<'_, 'rm> {
+    type Target = Session<'rm>;
+
+    fn deref(&self) -> &Self::Target {
+        self.session
+    }
+}
+
+impl DerefMut for SharedLockGuard<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.session
+    }
+}
+
+impl Drop for SharedLockGuard<'_, '_> {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
@@ -0,0 +1,180 @@
+//! Sessions to instrument resources.
+
+mod block;
+mod errors;
+mod events;
+mod io;
+mod lock;
+mod opc;
+mod registers;
+mod text;
+
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::attr::{self, Attribute, SetAttrValue, Writable};
+use crate::backend::VisaBackend;
+use crate::block::ByteOrder;
+use crate::ffi::*;
+use crate::rm::ResourceManager;
+use crate::scpi::ScpiError;
+use crate::status::Result;
+
+pub use errors::{ErrorCheck, ErrorQueueOptions, ErrorReport};
+pub use lock::{LockGuard, SharedLockGuard};
+pub use opc::OpcStrategy;
+pub use registers::StatusWait;
+pub use text::{Encoding, TextOptions};
+
+/// Whether `viOpen` acquires a lock on the resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccessMode {
+    /// No lock, the default.
+    #[default]
+    NoLock,
+    /// An exclusive lock, held until the session is closed.
+    ExclusiveLock,
+}
+
+/// Options for `ResourceManager::open_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenOptions {
+    access_mode: AccessMode,
+    load_config: bool,
+    open_timeout: Duration,
+}
+
+impl OpenOptions {
+    /// No lock, no configured attribute values, and an immediate timeout.
+    pub fn new() -> Self {
+        OpenOptions {
+            access_mode: AccessMode::NoLock,
+            load_config: false,
+            open_timeout: Duration::ZERO,
+        }
+    }
+
+    pub fn access_mode(mut self, access_mode: AccessMode) -> Self {
+        self.access_mode = access_mode;
+        self
+    }
+
+    /// Applies the attribute values stored in the VISA configuration
+    /// (`VI_LOAD_CONFIG`).
+    pub fn load_config(mut self, load_config: bool) -> Self {
+        self.load_config = load_config;
+        self
+    }
+
+    /// How long `viOpen` waits for the lock when `AccessMode::ExclusiveLock`
+    /// is used. Rounded down to milliseconds.
+    pub fn open_timeout(mut self, open_timeout: Duration) -> Self {
+        self.open_timeout = open_timeout;
+        self
+    }
+
+    pub(crate) fn access_mode_bits(&self) -> ViAccessMode {
+        let lock = match self.access_mode {
+            AccessMode::NoLock => VI_NO_LOCK,
+            AccessMode::ExclusiveLock => VI_EXCLUSIVE_LOCK,
+        };
+        let config = if self.load_config { VI_LOAD_CONFIG } else { 0 };
+        (lock | config) as ViAccessMode
+    }
+
+    pub(crate) fn open_timeout_ms(&self) -> ViUInt32 {
+        timeout_ms(self.open_timeout)
+    }
+}
+
+/// A VISA timeout in milliseconds, rounded down and kept below
+/// `VI_TMO_INFINITE`.
+pub(crate) fn timeout_ms(timeout: Duration) -> ViUInt32 {
+    timeout.as_millis().min(VI_TMO_INFINITE as u128 - 1) as ViUInt32
+}
+
+impl Default for OpenOptions {
+    fn default() -> Self {
+        OpenOptions::new()
+    }
+}
+
+/// An open session to a resource, closed when dropped.
+///
+/// Sessions implement `Read`, `BufRead` and `Write` on top of `viRead` and
+/// `viWrite`. `VI_ERROR_TMO` surfaces as `io::ErrorKind::TimedOut`; other
+/// errors carry the `VisaError` as the inner error.
+#[derive(Debug)]
+pub struct Session<'rm> {
+    rm: &'rm ResourceManager,
+    handle: ViSession,
+    read_buf: io::ReadBuffer,
+    text: TextOptions,
+    byte_order: ByteOrder,
+    errors: ErrorQueueOptions,
+    scpi_warnings: Vec<ScpiError>,
+    opc: OpcStrategy,
+    exclusive_locks: u32,
+    shared_locks: u32,
+}
+
+impl<'rm> Session<'rm> {
+    pub(crate) fn new(rm: &'rm ResourceManager, handle: ViSession) -> Self {
+        Session {
+            rm,
+            handle,
+            read_buf: io::ReadBuffer::default(),
+            text: TextOptions::new(),
+            byte_order: ByteOrder::BigEndian,
+            errors: ErrorQueueOptions::new(),
+            scpi_warnings: Vec::new(),
+            opc: OpcStrategy::Query,
+            exclusive_locks: 0,
+            shared_locks: 0,
+        }
+    }
+
+    /// The resource manager the session was opened with.
+    pub fn resource_manager(&self) -> &'rm ResourceManager {
+        self.rm
+    }
+
+    /// Reads attribute `A`, e.g. `session.get::<attr::TmoValue>()`.
+    pub fn get<A: Attribute>(&self) -> Result<A::Value> {
+        attr::get::<A>(&**self.backend(), self.handle)
+    }
+
+    /// Sets attribute `A`, e.g. `session.set::<attr::TermChar>(b'\n')`. Only
+    /// compiles for read-write attributes.
+    pub fn set<A: Writable>(&self, value: A::Value) -> Result
+    where
+        A::Value: SetAttrValue,
+    {
+        attr::set::<A>(&**self.backend(), self.handle, value)
+    }
+
+    /// Closes the session, reporting the status `Drop` would ignore.
+    pub fn close(mut self) -> Result {
+        let handle = mem::replace(&mut self.handle, VI_NULL);
+        self.backend().close(handle)
+    }
+
+    /// The raw handle, for calls not covered by the safe API. It must not be
+    /// closed, and is only meaningful to the backend of the resource manager.
+    pub fn as_raw(&self) -> ViSession {
+        self.handle
+    }
+
+    pub(crate) fn backend(&self) -> &'rm Arc<dyn VisaBackend> {
+        self.rm.backend()
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        if self.handle != VI_NULL {
+            let _ = self.backend().close(self.handle);
+        }
+    }
+}
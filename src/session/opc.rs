@@ -0,0 +1,98 @@
+//! Operation-complete synchronization: `*OPC?`, `*OPC` and `*WAI`.
+
+use std::io;
+use std::time::Duration;
+
+use super::{timeout_ms, Session, StatusWait};
+use crate::attr;
+use crate::registers::{StandardEvent, StatusByte};
+
+/// How `Session::wait_opc` waits for pending operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpcStrategy {
+    /// A blocking `*OPC?` query, with `VI_ATTR_TMO_VALUE` raised to the wait
+    /// timeout for its duration. The default. Simple, but the bus is tied up
+    /// until the operation completes.
+    #[default]
+    Query,
+    /// `*OPC`, then a wait for the service request raised when it sets the
+    /// OPC event (`VI_EVENT_SERVICE_REQ`).
+    ServiceRequest,
+    /// `*OPC`, then serial polls (`viReadSTB`) at the given interval until
+    /// the event summary bit is set.
+    Poll(Duration),
+}
+
+impl Session<'_> {
+    pub fn opc_strategy(&self) -> OpcStrategy {
+        self.opc
+    }
+
+    pub fn set_opc_strategy(&mut self, strategy: OpcStrategy) {
+        self.opc = strategy;
+    }
+
+    /// Blocks until the operations started by earlier commands have completed,
+    /// using the session's `OpcStrategy`. Fails with `io::ErrorKind::TimedOut`
+    /// after `timeout`.
+    ///
+    /// The `*OPC` strategies temporarily add OPC to the standard event enable
+    /// register (and ESB to the service request enable register) and clear
+    /// the standard event status register.
+    pub fn wait_opc(&mut self, timeout: Duration) -> io::Result<()> {
+        match self.opc {
+            OpcStrategy::Query => self.query_opc(timeout),
+            OpcStrategy::ServiceRequest => self.wait_opc_event(StatusWait::ServiceRequest, timeout),
+            OpcStrategy::Poll(interval) => self.wait_opc_event(StatusWait::Poll(interval), timeout),
+        }
+    }
+
+    /// Makes the instrument finish pending operations before executing any
+    /// later command (`*WAI`). Unlike `wait_opc`, this returns immediately.
+    pub fn wai(&mut self) -> io::Result<()> {
+        self.write_str("*WAI")
+    }
+
+    fn query_opc(&mut self, timeout: Duration) -> io::Result<()> {
+        let previous = self.get::<attr::TmoValue>()?;
+        self.set::<attr::TmoValue>(timeout_ms(timeout))?;
+        let response = self.query("*OPC?");
+        self.set::<attr::TmoValue>(previous)?;
+        match response?.trim() {
+            "1" | "+1" => Ok(()),
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unexpected *OPC? response {:?}", other),
+            )),
+        }
+    }
+
+    fn wait_opc_event(&mut self, wait: StatusWait, timeout: Duration) -> io::Result<()> {
+        let event_enable = self.standard_event_enable()?;
+        let request_enable = self.service_request_enable()?;
+        self.enable_standard_events(event_enable | StandardEvent::OPC)?;
+        if wait == StatusWait::ServiceRequest {
+            self.enable_service_requests(request_enable | StatusByte::ESB)?;
+        }
+        // Clears events left over from before, which would end the wait early.
+        self.read_standard_events()?;
+
+        let result = self
+            .write_str("*OPC")
+            .and_then(|()| self.wait_for_status(StatusByte::ESB, wait, timeout))
+            .and_then(|_| self.read_standard_events());
+
+        self.enable_standard_events(event_enable)?;
+        if wait == StatusWait::ServiceRequest {
+            self.enable_service_requests(request_enable)?;
+        }
+        let events = result?;
+        if !events.contains(StandardEvent::OPC) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("event summary set without OPC: {:?}", events),
+            ));
+        }
+        Ok(())
+    }
+}
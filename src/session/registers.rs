@@ -0,0 +1,99 @@
+//! The IEEE 488.2 status model: `viReadSTB`, `*ESR?`, `*SRE` and `*ESE`.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use super::Session;
+use crate::ascii::AsciiValue;
+use crate::event::EventKind;
+use crate::ffi::*;
+use crate::registers::{StandardEvent, StatusByte};
+use crate::status::{error, Result};
+
+/// How `Session::wait_for_status` notices status changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusWait {
+    /// Serial polls (`viReadSTB`) at the given interval.
+    Poll(Duration),
+    /// Service requests (`VI_EVENT_SERVICE_REQ`), each followed by a serial
+    /// poll. The awaited bits must be enabled with
+    /// `Session::enable_service_requests`, or no request is raised.
+    ServiceRequest,
+}
+
+impl Session<'_> {
+    /// Reads the status byte with a serial poll (`viReadSTB`).
+    pub fn read_stb(&self) -> Result<StatusByte> {
+        let status = self.backend().read_stb(self.handle)?;
+        Ok(StatusByte::from_bits_retain(status as u8))
+    }
+
+    /// Reads and clears the standard event status register (`*ESR?`).
+    pub fn read_standard_events(&mut self) -> io::Result<StandardEvent> {
+        self.query_register("*ESR?").map(StandardEvent::from_bits_retain)
+    }
+
+    /// The status byte bits that raise a service request (`*SRE?`).
+    pub fn service_request_enable(&mut self) -> io::Result<StatusByte> {
+        self.query_register("*SRE?").map(StatusByte::from_bits_retain)
+    }
+
+    /// Sets the status byte bits that raise a service request (`*SRE`).
+    pub fn enable_service_requests(&mut self, mask: StatusByte) -> io::Result<()> {
+        self.write_str(&format!("*SRE {}", mask.bits()))
+    }
+
+    /// The standard events summarized in `StatusByte::ESB` (`*ESE?`).
+    pub fn standard_event_enable(&mut self) -> io::Result<StandardEvent> {
+        self.query_register("*ESE?").map(StandardEvent::from_bits_retain)
+    }
+
+    /// Sets the standard events summarized in `StatusByte::ESB` (`*ESE`).
+    pub fn enable_standard_events(&mut self, mask: StandardEvent) -> io::Result<()> {
+        self.write_str(&format!("*ESE {}", mask.bits()))
+    }
+
+    /// Clears the event registers and the error queue (`*CLS`).
+    pub fn clear_status(&mut self) -> io::Result<()> {
+        self.write_str("*CLS")
+    }
+
+    /// Waits until the status byte has any of the bits in `condition` set and
+    /// returns it. Fails with `io::ErrorKind::TimedOut` after `timeout`.
+    pub fn wait_for_status(&mut self, condition: StatusByte, wait: StatusWait, timeout: Duration) -> io::Result<StatusByte> {
+        let deadline = Instant::now() + timeout;
+        match wait {
+            StatusWait::Poll(interval) => loop {
+                let status = self.read_stb()?;
+                if status.intersects(condition) {
+                    return Ok(status);
+                }
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(io::Error::from(error(VI_ERROR_TMO)));
+                }
+                std::thread::sleep(interval.min(deadline - now));
+            },
+            StatusWait::ServiceRequest => {
+                let guard = self.enable_event(EventKind::ServiceRequest)?;
+                loop {
+                    // Also reads the status byte of a request raised before the wait.
+                    let status = guard.read_stb()?;
+                    if status.intersects(condition) {
+                        return Ok(status);
+                    }
+                    // Rounded up, as the wait is in whole milliseconds and
+                    // must not time out before the deadline.
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    let remaining = Duration::from_millis(remaining.as_micros().div_ceil(1000) as u64);
+                    guard.wait_event(EventKind::ServiceRequest, remaining)?;
+                }
+            }
+        }
+    }
+
+    fn query_register(&mut self, command: &str) -> io::Result<u8> {
+        let response = self.query(command)?;
+        u8::parse_token(&response).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
@@ -0,0 +1,242 @@
+//! Text exchanges: `write_str`, `read_string`, `query` and ASCII values.
+
+use std::io::{self, Write};
+
+use super::Session;
+use crate::ascii::{self, AsciiValue};
+use crate::attr;
+use crate::status::Result;
+
+/// How text is converted to and from the bytes on the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// 7-bit ASCII. Other characters are rejected.
+    Ascii,
+    /// ISO 8859-1, one byte per character up to U+00FF.
+    Latin1,
+    /// UTF-8, the default.
+    #[default]
+    Utf8,
+}
+
+impl Encoding {
+    /// Encodes `text`, failing with `io::ErrorKind::InvalidInput` for
+    /// characters the encoding cannot represent.
+    pub fn encode(&self, text: &str) -> io::Result<Vec<u8>> {
+        match self {
+            Encoding::Ascii if !text.is_ascii() => Err(unrepresentable(text, "ASCII")),
+            Encoding::Ascii | Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).map_err(|_| unrepresentable(text, "Latin-1")))
+                .collect(),
+        }
+    }
+
+    /// Decodes `bytes`, failing with `io::ErrorKind::InvalidData` if they are
+    /// not valid in the encoding.
+    pub fn decode(&self, bytes: &[u8]) -> io::Result<String> {
+        match self {
+            Encoding::Ascii if !bytes.is_ascii() => {
+                Err(io::Error::new(io::ErrorKind::InvalidData, "response is not ASCII"))
+            }
+            Encoding::Ascii | Encoding::Utf8 => {
+                String::from_utf8(bytes.to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Encoding::Latin1 => Ok(bytes.iter().map(|&b| char::from(b)).collect()),
+        }
+    }
+}
+
+fn unrepresentable(text: &str, encoding: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{:?} cannot be encoded as {}", text, encoding),
+    )
+}
+
+/// Options for the text methods of `Session`, applied with
+/// `Session::set_text_options`.
+///
+/// A new session encodes and strips text as `TextOptions::new()` says, but
+/// leaves the VISA attributes as `viOpen` set them: `VI_ATTR_TERMCHAR_EN` is
+/// off, so reads only stop on END. Call `set_text_options`, even with the
+/// defaults, before talking to an instrument that does not assert END, e.g.
+/// over a serial port or a raw socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextOptions {
+    write_termination: String,
+    read_termination: String,
+    encoding: Encoding,
+    trim: bool,
+    send_end: bool,
+    separator: char,
+}
+
+impl TextOptions {
+    /// `\n` terminations, UTF-8, no trimming, END sent with each write and
+    /// comma-separated values.
+    pub fn new() -> Self {
+        TextOptions {
+            write_termination: "\n".to_string(),
+            read_termination: "\n".to_string(),
+            encoding: Encoding::Utf8,
+            trim: false,
+            send_end: true,
+            separator: ',',
+        }
+    }
+
+    /// Appended to every `write_str`.
+    pub fn write_termination(mut self, termination: &str) -> Self {
+        self.write_termination = termination.to_string();
+        self
+    }
+
+    /// Removed from the end of every response. A single-byte termination is
+    /// also set as `VI_ATTR_TERMCHAR` with `VI_ATTR_TERMCHAR_EN`, so reads stop
+    /// on it even if the instrument does not assert END (e.g. serial ports).
+    pub fn read_termination(mut self, termination: &str) -> Self {
+        self.read_termination = termination.to_string();
+        self
+    }
+
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Also strips leading and trailing whitespace from responses.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Whether END is asserted with the last byte of each write
+    /// (`VI_ATTR_SEND_END_EN`).
+    pub fn send_end(mut self, send_end: bool) -> Self {
+        self.send_end = send_end;
+        self
+    }
+
+    /// Separates the values read by `read_ascii_values`.
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// The termination byte handed to VISA, if the read termination is a
+    /// single byte.
+    pub(crate) fn term_char(&self) -> Option<u8> {
+        match self.read_termination.as_bytes() {
+            &[byte] => Some(byte),
+            _ => None,
+        }
+    }
+
+    /// The bytes written for `text`.
+    pub(crate) fn encode_command(&self, text: &str) -> io::Result<Vec<u8>> {
+        self.encode_command_with(text, |_| Ok(()))
+    }
+
+    /// The bytes written for `text` followed by the data `extend` appends,
+    /// e.g. a binary block.
+    pub(crate) fn encode_command_with(
+        &self,
+        text: &str,
+        extend: impl FnOnce(&mut Vec<u8>) -> io::Result<()>,
+    ) -> io::Result<Vec<u8>> {
+        let mut bytes = self.encoding.encode(text)?;
+        extend(&mut bytes)?;
+        bytes.extend(self.encoding.encode(&self.write_termination)?);
+        Ok(bytes)
+    }
+
+    /// Decodes a response, removing the termination and, if enabled,
+    /// surrounding whitespace.
+    pub(crate) fn decode_response(&self, bytes: &[u8]) -> io::Result<String> {
+        let mut text = self.encoding.decode(bytes)?;
+        if !self.read_termination.is_empty() && text.ends_with(&self.read_termination) {
+            text.truncate(text.len() - self.read_termination.len());
+        }
+        if self.trim {
+            text = text.trim().to_string();
+        }
+        Ok(text)
+    }
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        TextOptions::new()
+    }
+}
+
+impl Session<'_> {
+    /// The options used by `write_str`, `read_string` and `query`. Until
+    /// `set_text_options` is called they are `TextOptions::new()`, without
+    /// the VISA attributes it implies.
+    pub fn text_options(&self) -> &TextOptions {
+        &self.text
+    }
+
+    /// Replaces the text options, updating `VI_ATTR_TERMCHAR`,
+    /// `VI_ATTR_TERMCHAR_EN` and `VI_ATTR_SEND_END_EN` to match.
+    pub fn set_text_options(&mut self, options: TextOptions) -> Result {
+        match options.term_char() {
+            Some(term_char) => {
+                self.set::<attr::TermChar>(term_char)?;
+                self.set::<attr::TermCharEn>(true)?;
+            }
+            None => {
+                self.set::<attr::TermCharEn>(false)?;
+            }
+        }
+        let completion = self.set::<attr::SendEndEn>(options.send_end)?;
+        self.text = options;
+        Ok(completion)
+    }
+
+    /// Writes `text` followed by the write termination.
+    pub fn write_str(&mut self, text: &str) -> io::Result<()> {
+        self.send(text)?;
+        self.after_command(false)
+    }
+
+    /// `write_str` without the error queue check.
+    pub(super) fn send(&mut self, text: &str) -> io::Result<()> {
+        let bytes = self.text.encode_command(text)?;
+        self.write_all(&bytes)
+    }
+
+    /// Reads one response and decodes it, without the read termination.
+    pub fn read_string(&mut self) -> io::Result<String> {
+        let mut bytes = Vec::new();
+        self.read_until_end(&mut bytes)?;
+        self.text.decode_response(&bytes)
+    }
+
+    /// Writes `command` and reads the response, e.g. `query("*IDN?")`.
+    pub fn query(&mut self, command: &str) -> io::Result<String> {
+        self.send(command)?;
+        let response = self.read_string()?;
+        self.after_command(true)?;
+        Ok(response)
+    }
+
+    /// Reads a response holding a list of numbers, see `ascii`. Invalid
+    /// values are `io::ErrorKind::InvalidData` errors.
+    pub fn read_ascii_values<T: AsciiValue>(&mut self) -> io::Result<Vec<T>> {
+        let response = self.read_string()?;
+        ascii::parse_values(&response, self.text.separator).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes `command` and reads the list of numbers in the response, e.g.
+    /// `query_ascii_values::<f64>("MEAS:VOLT?")`.
+    pub fn query_ascii_values<T: AsciiValue>(&mut self, command: &str) -> io::Result<Vec<T>> {
+        self.send(command)?;
+        let values = self.read_ascii_values()?;
+        self.after_command(true)?;
+        Ok(values)
+    }
+}
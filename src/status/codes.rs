@@ -0,0 +1,530 @@
+//! Names and descriptions of the status codes defined in `visa.h`.
+//!
+//! The descriptions follow the VISA specification, so they are available
+//! without an open session (unlike `viStatusDesc`).
+
+use crate::ffi::*;
+
+/// `(status, name, description)` for every code. Codes sharing a value
+/// (`VI_ERROR_INV_SESSION` and `VI_ERROR_INV_OBJECT`) are listed with the
+/// preferred name first.
+pub(super) const CODES: &[(ViStatus, &str, &str)] = &[
+    (
+        VI_SUCCESS as ViStatus,
+        "VI_SUCCESS",
+        "Operation completed successfully.",
+    ),
+    (
+        VI_SUCCESS_EVENT_EN as ViStatus,
+        "VI_SUCCESS_EVENT_EN",
+        "Specified event is already enabled for at least one of the specified mechanisms.",
+    ),
+    (
+        VI_SUCCESS_EVENT_DIS as ViStatus,
+        "VI_SUCCESS_EVENT_DIS",
+        "Specified event is already disabled for at least one of the specified mechanisms.",
+    ),
+    (
+        VI_SUCCESS_QUEUE_EMPTY as ViStatus,
+        "VI_SUCCESS_QUEUE_EMPTY",
+        "Operation completed successfully, but the queue was already empty.",
+    ),
+    (
+        VI_SUCCESS_TERM_CHAR as ViStatus,
+        "VI_SUCCESS_TERM_CHAR",
+        "The specified termination character was read.",
+    ),
+    (
+        VI_SUCCESS_MAX_CNT as ViStatus,
+        "VI_SUCCESS_MAX_CNT",
+        "The number of bytes transferred is equal to the requested input count.",
+    ),
+    (
+        VI_SUCCESS_DEV_NPRESENT as ViStatus,
+        "VI_SUCCESS_DEV_NPRESENT",
+        "Session opened successfully, but the device at the specified address is not responding.",
+    ),
+    (
+        VI_SUCCESS_TRIG_MAPPED as ViStatus,
+        "VI_SUCCESS_TRIG_MAPPED",
+        "The path from the trigger source to the trigger destination is already mapped.",
+    ),
+    (
+        VI_SUCCESS_QUEUE_NEMPTY as ViStatus,
+        "VI_SUCCESS_QUEUE_NEMPTY",
+        "Wait terminated successfully on receipt of an event notification. More events of the requested type are available.",
+    ),
+    (
+        VI_SUCCESS_NCHAIN as ViStatus,
+        "VI_SUCCESS_NCHAIN",
+        "Event handled successfully. Do not invoke any other handlers on this session for this event.",
+    ),
+    (
+        VI_SUCCESS_NESTED_SHARED as ViStatus,
+        "VI_SUCCESS_NESTED_SHARED",
+        "Operation completed successfully, and this session has nested shared locks.",
+    ),
+    (
+        VI_SUCCESS_NESTED_EXCLUSIVE as ViStatus,
+        "VI_SUCCESS_NESTED_EXCLUSIVE",
+        "Operation completed successfully, and this session has nested exclusive locks.",
+    ),
+    (
+        VI_SUCCESS_SYNC as ViStatus,
+        "VI_SUCCESS_SYNC",
+        "Asynchronous operation request was actually performed synchronously.",
+    ),
+    (
+        VI_WARN_QUEUE_OVERFLOW as ViStatus,
+        "VI_WARN_QUEUE_OVERFLOW",
+        "VISA received more event information of the specified type than the configured queue size could hold.",
+    ),
+    (
+        VI_WARN_CONFIG_NLOADED as ViStatus,
+        "VI_WARN_CONFIG_NLOADED",
+        "The specified configuration either does not exist or could not be loaded. VISA-specified defaults will be used.",
+    ),
+    (
+        VI_WARN_NULL_OBJECT as ViStatus,
+        "VI_WARN_NULL_OBJECT",
+        "The specified object reference is uninitialized.",
+    ),
+    (
+        VI_WARN_NSUP_ATTR_STATE as ViStatus,
+        "VI_WARN_NSUP_ATTR_STATE",
+        "Although the specified state of the attribute is valid, it is not supported by this implementation.",
+    ),
+    (
+        VI_WARN_UNKNOWN_STATUS as ViStatus,
+        "VI_WARN_UNKNOWN_STATUS",
+        "The status code passed to the operation could not be interpreted.",
+    ),
+    (
+        VI_WARN_NSUP_BUF as ViStatus,
+        "VI_WARN_NSUP_BUF",
+        "The specified I/O buffer type is not supported.",
+    ),
+    (
+        VI_WARN_EXT_FUNC_NIMPL as ViStatus,
+        "VI_WARN_EXT_FUNC_NIMPL",
+        "The operation succeeded, but a lower level driver did not implement the extended functionality.",
+    ),
+    (
+        VI_WARN_SERVER_CERT_UNTRUSTED as ViStatus,
+        "VI_WARN_SERVER_CERT_UNTRUSTED",
+        "The server certificate is not trusted by the client.",
+    ),
+    (
+        VI_ERROR_SYSTEM_ERROR as ViStatus,
+        "VI_ERROR_SYSTEM_ERROR",
+        "Unknown system error (miscellaneous error).",
+    ),
+    (
+        VI_ERROR_INV_OBJECT as ViStatus,
+        "VI_ERROR_INV_OBJECT",
+        "The given session or object reference is invalid.",
+    ),
+    (
+        VI_ERROR_RSRC_LOCKED as ViStatus,
+        "VI_ERROR_RSRC_LOCKED",
+        "Specified type of lock cannot be obtained, or specified operation cannot be performed, because the resource is locked.",
+    ),
+    (
+        VI_ERROR_INV_EXPR as ViStatus,
+        "VI_ERROR_INV_EXPR",
+        "Invalid expression specified for search.",
+    ),
+    (
+        VI_ERROR_RSRC_NFOUND as ViStatus,
+        "VI_ERROR_RSRC_NFOUND",
+        "Insufficient location information or the requested device or resource is not present in the system.",
+    ),
+    (
+        VI_ERROR_INV_RSRC_NAME as ViStatus,
+        "VI_ERROR_INV_RSRC_NAME",
+        "Invalid resource reference specified. Parsing error.",
+    ),
+    (
+        VI_ERROR_INV_ACC_MODE as ViStatus,
+        "VI_ERROR_INV_ACC_MODE",
+        "Invalid access mode.",
+    ),
+    (
+        VI_ERROR_TMO as ViStatus,
+        "VI_ERROR_TMO",
+        "Timeout expired before operation completed.",
+    ),
+    (
+        VI_ERROR_CLOSING_FAILED as ViStatus,
+        "VI_ERROR_CLOSING_FAILED",
+        "Unable to deallocate the previously allocated data structures corresponding to this session or object reference.",
+    ),
+    (
+        VI_ERROR_INV_DEGREE as ViStatus,
+        "VI_ERROR_INV_DEGREE",
+        "Specified degree is invalid.",
+    ),
+    (
+        VI_ERROR_INV_JOB_ID as ViStatus,
+        "VI_ERROR_INV_JOB_ID",
+        "Specified job identifier is invalid.",
+    ),
+    (
+        VI_ERROR_NSUP_ATTR as ViStatus,
+        "VI_ERROR_NSUP_ATTR",
+        "The specified attribute is not defined or supported by the referenced session, event, or find list.",
+    ),
+    (
+        VI_ERROR_NSUP_ATTR_STATE as ViStatus,
+        "VI_ERROR_NSUP_ATTR_STATE",
+        "The specified state of the attribute is not valid, or is not supported as defined by the session, event, or find list.",
+    ),
+    (
+        VI_ERROR_ATTR_READONLY as ViStatus,
+        "VI_ERROR_ATTR_READONLY",
+        "The specified attribute is read-only.",
+    ),
+    (
+        VI_ERROR_INV_LOCK_TYPE as ViStatus,
+        "VI_ERROR_INV_LOCK_TYPE",
+        "The specified type of lock is not supported by this resource.",
+    ),
+    (
+        VI_ERROR_INV_ACCESS_KEY as ViStatus,
+        "VI_ERROR_INV_ACCESS_KEY",
+        "The access key to the resource associated with this session is invalid.",
+    ),
+    (
+        VI_ERROR_INV_EVENT as ViStatus,
+        "VI_ERROR_INV_EVENT",
+        "Specified event type is not supported by the resource.",
+    ),
+    (
+        VI_ERROR_INV_MECH as ViStatus,
+        "VI_ERROR_INV_MECH",
+        "Invalid mechanism specified.",
+    ),
+    (
+        VI_ERROR_HNDLR_NINSTALLED as ViStatus,
+        "VI_ERROR_HNDLR_NINSTALLED",
+        "A handler is not currently installed for the specified event.",
+    ),
+    (
+        VI_ERROR_INV_HNDLR_REF as ViStatus,
+        "VI_ERROR_INV_HNDLR_REF",
+        "The given handler reference is invalid.",
+    ),
+    (
+        VI_ERROR_INV_CONTEXT as ViStatus,
+        "VI_ERROR_INV_CONTEXT",
+        "Specified event context is invalid.",
+    ),
+    (
+        VI_ERROR_NENABLED as ViStatus,
+        "VI_ERROR_NENABLED",
+        "The session must be enabled for events of the specified type in order to receive them.",
+    ),
+    (
+        VI_ERROR_ABORT as ViStatus,
+        "VI_ERROR_ABORT",
+        "The operation was aborted.",
+    ),
+    (
+        VI_ERROR_RAW_WR_PROT_VIOL as ViStatus,
+        "VI_ERROR_RAW_WR_PROT_VIOL",
+        "Violation of raw write protocol occurred during transfer.",
+    ),
+    (
+        VI_ERROR_RAW_RD_PROT_VIOL as ViStatus,
+        "VI_ERROR_RAW_RD_PROT_VIOL",
+        "Violation of raw read protocol occurred during transfer.",
+    ),
+    (
+        VI_ERROR_OUTP_PROT_VIOL as ViStatus,
+        "VI_ERROR_OUTP_PROT_VIOL",
+        "Device reported an output protocol error during transfer.",
+    ),
+    (
+        VI_ERROR_INP_PROT_VIOL as ViStatus,
+        "VI_ERROR_INP_PROT_VIOL",
+        "Device reported an input protocol error during transfer.",
+    ),
+    (
+        VI_ERROR_BERR as ViStatus,
+        "VI_ERROR_BERR",
+        "Bus error occurred during transfer.",
+    ),
+    (
+        VI_ERROR_IN_PROGRESS as ViStatus,
+        "VI_ERROR_IN_PROGRESS",
+        "Unable to queue the asynchronous operation because there is already an operation in progress.",
+    ),
+    (
+        VI_ERROR_INV_SETUP as ViStatus,
+        "VI_ERROR_INV_SETUP",
+        "Unable to start operation because setup is invalid (due to attributes being set to an inconsistent state).",
+    ),
+    (
+        VI_ERROR_QUEUE_ERROR as ViStatus,
+        "VI_ERROR_QUEUE_ERROR",
+        "Unable to queue the asynchronous operation.",
+    ),
+    (
+        VI_ERROR_ALLOC as ViStatus,
+        "VI_ERROR_ALLOC",
+        "Insufficient system resources to perform necessary memory allocation.",
+    ),
+    (
+        VI_ERROR_INV_MASK as ViStatus,
+        "VI_ERROR_INV_MASK",
+        "Invalid buffer mask specified.",
+    ),
+    (
+        VI_ERROR_IO as ViStatus,
+        "VI_ERROR_IO",
+        "Could not perform operation because of an I/O error.",
+    ),
+    (
+        VI_ERROR_INV_FMT as ViStatus,
+        "VI_ERROR_INV_FMT",
+        "A format specifier in the format string is invalid.",
+    ),
+    (
+        VI_ERROR_NSUP_FMT as ViStatus,
+        "VI_ERROR_NSUP_FMT",
+        "A format specifier in the format string is not supported.",
+    ),
+    (
+        VI_ERROR_LINE_IN_USE as ViStatus,
+        "VI_ERROR_LINE_IN_USE",
+        "The specified trigger line is currently in use.",
+    ),
+    (
+        VI_ERROR_LINE_NRESERVED as ViStatus,
+        "VI_ERROR_LINE_NRESERVED",
+        "An attempt was made to use a trigger line that was not reserved.",
+    ),
+    (
+        VI_ERROR_NSUP_MODE as ViStatus,
+        "VI_ERROR_NSUP_MODE",
+        "The specified mode is not supported by this VISA implementation.",
+    ),
+    (
+        VI_ERROR_SRQ_NOCCURRED as ViStatus,
+        "VI_ERROR_SRQ_NOCCURRED",
+        "Service request has not been received for the session.",
+    ),
+    (
+        VI_ERROR_INV_SPACE as ViStatus,
+        "VI_ERROR_INV_SPACE",
+        "Invalid address space specified.",
+    ),
+    (
+        VI_ERROR_INV_OFFSET as ViStatus,
+        "VI_ERROR_INV_OFFSET",
+        "Invalid offset specified.",
+    ),
+    (
+        VI_ERROR_INV_WIDTH as ViStatus,
+        "VI_ERROR_INV_WIDTH",
+        "Invalid source or destination width specified.",
+    ),
+    (
+        VI_ERROR_NSUP_OFFSET as ViStatus,
+        "VI_ERROR_NSUP_OFFSET",
+        "Specified offset is not accessible from this hardware.",
+    ),
+    (
+        VI_ERROR_NSUP_VAR_WIDTH as ViStatus,
+        "VI_ERROR_NSUP_VAR_WIDTH",
+        "Cannot support source and destination widths that are different.",
+    ),
+    (
+        VI_ERROR_WINDOW_NMAPPED as ViStatus,
+        "VI_ERROR_WINDOW_NMAPPED",
+        "The specified session is not currently mapped.",
+    ),
+    (
+        VI_ERROR_RESP_PENDING as ViStatus,
+        "VI_ERROR_RESP_PENDING",
+        "A previous response is still pending, causing a multiple query error.",
+    ),
+    (
+        VI_ERROR_NLISTENERS as ViStatus,
+        "VI_ERROR_NLISTENERS",
+        "No listeners condition is detected (both NRFD and NDAC are deasserted).",
+    ),
+    (
+        VI_ERROR_NCIC as ViStatus,
+        "VI_ERROR_NCIC",
+        "The interface associated with this session is not currently the controller in charge.",
+    ),
+    (
+        VI_ERROR_NSYS_CNTLR as ViStatus,
+        "VI_ERROR_NSYS_CNTLR",
+        "The interface associated with this session is not the system controller.",
+    ),
+    (
+        VI_ERROR_NSUP_OPER as ViStatus,
+        "VI_ERROR_NSUP_OPER",
+        "The given session or object reference does not support this operation.",
+    ),
+    (
+        VI_ERROR_INTR_PENDING as ViStatus,
+        "VI_ERROR_INTR_PENDING",
+        "An interrupt is still pending from a previous call.",
+    ),
+    (
+        VI_ERROR_ASRL_PARITY as ViStatus,
+        "VI_ERROR_ASRL_PARITY",
+        "A parity error occurred during transfer.",
+    ),
+    (
+        VI_ERROR_ASRL_FRAMING as ViStatus,
+        "VI_ERROR_ASRL_FRAMING",
+        "A framing error occurred during transfer.",
+    ),
+    (
+        VI_ERROR_ASRL_OVERRUN as ViStatus,
+        "VI_ERROR_ASRL_OVERRUN",
+        "An overrun error occurred during transfer. A character was not read from the hardware before the next character arrived.",
+    ),
+    (
+        VI_ERROR_TRIG_NMAPPED as ViStatus,
+        "VI_ERROR_TRIG_NMAPPED",
+        "The path from the trigger source to the trigger destination is not currently mapped.",
+    ),
+    (
+        VI_ERROR_NSUP_ALIGN_OFFSET as ViStatus,
+        "VI_ERROR_NSUP_ALIGN_OFFSET",
+        "The specified offset is not properly aligned for the access width of the operation.",
+    ),
+    (
+        VI_ERROR_USER_BUF as ViStatus,
+        "VI_ERROR_USER_BUF",
+        "A specified user buffer is not valid or cannot be accessed for the required size.",
+    ),
+    (
+        VI_ERROR_RSRC_BUSY as ViStatus,
+        "VI_ERROR_RSRC_BUSY",
+        "The resource is valid, but VISA cannot currently access it.",
+    ),
+    (
+        VI_ERROR_NSUP_WIDTH as ViStatus,
+        "VI_ERROR_NSUP_WIDTH",
+        "Specified width is not supported by this hardware.",
+    ),
+    (
+        VI_ERROR_INV_PARAMETER as ViStatus,
+        "VI_ERROR_INV_PARAMETER",
+        "The value of some parameter (which parameter is not known) is invalid.",
+    ),
+    (
+        VI_ERROR_INV_PROT as ViStatus,
+        "VI_ERROR_INV_PROT",
+        "The protocol specified is invalid.",
+    ),
+    (
+        VI_ERROR_INV_SIZE as ViStatus,
+        "VI_ERROR_INV_SIZE",
+        "Invalid size of window specified.",
+    ),
+    (
+        VI_ERROR_WINDOW_MAPPED as ViStatus,
+        "VI_ERROR_WINDOW_MAPPED",
+        "The specified session currently contains a mapped window.",
+    ),
+    (
+        VI_ERROR_NIMPL_OPER as ViStatus,
+        "VI_ERROR_NIMPL_OPER",
+        "The given operation is not implemented.",
+    ),
+    (
+        VI_ERROR_INV_LENGTH as ViStatus,
+        "VI_ERROR_INV_LENGTH",
+        "Invalid length specified.",
+    ),
+    (
+        VI_ERROR_INV_MODE as ViStatus,
+        "VI_ERROR_INV_MODE",
+        "The specified mode is invalid.",
+    ),
+    (
+        VI_ERROR_SESN_NLOCKED as ViStatus,
+        "VI_ERROR_SESN_NLOCKED",
+        "The current session did not have any lock on the resource.",
+    ),
+    (
+        VI_ERROR_MEM_NSHARED as ViStatus,
+        "VI_ERROR_MEM_NSHARED",
+        "The device does not export any memory.",
+    ),
+    (
+        VI_ERROR_LIBRARY_NFOUND as ViStatus,
+        "VI_ERROR_LIBRARY_NFOUND",
+        "A code library required by VISA could not be located or loaded.",
+    ),
+    (
+        VI_ERROR_NSUP_INTR as ViStatus,
+        "VI_ERROR_NSUP_INTR",
+        "The interface cannot generate an interrupt on the requested level or with the requested status ID value.",
+    ),
+    (
+        VI_ERROR_INV_LINE as ViStatus,
+        "VI_ERROR_INV_LINE",
+        "The value specified by the line parameter is invalid.",
+    ),
+    (
+        VI_ERROR_FILE_ACCESS as ViStatus,
+        "VI_ERROR_FILE_ACCESS",
+        "An error occurred while trying to open the specified file. Possible reasons include an invalid path or lack of access rights.",
+    ),
+    (
+        VI_ERROR_FILE_IO as ViStatus,
+        "VI_ERROR_FILE_IO",
+        "An error occurred while performing I/O on the specified file.",
+    ),
+    (
+        VI_ERROR_NSUP_LINE as ViStatus,
+        "VI_ERROR_NSUP_LINE",
+        "One of the specified trigger lines is not supported by this VISA implementation, or the combination of lines is not a valid mapping.",
+    ),
+    (
+        VI_ERROR_NSUP_MECH as ViStatus,
+        "VI_ERROR_NSUP_MECH",
+        "The specified mechanism is not supported for the given event type.",
+    ),
+    (
+        VI_ERROR_INTF_NUM_NCONFIG as ViStatus,
+        "VI_ERROR_INTF_NUM_NCONFIG",
+        "The interface type is valid, but the specified interface number is not configured.",
+    ),
+    (
+        VI_ERROR_CONN_LOST as ViStatus,
+        "VI_ERROR_CONN_LOST",
+        "The connection for the given session has been lost.",
+    ),
+    (
+        VI_ERROR_NPERMISSION as ViStatus,
+        "VI_ERROR_NPERMISSION",
+        "Access to the remote machine is denied.",
+    ),
+    (
+        VI_ERROR_SERVER_CERT as ViStatus,
+        "VI_ERROR_SERVER_CERT",
+        "The server certificate could not be validated.",
+    ),
+    (
+        VI_ERROR_INV_SESSION as ViStatus,
+        "VI_ERROR_INV_SESSION",
+        "The given session or object reference is invalid.",
+    ),
+];
+
+/// Looks up the name and description of `status`.
+pub(super) fn lookup(status: ViStatus) -> Option<(&'static str, &'static str)> {
+    CODES
+        .iter()
+        .find(|(code, _, _)| *code == status)
+        .map(|(_, name, description)| (*name, *description))
+}
@@ -0,0 +1,187 @@
+//! Typed view of `ViStatus`.
+//!
+//! VISA statuses are errors when negative (`VI_ERROR_*`) and completion codes
+//! otherwise: `VI_SUCCESS`, the other `VI_SUCCESS_*` codes and the `VI_WARN_*`
+//! warnings. The safe wrappers return `Result<Completion, VisaError>`, so a
+//! caller that cares about e.g. `VI_SUCCESS_MAX_CNT` can still see it.
+
+mod codes;
+
+use std::error::Error;
+use std::{fmt, io};
+
+use crate::ffi::{ViStatus, VI_ERROR_TMO, VI_SUCCESS};
+
+/// A `ViStatus` split into completion codes and errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    Completion(Completion),
+    Error(VisaError),
+}
+
+impl Status {
+    pub fn from_raw(status: ViStatus) -> Self {
+        if status < VI_SUCCESS as ViStatus {
+            Status::Error(VisaError(status))
+        } else {
+            Status::Completion(Completion(status))
+        }
+    }
+
+    /// The raw status code.
+    pub fn raw(&self) -> ViStatus {
+        match self {
+            Status::Completion(completion) => completion.raw(),
+            Status::Error(error) => error.raw(),
+        }
+    }
+
+    /// The symbolic name from `visa.h`, e.g. `"VI_ERROR_TMO"`, if the code is known.
+    pub fn name(&self) -> Option<&'static str> {
+        codes::lookup(self.raw()).map(|(name, _)| name)
+    }
+
+    /// The description from the VISA specification, if the code is known.
+    pub fn description(&self) -> Option<&'static str> {
+        codes::lookup(self.raw()).map(|(_, description)| description)
+    }
+
+    pub fn into_result(self) -> Result {
+        match self {
+            Status::Completion(completion) => Ok(completion),
+            Status::Error(error) => Err(error),
+        }
+    }
+}
+
+impl From<ViStatus> for Status {
+    fn from(status: ViStatus) -> Self {
+        Status::from_raw(status)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Completion(completion) => completion.fmt(f),
+            Status::Error(error) => error.fmt(f),
+        }
+    }
+}
+
+/// A successful status: `VI_SUCCESS`, another `VI_SUCCESS_*` code, or a
+/// `VI_WARN_*` warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Completion(ViStatus);
+
+impl Completion {
+    /// Plain `VI_SUCCESS`.
+    pub const SUCCESS: Completion = Completion(VI_SUCCESS as ViStatus);
+
+    /// The raw status code.
+    pub fn raw(&self) -> ViStatus {
+        self.0
+    }
+
+    /// Whether this is a `VI_WARN_*` code. Unknown positive codes are treated
+    /// as warnings as well.
+    pub fn is_warning(&self) -> bool {
+        match self.name() {
+            Some(name) => name.starts_with("VI_WARN_"),
+            None => self.0 != VI_SUCCESS as ViStatus,
+        }
+    }
+
+    /// The symbolic name from `visa.h`, e.g. `"VI_SUCCESS_MAX_CNT"`.
+    pub fn name(&self) -> Option<&'static str> {
+        codes::lookup(self.0).map(|(name, _)| name)
+    }
+
+    /// The description from the VISA specification.
+    pub fn description(&self) -> Option<&'static str> {
+        codes::lookup(self.0).map(|(_, description)| description)
+    }
+}
+
+impl fmt::Display for Completion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        describe(f, self.0, "completion code")
+    }
+}
+
+/// A failed VISA call, i.e. a negative `ViStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VisaError(ViStatus);
+
+impl VisaError {
+    /// Wraps a raw status. Returns `None` if `status` is not an error.
+    pub fn from_status(status: ViStatus) -> Option<Self> {
+        match Status::from_raw(status) {
+            Status::Error(error) => Some(error),
+            Status::Completion(_) => None,
+        }
+    }
+
+    /// The raw `VI_ERROR_*` status code.
+    pub fn raw(&self) -> ViStatus {
+        self.0
+    }
+
+    /// The symbolic name from `visa.h`, e.g. `"VI_ERROR_TMO"`.
+    pub fn name(&self) -> Option<&'static str> {
+        codes::lookup(self.0).map(|(name, _)| name)
+    }
+
+    /// The description from the VISA specification.
+    pub fn description(&self) -> Option<&'static str> {
+        codes::lookup(self.0).map(|(_, description)| description)
+    }
+}
+
+impl fmt::Display for VisaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        describe(f, self.0, "VISA error")
+    }
+}
+
+impl Error for VisaError {}
+
+/// `VI_ERROR_TMO` becomes `io::ErrorKind::TimedOut`, anything else
+/// `io::ErrorKind::Other`. The `VisaError` is kept as the inner error.
+impl From<VisaError> for io::Error {
+    fn from(error: VisaError) -> Self {
+        let kind = if error.0 == VI_ERROR_TMO {
+            io::ErrorKind::TimedOut
+        } else {
+            io::ErrorKind::Other
+        };
+        io::Error::new(kind, error)
+    }
+}
+
+/// `VI_ERROR_TMO (0xBFFF0015): Timeout expired before operation completed.`
+fn describe(f: &mut fmt::Formatter<'_>, status: ViStatus, unknown: &str) -> fmt::Result {
+    match codes::lookup(status) {
+        Some((name, description)) => write!(f, "{} ({:#010X}): {}", name, status, description),
+        None => write!(f, "Unknown {} {:#010X}", unknown, status),
+    }
+}
+
+/// Result of the safe wrappers. Calls that return nothing else return the
+/// completion code.
+pub type Result<T = Completion> = std::result::Result<T, VisaError>;
+
+/// Turns a raw status into a `Result`.
+pub(crate) fn check(status: ViStatus) -> Result {
+    Status::from_raw(status).into_result()
+}
+
+/// Builds a completion from one of the `VI_SUCCESS_*` or `VI_WARN_*` constants.
+pub(crate) fn completion(status: u32) -> Completion {
+    Completion(status as ViStatus)
+}
+
+/// Builds an error from one of the (negative) `VI_ERROR_*` constants.
+pub(crate) fn error(status: i32) -> VisaError {
+    VisaError(status as ViStatus)
+}
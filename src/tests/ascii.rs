@@ -0,0 +1,121 @@
+use proptest::prelude::*;
+
+use crate::ascii::{self, Nrf};
+
+#[test]
+fn test_numeric_forms() {
+    assert_eq!(ascii::parse_number("42").unwrap(), 42.0);
+    assert_eq!(ascii::parse_number("-0.5").unwrap(), -0.5);
+    assert_eq!(ascii::parse_number(".5").unwrap(), 0.5);
+    assert_eq!(ascii::parse_number("+2.000000000000000E+04").unwrap(), 2e4);
+    assert_eq!(ascii::parse_number(" 1.5e-3\n").unwrap(), 1.5e-3);
+    assert_eq!(ascii::parse_number("1.0 E 3").unwrap(), 1e3);
+    assert_eq!(ascii::parse_number("#HFF").unwrap(), 255.0);
+    assert_eq!(ascii::parse_number("#q17").unwrap(), 15.0);
+    assert_eq!(ascii::parse_number("#B101").unwrap(), 5.0);
+}
+
+#[test]
+fn test_special_values() {
+    assert_eq!(ascii::parse_number("9.9E37").unwrap(), f64::INFINITY);
+    assert_eq!(ascii::parse_number("+9.90000000E+37").unwrap(), f64::INFINITY);
+    assert_eq!(ascii::parse_number("-9.9E37").unwrap(), f64::NEG_INFINITY);
+    assert!(ascii::parse_number("9.91E37").unwrap().is_nan());
+    assert!(ascii::parse_number("NAN").unwrap().is_nan());
+    assert_eq!(ascii::parse_number("inf").unwrap(), f64::INFINITY);
+    assert_eq!(ascii::parse_number("NINF").unwrap(), f64::NEG_INFINITY);
+}
+
+#[test]
+fn test_suffixes() {
+    assert_eq!(ascii::parse_number("2mV").unwrap(), 2e-3);
+    assert_eq!(ascii::parse_number("20kHz").unwrap(), 20e3);
+    assert_eq!(ascii::parse_number("20 KHz").unwrap(), 20e3);
+    assert_eq!(ascii::parse_number("1.5MHz").unwrap(), 1.5e6);
+    assert_eq!(ascii::parse_number("100n").unwrap(), 100e-9);
+    assert_eq!(ascii::parse_number("3.3V").unwrap(), 3.3);
+    assert_eq!(ascii::parse_number("10 Hz").unwrap(), 10.0);
+    assert_eq!(ascii::parse_number("5EV").unwrap(), 5.0);
+    assert!((ascii::parse_number("1E3µs").unwrap() - 1e-3).abs() < 1e-15);
+}
+
+#[test]
+fn test_suffix_case() {
+    // SI prefixes are case-sensitive.
+    assert_eq!(ascii::parse_number("2mA").unwrap(), 2e-3);
+    assert_eq!(ascii::parse_number("2mv").unwrap(), 2e-3);
+    assert_eq!(ascii::parse_number("2 MOhm").unwrap(), 2e6);
+    assert_eq!(ascii::parse_number("10 mohm").unwrap(), 10e-3);
+    assert_eq!(ascii::parse_number("3 mHz").unwrap(), 3e-3);
+
+    // In upper case, as in IEEE 488.2, `M` is milli and `MA` mega.
+    assert_eq!(ascii::parse_number("2MV").unwrap(), 2e-3);
+    assert_eq!(ascii::parse_number("2MA").unwrap(), 2e-3);
+    assert_eq!(ascii::parse_number("2MAV").unwrap(), 2e6);
+    assert_eq!(ascii::parse_number("1.5MHZ").unwrap(), 1.5e6);
+    assert_eq!(ascii::parse_number("10 MOHM").unwrap(), 10e6);
+    assert_eq!(ascii::parse_number("3 MS").unwrap(), 3e-3);
+    assert_eq!(ascii::parse_number("100N").unwrap(), 100e-9);
+    assert_eq!(ascii::parse_number("4US").unwrap(), 4e-6);
+    assert_eq!(ascii::parse_number("2 GHz").unwrap(), 2e9);
+    assert_eq!(ascii::parse_number("1 EX").unwrap(), 1e18);
+}
+
+#[test]
+fn test_units_are_not_prefixes() {
+    assert_eq!(ascii::parse_number("1F").unwrap(), 1.0);
+    assert_eq!(ascii::parse_number("1PF").unwrap(), 1e-12);
+    assert_eq!(ascii::parse_number("5PCT").unwrap(), 5.0);
+    assert_eq!(ascii::parse_number("-3 DBM").unwrap(), -3.0);
+    assert_eq!(ascii::parse_number("2 Pa").unwrap(), 2.0);
+    assert_eq!(ascii::parse_number("1 ex").unwrap(), 1.0);
+}
+
+#[test]
+fn test_invalid_numbers() {
+    for token in ["", "abc", "-", ".", "1,5", "#H", "#HG", "1.2.3", "5 V2", "E3", "1E", "1 e"] {
+        let error = ascii::parse_number(token).unwrap_err();
+        assert_eq!(error.token(), token);
+    }
+}
+
+#[test]
+fn test_integer_values() {
+    assert_eq!(ascii::parse_values::<i32>("1,-2,+3", ',').unwrap(), [1, -2, 3]);
+    assert_eq!(ascii::parse_values::<u16>("1.0E+02, #HFFFF", ',').unwrap(), [100, 0xFFFF]);
+    assert!(ascii::parse_values::<u8>("256", ',').is_err());
+    assert!(ascii::parse_values::<u8>("-1", ',').is_err());
+    assert!(ascii::parse_values::<i32>("1.5", ',').is_err());
+    assert!(ascii::parse_values::<i32>("9.9E37", ',').is_err());
+}
+
+#[test]
+fn test_lists_and_separators() {
+    assert_eq!(ascii::parse_values::<f64>("1,2,3", ',').unwrap(), [1.0, 2.0, 3.0]);
+    assert_eq!(ascii::parse_values::<f64>(" 1.5;2mV ", ';').unwrap(), [1.5, 2e-3]);
+    assert_eq!(ascii::parse_values::<f32>("", ',').unwrap(), [0f32; 0]);
+    assert!(ascii::parse_values::<f64>("1,,2", ',').is_err());
+}
+
+#[test]
+fn test_nrf_formatting() {
+    assert_eq!(Nrf(20000.0).to_string(), "20000");
+    assert_eq!(Nrf(-3.0).to_string(), "-3");
+    assert_eq!(Nrf(0.5).to_string(), "5.0E-1");
+    assert_eq!(Nrf(1.25e-6).to_string(), "1.25E-6");
+    assert_eq!(Nrf(1e20).to_string(), "1.0E20");
+    assert_eq!(Nrf(f64::NEG_INFINITY).to_string(), "-9.9E37");
+    assert_eq!(Nrf(f64::NAN).to_string(), "9.91E37");
+}
+
+proptest! {
+    #[test]
+    fn test_nrf_round_trip(value in any::<f64>()) {
+        let parsed = ascii::parse_number(&Nrf(value).to_string()).unwrap();
+        if value.is_nan() {
+            prop_assert!(parsed.is_nan());
+        } else {
+            prop_assert_eq!(parsed, value);
+        }
+    }
+}
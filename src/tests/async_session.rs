@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Poll, Wake, Waker};
+
+use crate::async_session::Jobs;
+use crate::backend::mock::{MockBackend, MockValue};
+use crate::ffi::*;
+use crate::{Completion, EventKind, ResourceManager, Status};
+
+const DMM: &str = "GPIB0::5::INSTR";
+
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+const SUCCESS: Status = Status::Completion(Completion::SUCCESS);
+
+#[test]
+fn test_completion_wakes_pending_job() {
+    let jobs = Jobs::default();
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+
+    assert_eq!(jobs.poll(7, &waker), Poll::Pending);
+    jobs.complete(7, SUCCESS, 12);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    assert_eq!(jobs.poll(7, &waker), Poll::Ready((SUCCESS, 12)));
+    assert_eq!(jobs.len(), 0);
+}
+
+#[test]
+fn test_completion_before_poll() {
+    // The handler may run before `viReadAsync` has returned the job id.
+    let jobs = Jobs::default();
+    let timeout = Status::from_raw(VI_ERROR_TMO);
+    jobs.complete(3, timeout, 0);
+    assert_eq!(jobs.poll(3, Waker::noop()), Poll::Ready((timeout, 0)));
+    assert_eq!(jobs.len(), 0);
+}
+
+#[test]
+fn test_abandoned_buffer_outlives_job() {
+    let jobs = Jobs::default();
+    assert_eq!(jobs.poll(1, Waker::noop()), Poll::Pending);
+    jobs.abandon(1, vec![0; 16]);
+    assert_eq!(jobs.len(), 1);
+    jobs.complete(1, Status::from_raw(VI_ERROR_ABORT), 0);
+    assert_eq!(jobs.len(), 0);
+
+    jobs.complete(2, SUCCESS, 4);
+    jobs.abandon(2, vec![0; 16]);
+    assert_eq!(jobs.len(), 0);
+}
+
+#[test]
+fn test_unreadable_completion_fails_job() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, |_: &[u8]| None));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+    let jobs = Arc::new(Jobs::default());
+    let completed = jobs.clone();
+    let _handler = session.on_event(EventKind::IoCompletion, move |event| completed.complete_event(event)).unwrap();
+
+    let job = |id: u32| (VI_ATTR_JOB_ID, MockValue::from(id));
+    let status = (VI_ATTR_STATUS, MockValue::from(VI_SUCCESS as i32));
+    let count = (VI_ATTR_RET_COUNT, MockValue::from(5usize));
+    mock.raise_event(DMM, VI_EVENT_IO_COMPLETION, &[job(1), status.clone(), count]).unwrap();
+    assert_eq!(jobs.poll(1, Waker::noop()), Poll::Ready((SUCCESS, 5)));
+
+    // Without a count or a status the job fails instead of never completing.
+    let nsup = Status::from_raw(VI_ERROR_NSUP_ATTR);
+    mock.raise_event(DMM, VI_EVENT_IO_COMPLETION, &[job(2), status]).unwrap();
+    assert_eq!(jobs.poll(2, Waker::noop()), Poll::Ready((nsup, 0)));
+    mock.raise_event(DMM, VI_EVENT_IO_COMPLETION, &[job(3)]).unwrap();
+    assert_eq!(jobs.poll(3, Waker::noop()), Poll::Ready((nsup, 0)));
+    assert_eq!(jobs.len(), 0);
+}
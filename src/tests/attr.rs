@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use crate::attr::{self, Access, Attribute, Scope, SetAttrValue, ValueType, ALL};
+use crate::backend::mock::{MockBackend, MockValue};
+use crate::backend::VisaBackend;
+use crate::ffi::*;
+
+/// Bit set in the IDs of attributes read into a buffer rather than a number.
+const STRING_BIT: ViAttr = 0x8000_0000;
+
+#[test]
+fn test_string_attributes_have_the_string_bit() {
+    for info in ALL {
+        let buffer = matches!(info.value_type, ValueType::String | ValueType::Bytes);
+        assert_eq!(info.id & STRING_BIT != 0, buffer, "{}", info.name);
+    }
+}
+
+#[test]
+fn test_every_ffi_attribute_has_an_entry() {
+    let types = include_str!(concat!(env!("OUT_DIR"), "/types.rs"));
+    let mut missing = Vec::new();
+    for line in types.lines() {
+        let Some(constant) = line.strip_prefix("pub const VI_ATTR_") else {
+            continue;
+        };
+        let (name, value) = constant.split_once(": u32 = ").unwrap();
+        let id: ViAttr = value.trim_end_matches(';').parse().unwrap();
+        if attr::info(id).is_none() {
+            missing.push(name);
+        }
+    }
+    assert!(missing.is_empty(), "No entry for VI_ATTR_{}", missing.join(", VI_ATTR_"));
+}
+
+#[test]
+fn test_ids_and_names_are_unique() {
+    let mut ids = HashSet::new();
+    let mut names = HashSet::new();
+    for info in ALL {
+        assert!(ids.insert(info.id), "duplicate ID {:#X}", info.id);
+        assert!(names.insert(info.name), "duplicate name {}", info.name);
+        assert!(info.name.starts_with("VI_ATTR_"));
+    }
+}
+
+#[test]
+fn test_marker_types_match_table() {
+    assert_eq!(attr::TmoValue::ID, VI_ATTR_TMO_VALUE as ViAttr);
+    assert_eq!(attr::TmoValue::NAME, "VI_ATTR_TMO_VALUE");
+    assert_eq!(attr::TmoValue::ACCESS, Access::ReadWrite);
+    assert_eq!(attr::RsrcName::ACCESS, Access::ReadOnly);
+    assert_eq!(attr::AsrlBaud::SCOPE, Scope::Global);
+    assert_eq!(attr::AsrlBaud::INTERFACES, &[VI_INTF_ASRL as u16]);
+    assert!(attr::TermChar::INTERFACES.is_empty());
+
+    let info = attr::info(VI_ATTR_TERMCHAR as ViAttr).unwrap();
+    assert_eq!(info.name, "VI_ATTR_TERMCHAR");
+    assert_eq!(info.value_type, ValueType::U8);
+    assert_eq!(info.scope, Scope::Local);
+    assert!(attr::info(0).is_none());
+}
+
+#[test]
+fn test_buffer_len_matches_value_type() {
+    const USB_DEVICE: &str = "USB0::0x1234::0x5678::SN1::INSTR";
+    let mock = MockBackend::new()
+        .with_resource(USB_DEVICE, |_: &[u8]| None)
+        .with_attribute(USB_DEVICE, VI_ATTR_USB_RECV_INTR_SIZE as ViAttr, MockValue::from(6u16));
+    let rm = mock.open_default_rm().unwrap();
+    let vi = mock.open(rm, USB_DEVICE, VI_NULL, 0).unwrap();
+    let buffer_len = |id: u32| attr::buffer_len(&mock, vi, id as ViAttr).unwrap();
+
+    assert_eq!(buffer_len(VI_ATTR_TERMCHAR), 1);
+    assert_eq!(buffer_len(VI_ATTR_TERMCHAR_EN), 2);
+    assert_eq!(buffer_len(VI_ATTR_TMO_VALUE), 4);
+    assert_eq!(buffer_len(VI_ATTR_RSRC_NAME), VI_FIND_BUFLEN as usize);
+    assert_eq!(buffer_len(VI_ATTR_USB_RECV_INTR_DATA), 6);
+    assert_eq!(buffer_len(0), VI_FIND_BUFLEN as usize);
+    assert_eq!(attr::info(VI_ATTR_USB_RECV_INTR_DATA as ViAttr).unwrap().value_type, ValueType::Bytes);
+}
+
+#[test]
+fn test_values_convert_to_attr_state() {
+    assert_eq!(true.to_state(), VI_TRUE as ViAttrState);
+    assert_eq!(false.to_state(), VI_FALSE as ViAttrState);
+    assert_eq!(b'\n'.to_state(), 10);
+    assert_eq!(2000u32.to_state(), 2000);
+    assert_eq!((-1i16).to_state(), ViAttrState::MAX);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_64_bit_values_convert_to_attr_state() {
+    assert_eq!(u64::MAX.to_state(), u64::MAX);
+    assert_eq!(attr::info(VI_ATTR_PXI_MEM_BASE_BAR0_64 as ViAttr).unwrap().value_type, ValueType::U64);
+    assert_eq!(attr::MemBase::ID, VI_ATTR_MEM_BASE as ViAttr);
+    assert_eq!(attr::MemBase::NAME, "VI_ATTR_MEM_BASE_64");
+    assert_eq!(attr::RetCount::ID, VI_ATTR_RET_COUNT as ViAttr);
+}
@@ -0,0 +1,41 @@
+use crate::attr;
+use crate::backend::mock::{MockBackend, MockValue};
+use crate::backend::VisaBackend;
+use crate::ffi::*;
+
+const USB_DEVICE: &str = "USB0::0x1234::0x5678::SN1::INSTR";
+
+#[test]
+fn test_small_attribute_buffers_are_rejected() {
+    let mock = MockBackend::new()
+        .with_resource(USB_DEVICE, |_: &[u8]| None)
+        .with_attribute(USB_DEVICE, VI_ATTR_USB_RECV_INTR_SIZE as ViAttr, MockValue::from(6u16));
+    let rm = mock.open_default_rm().unwrap();
+    let vi = mock.open(rm, USB_DEVICE, VI_NULL, 0).unwrap();
+
+    for (attr, len) in [
+        (VI_ATTR_TMO_VALUE, 4),
+        (VI_ATTR_RSRC_NAME, VI_FIND_BUFLEN),
+        (VI_ATTR_USB_RECV_INTR_DATA, 6),
+        (0, VI_FIND_BUFLEN),
+    ] {
+        let error = attr::check_buffer(&mock, vi, attr as ViAttr, &vec![0; len as usize - 1]).unwrap_err();
+        assert_eq!(error.raw(), VI_ERROR_INV_PARAMETER);
+        attr::check_buffer(&mock, vi, attr as ViAttr, &vec![0; len as usize]).unwrap();
+    }
+}
+
+// `FfiBackend` needs the VISA functions to link, from the library or the
+// runtime loader.
+#[cfg(any(visa_linked, feature = "dynamic-loading"))]
+#[test]
+fn test_ffi_get_attribute_rejects_small_buffers() {
+    use crate::backend::FfiBackend;
+
+    // Checked before VISA is called, so this holds without an installation.
+    for (attr, len) in [(VI_ATTR_TMO_VALUE, 2), (VI_ATTR_RSRC_NAME, 255), (0, 8)] {
+        let mut value = vec![0; len];
+        let error = FfiBackend.get_attribute(0, attr as ViAttr, &mut value).unwrap_err();
+        assert_eq!(error.raw(), VI_ERROR_INV_PARAMETER);
+    }
+}
@@ -0,0 +1,31 @@
+//! Guards the pre-generated bindings against drifting from `visa.h`.
+
+#[cfg(target_pointer_width = "64")]
+const COMMITTED: [(&str, &str); 2] = [
+    ("types_64.rs", include_str!("../ffi/generated/types_64.rs")),
+    ("functions_64.rs", include_str!("../ffi/generated/functions_64.rs")),
+];
+
+#[cfg(target_pointer_width = "32")]
+const COMMITTED: [(&str, &str); 2] = [
+    ("types_32.rs", include_str!("../ffi/generated/types_32.rs")),
+    ("functions_32.rs", include_str!("../ffi/generated/functions_32.rs")),
+];
+
+const GENERATED: [(&str, &str); 2] = [
+    ("types.rs", include_str!(concat!(env!("OUT_DIR"), "/types.rs"))),
+    ("functions.rs", include_str!(concat!(env!("OUT_DIR"), "/functions.rs"))),
+];
+
+#[test]
+fn test_pregenerated_bindings_are_up_to_date() {
+    for ((committed_name, committed), (generated_name, generated)) in COMMITTED.iter().zip(GENERATED.iter()) {
+        assert!(
+            committed == generated,
+            "src/ffi/generated/{} is out of date, copy {}/{} over it",
+            committed_name,
+            env!("OUT_DIR"),
+            generated_name
+        );
+    }
+}
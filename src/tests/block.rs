@@ -0,0 +1,180 @@
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use proptest::prelude::*;
+
+use crate::attr;
+use crate::backend::mock::MockBackend;
+use crate::block::{self, BlockValue, ByteOrder};
+use crate::{ResourceManager, TextOptions};
+
+const SCOPE: &str = "USB0::0x0699::0x0401::C0001::INSTR";
+
+/// A mock instrument answering `CURV?` with `response`.
+fn scope(response: &'static [u8]) -> ResourceManager {
+    let mock = MockBackend::new().with_resource(SCOPE, move |command: &[u8]| {
+        (command == b"CURV?\n").then(|| response.to_vec())
+    });
+    ResourceManager::with_backend(Arc::new(mock)).unwrap()
+}
+
+#[test]
+fn test_definite_length_blocks() {
+    assert_eq!(block::header(0).unwrap(), "#10");
+    assert_eq!(block::header(1200).unwrap(), "#41200");
+    assert_eq!(block::header(1_000_000_000).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    let bytes = block::encode(&[1.5f32, -2.0], ByteOrder::LittleEndian).unwrap();
+    assert_eq!(&bytes[..3], b"#18");
+    assert_eq!(&bytes[3..7], 1.5f32.to_le_bytes());
+    assert_eq!(block::parse::<f32>(&bytes, ByteOrder::LittleEndian).unwrap(), [1.5, -2.0]);
+    assert_ne!(block::parse::<f32>(&bytes, ByteOrder::BigEndian).unwrap(), [1.5, -2.0]);
+}
+
+#[test]
+fn test_prefix_and_terminators() {
+    assert_eq!(block::parse::<i8>(b":CURV #13\x01\x02\xFF\n", ByteOrder::BigEndian).unwrap(), [1, 2, -1]);
+    assert_eq!(block::parse::<u8>(b"#12ab\r\n", ByteOrder::BigEndian).unwrap(), b"ab");
+    assert_eq!(block::parse::<u8>(b"#10\n", ByteOrder::BigEndian).unwrap(), b"");
+    // A `\n` inside the data is data, not a terminator.
+    assert_eq!(block::parse::<u8>(b"#11\n", ByteOrder::BigEndian).unwrap(), b"\n");
+}
+
+#[test]
+fn test_indefinite_length_blocks() {
+    assert_eq!(
+        block::parse::<i16>(b"#0\x00\x01\x00\x02\n", ByteOrder::BigEndian).unwrap(),
+        [1, 2]
+    );
+    assert_eq!(block::parse::<i16>(b"#0\x01\x00", ByteOrder::LittleEndian).unwrap(), [1]);
+}
+
+#[test]
+fn test_malformed_blocks() {
+    for bytes in [
+        &b"1,2,3\n"[..],
+        b"#",
+        b"#A12",
+        b"#2",
+        b"#2x4abcd",
+        b"#14abc",
+        b"#13abc,\n",
+        b"#0abc\n",
+    ] {
+        let error = block::parse::<u16>(bytes, ByteOrder::BigEndian).unwrap_err();
+        assert!(
+            matches!(error.kind(), io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof),
+            "{:?}: {}",
+            String::from_utf8_lossy(bytes),
+            error
+        );
+    }
+}
+
+fn round_trip<T: BlockValue + PartialEq + std::fmt::Debug>(values: &[T], order: ByteOrder) -> Result<(), TestCaseError> {
+    let mut bytes = block::encode(values, order).unwrap();
+    bytes.push(b'\n');
+    prop_assert_eq!(block::parse::<T>(&bytes, order).unwrap(), values);
+    Ok(())
+}
+
+fn byte_order() -> impl Strategy<Value = ByteOrder> {
+    prop_oneof![Just(ByteOrder::BigEndian), Just(ByteOrder::LittleEndian)]
+}
+
+proptest! {
+    #[test]
+    fn test_block_round_trip(
+        bytes in proptest::collection::vec(any::<i8>(), 0..200),
+        words in proptest::collection::vec(any::<i16>(), 0..200),
+        ints in proptest::collection::vec(any::<i32>(), 0..200),
+        doubles in proptest::collection::vec(-1e300..1e300f64, 0..200),
+        order in byte_order(),
+    ) {
+        round_trip(&bytes, order)?;
+        round_trip(&words, order)?;
+        round_trip(&ints, order)?;
+        round_trip(&doubles, order)?;
+    }
+}
+
+#[test]
+fn test_large_block_spans_chunks() {
+    let values: Vec<f64> = (0..50_000).map(|i| i as f64 / 7.0).collect();
+    let bytes = block::encode(&values, ByteOrder::BigEndian).unwrap();
+    assert_eq!(&bytes[..8], b"#6400000");
+    assert_eq!(block::parse::<f64>(&bytes, ByteOrder::BigEndian).unwrap(), values);
+}
+
+#[test]
+fn test_session_reads_blocks() {
+    let rm = scope(b"#13abc\n");
+    let mut session = rm.open(SCOPE).unwrap();
+    assert_eq!(session.query_binary_block::<u8>("CURV?").unwrap(), b"abc");
+
+    session.write_all(b"CURV?\n").unwrap();
+    assert_eq!(session.read_binary_block::<u8>().unwrap(), b"abc");
+
+    session.set_text_options(TextOptions::new()).unwrap();
+    assert_eq!(session.query_binary_block::<u8>("CURV?").unwrap(), b"abc");
+    assert_eq!(session.query_binary_block::<u8>("IDN?").unwrap_err().kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn test_session_block_data_holds_termination() {
+    let rm = scope(b"#16a\nb\nc\n\n");
+    let mut session = rm.open(SCOPE).unwrap();
+    session.set_text_options(TextOptions::new()).unwrap();
+    assert_eq!(session.query_binary_block::<u8>("CURV?").unwrap(), b"a\nb\nc\n");
+    assert!(session.get::<attr::TermCharEn>().unwrap());
+
+    // Data already buffered up to a termination character is continued.
+    let rm = scope(b"CURV\n#14a\nbc\n");
+    let mut session = rm.open(SCOPE).unwrap();
+    session.set_text_options(TextOptions::new()).unwrap();
+    session.write_all(b"CURV?\n").unwrap();
+    let mut prefix = [0u8; 4];
+    io::Read::read_exact(&mut session, &mut prefix).unwrap();
+    assert_eq!(&prefix, b"CURV");
+    assert_eq!(session.read_binary_block::<u8>().unwrap(), b"a\nbc");
+}
+
+#[test]
+fn test_session_rejects_responses_without_block() {
+    let rm = scope(b"NONE\n");
+    let mut session = rm.open(SCOPE).unwrap();
+    let error = session.query_binary_block::<u8>("CURV?").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "response holds no binary block");
+}
+
+#[test]
+fn test_huge_header_is_not_reserved() {
+    // A block within the cap is reserved once, exactly.
+    let values = block::parse::<u16>(b"#14abcd", ByteOrder::BigEndian).unwrap();
+    assert_eq!(values.capacity(), 2);
+    assert_eq!(block::reserved_values::<f64>(8000), 1000);
+
+    // A 999 999 999 byte header reserves a million values at most.
+    assert_eq!(block::reserved_values::<u8>(999_999_999), 1 << 20);
+    assert_eq!(block::reserved_values::<f64>(999_999_992), 1 << 20);
+    let error = block::parse::<u8>(b"#9999999999abc", ByteOrder::BigEndian).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "binary block ended 999999996 bytes early");
+}
+
+#[test]
+fn test_session_fails_on_early_end() {
+    // The mock times out once nothing is queued, so a read past END would
+    // report `TimedOut` rather than the short block.
+    let rm = scope(b"#15abc\n");
+    let mut session = rm.open(SCOPE).unwrap();
+    let error = session.query_binary_block::<u8>("CURV?").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "binary block ended 1 bytes early");
+
+    let rm = scope(b"#6100000abc");
+    let mut session = rm.open(SCOPE).unwrap();
+    let error = session.query_binary_block::<u8>("CURV?").unwrap_err();
+    assert_eq!(error.to_string(), "binary block ended 99997 bytes early");
+}
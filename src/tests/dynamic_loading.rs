@@ -0,0 +1,49 @@
+use crate::ffi::*;
+
+#[test]
+fn test_missing_library_is_reported_as_status() {
+    // Only meaningful on machines without a VISA installation.
+    let Err(error) = library() else {
+        return;
+    };
+    assert!(
+        error.to_string().starts_with("VISA library not found"),
+        "Unexpected load error: {}",
+        error
+    );
+
+    let mut default_rm: ViSession = 0;
+    let status = unsafe { viOpenDefaultRM(&mut default_rm) };
+    assert_eq!(status, VI_ERROR_LIBRARY_NFOUND as ViStatus);
+}
+
+#[test]
+fn test_load_library_reports_every_attempt() {
+    let error = LoadError::NotFound {
+        attempts: vec![
+            ("libvisa.so".into(), "not found".to_string()),
+            ("librsvisa.so".into(), "not found".to_string()),
+        ],
+    };
+    assert_eq!(
+        error.to_string(),
+        "VISA library not found, tried: libvisa.so (not found); librsvisa.so (not found)"
+    );
+}
+
+#[test]
+fn test_every_function_is_resolved() {
+    // Only the formatted I/O functions (variadic or taking a `ViVAList`) are
+    // left out on purpose.
+    let generated = include_str!("../ffi/generated/functions_64.rs");
+    let expected: Vec<_> = generated
+        .split("pub fn ")
+        .skip(1)
+        .filter_map(|declaration| declaration.split('(').next())
+        .filter(|name| !["Printf", "Scanf", "Queryf"].iter().any(|suffix| name.ends_with(suffix)))
+        .collect();
+    let mut missing: Vec<_> = expected.iter().filter(|name| !FUNCTIONS.contains(name)).collect();
+    missing.sort();
+    assert!(missing.is_empty(), "Not resolved at runtime: {:?}", missing);
+    assert_eq!(FUNCTIONS.len(), expected.len());
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::backend::mock::{MockBackend, MockValue};
+use crate::ffi::*;
+use crate::{EventKind, ResourceManager};
+
+const USB_DEVICE: &str = "USB0::0x1234::0x5678::SN1::INSTR";
+
+#[test]
+fn test_event_kind_round_trip() {
+    for raw in [
+        VI_EVENT_SERVICE_REQ,
+        VI_EVENT_IO_COMPLETION,
+        VI_EVENT_TRIG,
+        VI_EVENT_CLEAR,
+        VI_EVENT_USB_INTR,
+        VI_EVENT_EXCEPTION,
+        VI_EVENT_PXI_INTR,
+        VI_EVENT_GPIB_CIC,
+        VI_EVENT_GPIB_TALK,
+        VI_EVENT_GPIB_LISTEN,
+        VI_ALL_ENABLED_EVENTS,
+    ] {
+        let kind = EventKind::from_raw(raw as ViEventType);
+        assert!(!matches!(kind, EventKind::Other(_)), "{:#X}", raw);
+        assert_eq!(kind.raw(), raw as ViEventType);
+    }
+    assert_eq!(EventKind::from_raw(VI_EVENT_SERVICE_REQ as ViEventType), EventKind::ServiceRequest);
+    assert_eq!(EventKind::from_raw(0x1234), EventKind::Other(0x1234));
+    assert_eq!(EventKind::Other(0x1234).raw(), 0x1234);
+}
+
+#[test]
+fn test_usb_interrupt_data() {
+    let mock = Arc::new(MockBackend::new().with_resource(USB_DEVICE, |_: &[u8]| None));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(USB_DEVICE).unwrap();
+    let guard = session.enable_event(EventKind::UsbInterrupt).unwrap();
+
+    let data = vec![0x81, 0x01, 0x00, 0x10];
+    mock.raise_event(
+        USB_DEVICE,
+        VI_EVENT_USB_INTR,
+        &[
+            (VI_ATTR_USB_RECV_INTR_SIZE, MockValue::from(data.len() as u16)),
+            (VI_ATTR_USB_RECV_INTR_DATA, MockValue::from(data.clone())),
+        ],
+    )
+    .unwrap();
+    let event = guard.wait_event(EventKind::UsbInterrupt, Duration::from_millis(10)).unwrap();
+    assert_eq!(event.kind(), EventKind::UsbInterrupt);
+    assert_eq!(event.usb_interrupt_data().unwrap(), data);
+}
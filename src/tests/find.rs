@@ -0,0 +1,46 @@
+use crate::ffi::*;
+use crate::{FilterValue, SearchExpr};
+
+#[test]
+fn test_search_expr_without_filters() {
+    assert_eq!(SearchExpr::instruments().to_string(), "?*INSTR");
+    assert_eq!(SearchExpr::all().to_string(), "?*");
+    assert_eq!(SearchExpr::new("TCPIP?*SOCKET").to_string(), "TCPIP?*SOCKET");
+}
+
+#[test]
+fn test_search_expr_attribute_filters() {
+    let expr = SearchExpr::new("USB?*INSTR").attr_eq("VI_ATTR_MANF_ID", 0x0957u16);
+    assert_eq!(expr.to_string(), "USB?*INSTR{VI_ATTR_MANF_ID==0x0957}");
+
+    let expr = SearchExpr::instruments()
+        .attr_eq("VI_ATTR_MANF_ID", 0x0957u16)
+        .attr_ne("VI_ATTR_MODEL_CODE", 0x5407u16)
+        .attr_eq("VI_ATTR_USB_SERIAL_NUM", "MY59002371")
+        .filter("VI_ATTR_GPIB_PRIMARY_ADDR > 4");
+    assert_eq!(
+        String::from(expr),
+        "?*INSTR{VI_ATTR_MANF_ID==0x0957 && VI_ATTR_MODEL_CODE!=0x5407 && \
+         VI_ATTR_USB_SERIAL_NUM==\"MY59002371\" && VI_ATTR_GPIB_PRIMARY_ADDR > 4}"
+    );
+}
+
+#[test]
+fn test_filter_strings_escape_quotes() {
+    let expr = SearchExpr::instruments().attr_eq("VI_ATTR_MODEL_NAME", "34461A \"Truevolt\"");
+    assert_eq!(expr.to_string(), "?*INSTR{VI_ATTR_MODEL_NAME==\"34461A \"\"Truevolt\"\"\"}");
+
+    for value in [
+        FilterValue::from("say \"hi\""),
+        FilterValue::from("\""),
+        FilterValue::from(""),
+        FilterValue::from(0x0957u16),
+        FilterValue::from(-4),
+    ] {
+        assert_eq!(value.to_string().parse::<FilterValue>().unwrap(), value);
+    }
+    assert_eq!("\"a\"\"b\"".parse::<FilterValue>().unwrap(), FilterValue::from("a\"b"));
+    for text in ["\"a\"b\"", "\"open", "\"", "0xZZ", "ten"] {
+        assert_eq!(text.parse::<FilterValue>().unwrap_err().raw(), VI_ERROR_INV_EXPR, "{:?}", text);
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::backend::mock::MockBackend;
+use crate::event::Event;
+use crate::ffi::*;
+use crate::handler::{trampoline, Callback, EventSender};
+use crate::{EventHandler, EventKind, ResourceManager};
+
+const DMM: &str = "GPIB0::5::INSTR";
+
+/// Registers `closure`, returning its user handle.
+fn callback(closure: impl FnMut(&Event) + Send + 'static) -> usize {
+    Arc::new(Callback::new(Arc::new(MockBackend::new()), Box::new(closure))).register()
+}
+
+fn call(user_handle: usize, event_type: ViEventType) -> ViStatus {
+    unsafe { trampoline(0, event_type, 0, user_handle as ViAddr) }
+}
+
+#[test]
+fn test_trampoline_calls_closure() {
+    let (sender, receiver) = mpsc::channel();
+    let callback = callback(move |event: &Event| sender.send_event(event.kind()));
+
+    assert_eq!(call(callback, VI_EVENT_SERVICE_REQ as ViEventType), VI_SUCCESS as ViStatus);
+    assert_eq!(call(callback, VI_EVENT_TRIG as ViEventType), VI_SUCCESS as ViStatus);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [EventKind::ServiceRequest, EventKind::Trigger]);
+
+    // Calls after unregistering, or with an unknown handle, are ignored.
+    Callback::unregister(callback);
+    assert_eq!(call(callback, VI_EVENT_TRIG as ViEventType), VI_SUCCESS as ViStatus);
+    assert_eq!(call(0, VI_EVENT_TRIG as ViEventType), VI_SUCCESS as ViStatus);
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test]
+fn test_trampoline_catches_panics() {
+    let calls = Arc::new(Mutex::new(0));
+    let counter = calls.clone();
+    let callback = callback(move |_: &Event| {
+        *counter.lock().unwrap() += 1;
+        panic!("handler panicked");
+    });
+
+    // The closure keeps being called after a panic poisoned its mutex.
+    assert_eq!(call(callback, VI_EVENT_SERVICE_REQ as ViEventType), VI_SUCCESS as ViStatus);
+    assert_eq!(call(callback, VI_EVENT_SERVICE_REQ as ViEventType), VI_SUCCESS as ViStatus);
+    assert_eq!(*calls.lock().unwrap(), 2);
+    Callback::unregister(callback);
+}
+
+#[test]
+fn test_drop_waits_for_running_handler() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, |_: &[u8]| None));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    let (started, running) = mpsc::channel();
+    let finished = Arc::new(AtomicBool::new(false));
+    let done = finished.clone();
+    let handler = session
+        .on_event(EventKind::Trigger, move |_| {
+            started.send(()).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            done.store(true, Ordering::SeqCst);
+        })
+        .unwrap();
+
+    let raiser = thread::spawn(move || mock.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap());
+    running.recv().unwrap();
+    drop(handler);
+    assert!(finished.load(Ordering::SeqCst));
+    raiser.join().unwrap();
+}
+
+#[test]
+fn test_drop_while_event_is_raised() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, |_: &[u8]| None));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    // The first handler holds the mock's thread until the second one, which
+    // the same event is about to call, has been dropped.
+    let (started, running) = mpsc::channel();
+    let (resume, paused) = mpsc::channel::<()>();
+    let mut first = session
+        .on_event(EventKind::Trigger, move |_| {
+            started.send(()).unwrap();
+            let _ = paused.recv();
+        })
+        .unwrap();
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    let second = first
+        .on_event(EventKind::Trigger, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+    let raiser = thread::spawn(move || mock.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap());
+    running.recv().unwrap();
+    drop(second);
+    resume.send(()).unwrap();
+    raiser.join().unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_drop_from_own_handler() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, |_: &[u8]| None));
+    // Leaked so the handler can move into its own closure.
+    let rm = Box::leak(Box::new(ResourceManager::with_backend(mock.clone()).unwrap()));
+    let session = Box::leak(Box::new(rm.open(DMM).unwrap()));
+
+    let slot: Arc<Mutex<Option<EventHandler<'static, 'static>>>> = Arc::default();
+    let calls = Arc::new(AtomicU32::new(0));
+    let (owner, counter) = (slot.clone(), calls.clone());
+    let handler = session
+        .on_event(EventKind::Trigger, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            drop(owner.lock().unwrap().take());
+        })
+        .unwrap();
+    *slot.lock().unwrap() = Some(handler);
+
+    mock.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap();
+    assert!(slot.lock().unwrap().is_none());
+    mock.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_event_raised_by_own_handler_is_dropped() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, |_: &[u8]| None));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    let calls = Arc::new(AtomicU32::new(0));
+    let (counter, raiser) = (calls.clone(), mock.clone());
+    let _handler = session
+        .on_event(EventKind::Trigger, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            raiser.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap();
+        })
+        .unwrap();
+    mock.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_sync_sender_does_not_block() {
+    let (sender, receiver) = mpsc::sync_channel(1);
+    sender.send_event(EventKind::ServiceRequest);
+    sender.send_event(EventKind::Trigger);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [EventKind::ServiceRequest]);
+
+    drop(receiver);
+    sender.send_event(EventKind::Clear);
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+fn test_crossbeam_sender() {
+    let (sender, receiver) = crossbeam_channel::bounded(1);
+    sender.send_event(EventKind::Clear);
+    sender.send_event(EventKind::Trigger);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [EventKind::Clear]);
+}
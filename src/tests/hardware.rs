@@ -0,0 +1,1176 @@
+use crate::ffi::*;
+const DEVICE_ADDRESS: &'static [u8; 43] = b"USB0::0x0957::0x5407::MY59002371::0::INSTR\0";
+const DEVICE_ADDRESS_PTR: *const u8 = DEVICE_ADDRESS.as_ptr();
+
+unsafe fn setup(method_under_test: &str) -> (ViSession, ViSession, ViStatus) {
+    print_test_header(method_under_test);
+    let mut default_rm: ViSession = 0;
+
+    assert_eq!( 
+        viOpenDefaultRM(&mut default_rm),
+        ViStatus::try_from(VI_SUCCESS).unwrap(),
+        "Failed to open default resource"
+    );
+
+    println!("Resource Manager ID is {}", default_rm);
+    // Open a resource
+    let mut session: ViSession = 0;
+    let status_open = viOpen(
+        default_rm,
+        DEVICE_ADDRESS_PTR as *const i8,
+        VI_NULL,
+        VI_NULL,
+        &mut session,
+    );
+
+    println!("Session ID is {}", session);
+
+    (default_rm, session, status_open)
+}
+
+fn print_test_header(method_under_test: &str) {
+    println!("\n=================================================\n");
+    println!("\n\tMethod under test is {}\n", method_under_test);
+    println!("\n=================================================\n");
+}
+
+fn print_test_footer() {
+    println!("\n=================================================\n");
+}
+
+unsafe fn teardown(default_rm: ViSession, session: ViSession) {
+    println!("Closing Session ID is {}", session);
+    println!("Closing Resource Manager ID {}", default_rm);
+    let status_close_session = viClose(session);
+    viClose(session);
+    assert_eq!(
+      status_close_session as i32, // Cast to i32
+      ViStatus::try_from(VI_SUCCESS).unwrap(),
+      "Failed to close resource manager"
+    );
+    let status_close_rm = viClose(default_rm);
+    assert_eq!(
+      status_close_rm as i32, // Cast to i32
+      ViStatus::try_from(VI_SUCCESS).unwrap(),
+      "Failed to close resource manager"
+    );
+    print_test_footer();
+}
+#[test]
+fn test_visa_open_default_rm() {
+    println!("test_visa_open_default_rm");
+
+    let mut default_rm: ViSession = 0;
+
+    unsafe {
+        let status = viOpenDefaultRM(&mut default_rm);
+        assert!(status >= 0, "Failed to open default resource manager");
+
+        // Close the resource manager
+        let status_rm_close = viClose(default_rm);
+        assert!(status_rm_close >= 0, "Failed to close resource manager");
+    }
+}
+
+#[test]
+fn test_visa_open_close() {
+    println!("test_visa_open_close");
+
+    let mut default_rm: ViSession = 0;
+    let mut session: ViSession = 0;
+
+    unsafe {
+        // Open Resource Manager
+        let status_rm = viOpenDefaultRM(&mut default_rm);
+        assert!(status_rm >= 0, "Failed to open default resource manager");
+
+        // Open a TCP/IP resource
+        let resource_name = DEVICE_ADDRESS;
+        let status_open = viOpen(
+            default_rm,
+            resource_name.as_ptr() as *const i8,
+            VI_NULL,
+            VI_NULL,
+            &mut session,
+        );
+        assert!(status_open >= 0, "Failed to open session");
+
+        // Close the session
+        let status_close = viClose(session);
+        assert!(status_close >= 0, "Failed to close session");
+
+        // Close the resource manager
+        let status_rm_close = viClose(default_rm);
+        assert!(status_rm_close >= 0, "Failed to close resource manager");
+    }
+}
+
+#[test]
+fn test_resource_manager_open_close() {
+    print_test_header("test_resource_manager_open_close");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let session = rm.open(resource).expect("Failed to open session");
+    println!("Session ID is {}", session.as_raw());
+
+    session.close().expect("Failed to close session");
+    rm.close().expect("Failed to close resource manager");
+    print_test_footer();
+}
+
+#[test]
+fn test_session_attributes() {
+    print_test_header("test_session_attributes");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let session = rm.open(resource).expect("Failed to open session");
+
+    session.set::<crate::attr::TmoValue>(2500).expect("Failed to set timeout");
+    assert_eq!(session.get::<crate::attr::TmoValue>().unwrap(), 2500);
+    session.set::<crate::attr::TermChar>(b'\n').expect("Failed to set termination character");
+    assert_eq!(session.get::<crate::attr::TermChar>().unwrap(), b'\n');
+    assert_eq!(session.get::<crate::attr::RsrcName>().unwrap(), resource);
+    println!("Model name is {}", session.get::<crate::attr::ModelName>().unwrap());
+    print_test_footer();
+}
+
+#[test]
+fn test_find_all_resources() {
+    print_test_header("test_find_all_resources");
+
+    unsafe {
+        // Open the default resource manager
+        let mut default_rm: ViSession = 0;
+        let status_rm = viOpenDefaultRM(&mut default_rm);
+        assert_eq!(
+            status_rm as i32,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to open default resource manager"
+        );
+
+        // Variables for viFindRsrc
+        let mut find_list: ViFindList = 0;
+        let mut return_count: ViUInt32 = 0;
+        let mut resource_name: [ViChar; 256] = [0; 256]; // Buffer to hold the resource name
+
+        // Search for all resources
+        let search_expression = b"?*INSTR\0"; // Wildcard for all instruments
+        let status_find = viFindRsrc(
+            default_rm,
+            search_expression.as_ptr() as *const i8,
+            &mut find_list,
+            &mut return_count,
+            resource_name.as_mut_ptr(),
+        );
+
+        assert_eq!(
+            status_find as i32,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to find resources with viFindRsrc"
+        );
+
+        // Print the first found resource
+        let first_resource = std::ffi::CStr::from_ptr(resource_name.as_ptr())
+            .to_string_lossy()
+            .to_string();
+
+        println!("Found resource: {}", first_resource);
+
+        // Iterate through additional resources (if any)
+        for _ in 1..return_count {
+            let status_next = viFindNext(find_list, resource_name.as_mut_ptr());
+            assert_eq!(
+                status_next as i32, // Cast `status_next` to i32
+                ViStatus::try_from(VI_SUCCESS).unwrap(),
+                "Failed to find next resource with viFindNext"
+            );
+
+            let resource_name_str = std::ffi::CStr::from_ptr(resource_name.as_ptr())
+                .to_string_lossy()
+                .to_string();
+            println!("Found resource: {}", resource_name_str);
+        }
+
+        // Clean up
+        let status_close_list = viClose(find_list);
+        assert_eq!(
+            status_close_list as i32, // Cast to i32
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to close find list"
+        );
+
+        let status_close_rm = viClose(default_rm);
+        assert_eq!(
+            status_close_rm as i32, // Cast to i32
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to close resource manager"
+        );
+    }
+    print_test_footer();
+}
+
+#[test]
+fn test_resource_manager_find() {
+    print_test_header("test_resource_manager_find");
+
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    for resource in rm.find(crate::SearchExpr::instruments()) {
+        println!("Found resource: {}", resource.expect("Failed to find next resource"));
+    }
+    assert_eq!(rm.find("NOSUCHINTF?*").count(), 0, "No match should be an empty search");
+    print_test_footer();
+}
+
+#[test]
+fn test_vi_find_next() {
+    println!("test_vi_find_next");
+    unsafe {
+        let mut default_rm: ViSession = 0;
+        assert_eq!(
+            viOpenDefaultRM(&mut default_rm),
+            ViStatus::try_from(VI_SUCCESS).unwrap()
+        );
+
+        let mut find_list: ViFindList = 0;
+        let mut return_count: ViUInt32 = 0;
+        let mut resource_name: [ViChar; 256] = [0; 256];
+
+        // Find resources
+        let status_find = viFindRsrc(
+            default_rm,
+            b"?*INSTR\0".as_ptr() as *const i8,
+            &mut find_list,
+            &mut return_count,
+            resource_name.as_mut_ptr(),
+        );
+        assert_eq!(status_find, ViStatus::try_from(VI_SUCCESS).unwrap());
+
+        // Find next resource
+        let status_next = viFindNext(find_list, resource_name.as_mut_ptr());
+        assert_eq!(
+            status_next,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to find next resource"
+        );
+
+        // Print the resource name
+        let resource = std::ffi::CStr::from_ptr(resource_name.as_ptr());
+        println!("Next resource: {}", resource.to_string_lossy());
+
+        // Cleanup
+        viClose(default_rm);
+    }
+}
+
+#[test]
+fn test_vi_parse_rsrc() {
+    println!("test_vi_parse_rsrc");
+    unsafe {
+        let mut default_rm: ViSession = 0;
+        assert_eq!(
+            viOpenDefaultRM(&mut default_rm),
+            ViStatus::try_from(VI_SUCCESS).unwrap()
+        );
+
+        let mut interface_type: ViUInt16 = 0;
+        let mut interface_number: ViUInt16 = 0;
+
+        let status = viParseRsrc(
+            default_rm,
+            DEVICE_ADDRESS_PTR as *const i8,
+            &mut interface_type,
+            &mut interface_number,
+        );
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to parse resource"
+        );
+
+        println!(
+            "Parsed resource: Interface type = {}, Interface number = {}",
+            interface_type, interface_number
+        );
+
+        // Cleanup
+        viClose(default_rm);
+    }
+}
+
+#[test]
+fn test_vi_parse_rsrc_ex() {
+    println!("test_vi_parse_rsrc_ex");
+    unsafe {
+        let mut default_rm: ViSession = 0;
+        assert_eq!(
+            viOpenDefaultRM(&mut default_rm),
+            ViStatus::try_from(VI_SUCCESS).unwrap()
+        );
+
+        let mut interface_type: ViUInt16 = 0;
+        let mut interface_number: ViUInt16 = 0;
+        let mut resource_class: [ViChar; 256] = [0; 256];
+        let mut expanded_resource: [ViChar; 256] = [0; 256];
+        let mut alias_if_exists: [ViChar; 256] = [0; 256];
+
+        let status = viParseRsrcEx(
+            default_rm,
+            DEVICE_ADDRESS_PTR as *const i8,
+            &mut interface_type,
+            &mut interface_number,
+            resource_class.as_mut_ptr(),
+            expanded_resource.as_mut_ptr(),
+            alias_if_exists.as_mut_ptr(),
+        );
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to parse resource (extended)"
+        );
+
+        println!(
+    "Parsed resource (extended): Interface type = {}, Interface number = {}, Resource class = {}, Expanded resource = {}",
+    interface_type,
+    interface_number,
+    std::ffi::CStr::from_ptr(resource_class.as_ptr()).to_string_lossy(),
+    std::ffi::CStr::from_ptr(expanded_resource.as_ptr()).to_string_lossy(),
+  );
+
+        // Cleanup
+        viClose(default_rm);
+    }
+}
+
+#[test]
+fn test_vi_open() {
+    println!("test_vi_open");
+    unsafe {
+        let mut default_rm: ViSession = 0;
+        assert_eq!(
+            viOpenDefaultRM(&mut default_rm),
+            ViStatus::try_from(VI_SUCCESS).unwrap()
+        );
+
+        let mut session: ViSession = 0;
+        let status = viOpen(
+            default_rm,
+            DEVICE_ADDRESS_PTR as *const i8,
+            VI_NULL,
+            VI_NULL,
+            &mut session,
+        );
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to open resource"
+        );
+
+        println!("Resource opened successfully: Session = {}", session);
+
+        // Cleanup
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_close() {
+    unsafe {
+        let (default_rm, session, status_open) = setup("test_vi_close");
+        print_status_description(default_rm, status_open);
+        assert_eq!(
+            status_open,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to open the session"
+        );
+
+        // Close the session
+        let status_close = viClose(session);
+        assert_eq!(
+            status_close,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to close session"
+        );
+
+        println!("Session closed successfully");
+
+        // Cleanup
+        viClose(default_rm);
+    }
+}
+
+#[test]
+fn test_vi_set_attribute() {
+    unsafe {
+        let (default_rm, session, status_open) = setup("test_vi_set_attribute");
+        assert_eq!(
+            status_open,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to open session"
+        );
+
+        let status = viSetAttribute(session, VI_ATTR_TMO_VALUE, 5000); // Set timeout to 5000ms
+        print_status_description(default_rm, status);
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to set attribute"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_get_attribute() {
+    unsafe {
+        let (default_rm, session, _) = setup("test_vi_get_attribute");
+
+        let status = viSetAttribute(session, VI_ATTR_TMO_VALUE, 5000); // Set timeout to 5000ms
+        print_status_description(default_rm, status);
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to set attribute"
+        );
+
+        let mut timeout: ViUInt32 = 0;
+
+        let status = viGetAttribute(
+            session,
+            VI_ATTR_TMO_VALUE,
+            &mut timeout as *mut _ as *mut std::os::raw::c_void,
+        );
+        print_status_description(default_rm, status);
+
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to get attribute"
+        );
+        assert_eq!(timeout, 5000, "Expected default timeout of 2000ms");
+
+        println!("Session #: {}", session);
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_status_desc() {
+    unsafe {
+        let mut default_rm: ViSession = 0;
+        assert_eq!(
+            viOpenDefaultRM(&mut default_rm),
+            ViStatus::try_from(VI_SUCCESS).unwrap()
+        );
+
+        let mut desc: [ViChar; 256] = [0; 256];
+        let status = viStatusDesc(
+            default_rm,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            desc.as_mut_ptr(),
+        );
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to get status description"
+        );
+
+        let description = std::ffi::CStr::from_ptr(desc.as_ptr())
+            .to_string_lossy()
+            .to_string();
+        assert_eq!(description, "Operation completed successfully.");
+
+        viClose(default_rm);
+    }
+}
+
+#[test]
+fn test_vi_terminate() {
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_terminate");
+
+        let status = viTerminate(session, VI_NULL as ViUInt16, VI_NULL);
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to terminate operation"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_lock() {
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_lock");
+
+        let lock_status = viLock(
+            session,
+            VI_EXCLUSIVE_LOCK,
+            0,
+            VI_NULL as ViConstKeyId,
+            VI_NULL as *mut ViChar,
+        );
+        print_status_description(default_rm, lock_status);
+        assert_eq!(
+            lock_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to acquire lock"
+        );
+
+        let unlock_status = viUnlock(session);
+        print_status_description(default_rm, unlock_status);
+        assert_eq!(
+            unlock_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to release lock"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+#[test]
+fn test_vi_enable_event() {
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_enable_event");
+
+        let enable_event_status = viEnableEvent(
+            session,
+            VI_EVENT_IO_COMPLETION,
+            VI_QUEUE.try_into().unwrap(),
+            VI_NULL,
+        );
+        assert_eq!(
+            enable_event_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to enable event"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_disable_event() {
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_disable_event");
+
+        let enable_event_status = viEnableEvent(
+            session,
+            VI_EVENT_IO_COMPLETION,
+            VI_QUEUE.try_into().unwrap(),
+            VI_NULL,
+        );
+        assert_eq!(
+            enable_event_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to enable event"
+        );
+
+        let disable_event_status =
+            viDisableEvent(session, VI_EVENT_IO_COMPLETION, VI_QUEUE as ViUInt16);
+        assert_eq!(
+            disable_event_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to disable event"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_discard_events() {
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_discard_events");
+
+        let status = viDiscardEvents(session, VI_ALL_ENABLED_EVENTS, VI_ALL_MECH as ViUInt16);
+        print_status_description(default_rm, status);
+        assert!(
+            status == ViStatus::try_from(VI_SUCCESS).unwrap()
+                || status == ViStatus::try_from(VI_SUCCESS_QUEUE_EMPTY).unwrap(),
+            "Failed to discard events"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_wait_on_event() {
+    unsafe {
+        let (default_rm, session, _) = setup("test_vi_wait_on_event");
+
+        let mut event_type: ViEventType = 0;
+        let mut event_context: ViEvent = 0;
+
+        let status = viWaitOnEvent(
+            session,
+            VI_EVENT_IO_COMPLETION,
+            5000,
+            &mut event_type,
+            &mut event_context,
+        );
+        print_status_description(default_rm, status);
+        assert_ne!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Unexpected success waiting for event"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_install_handler() {
+    unsafe extern "system" fn handler(
+        _vi: ViSession,
+        _event_type: ViEventType,
+        _event_context: ViEvent,
+        _user_handle: ViAddr,
+    ) -> i32 {
+        println!("Event handler invoked");
+        ViStatus::try_from(VI_SUCCESS).unwrap()
+    }
+
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_install_handler");
+
+        let status = viInstallHandler(
+            session,
+            VI_EVENT_IO_COMPLETION,
+            Some(handler), // Correctly matches expected function signature
+            VI_NULL as *mut std::os::raw::c_void,
+        );
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to install handler"
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_read() {
+    unsafe {
+      let (default_rm, session, _) = setup("test_vi_read");
+
+        let scpi_command = b"*IDN?\n";
+        let mut write_count: ViUInt32 = 0;
+        let write_status = viWrite(
+            session,
+            scpi_command.as_ptr() as *const ViByte,
+            scpi_command.len() as ViUInt32,
+            &mut write_count,
+        );
+        assert_eq!(
+            write_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to write data"
+        );
+
+        let mut buffer: [u8; 256] = [0; 256];
+        let mut read_count: ViUInt32 = 0;
+        let read_status = viRead(
+            session,
+            buffer.as_mut_ptr() as *mut ViByte,
+            buffer.len() as ViUInt32,
+            &mut read_count,
+        );
+        print_status_description(default_rm, read_status);
+        assert!(
+            read_status == ViStatus::try_from(VI_SUCCESS).unwrap()
+                || read_status == ViStatus::try_from(VI_SUCCESS_MAX_CNT).unwrap(),
+            "Failed to read data"
+        );
+
+        println!(
+            "On SCPI command written: {}Read {} bytes: {:?}.\nText: {}",
+            String::from_utf8_lossy(scpi_command),
+            read_count,
+            &buffer[..read_count as usize],
+            String::from_utf8_lossy(&buffer[..read_count as usize])
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_session_io() {
+    use std::io::{BufRead, Write};
+
+    print_test_header("test_session_io");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+
+    session.write_all(b"*IDN?\n").expect("Failed to write data");
+    let mut response = Vec::new();
+    session.read_until_end(&mut response).expect("Failed to read data");
+    println!("Text: {}", String::from_utf8_lossy(&response));
+    assert_eq!(response.last(), Some(&b'\n'));
+
+    session.set::<crate::attr::TermChar>(b'\n').unwrap();
+    session.set::<crate::attr::TermCharEn>(true).unwrap();
+    session.write_all(b"*IDN?\n").expect("Failed to write data");
+    let mut line = String::new();
+    session.read_line(&mut line).expect("Failed to read line");
+    assert_eq!(line.as_bytes(), response.as_slice());
+
+    session.set::<crate::attr::TmoValue>(100).unwrap();
+    let error = session.read_until_end(&mut response).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+    print_test_footer();
+}
+
+#[test]
+fn test_session_query() {
+    print_test_header("test_session_query");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+    session
+        .set_text_options(crate::TextOptions::new().trim(true))
+        .expect("Failed to set text options");
+
+    let idn = session.query("*IDN?").expect("Failed to query identification");
+    println!("Identification: {}", idn);
+    assert!(!idn.ends_with('\n'));
+    session.write_str("SYSTem:ERRor?").expect("Failed to write data");
+    println!("Error queue: {}", session.read_string().expect("Failed to read data"));
+    print_test_footer();
+}
+
+#[test]
+fn test_session_binary_block() {
+    print_test_header("test_session_binary_block");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+
+    // Loads a short arbitrary waveform and checks the generator accepted it.
+    session
+        .write_binary_block("DATA:ARB:DAC test,", &[0i16, 1000, -1000, 0])
+        .expect("Failed to write binary block");
+    let error = session.query("SYSTem:ERRor?").expect("Failed to query");
+    assert!(error.starts_with("+0"), "Instrument reported {}", error);
+    print_test_footer();
+}
+
+#[test]
+fn test_session_ascii_values() {
+    print_test_header("test_session_ascii_values");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+
+    session.write_str("FREQuency 20 kHz").expect("Failed to write data");
+    let frequency = session.query_ascii_values::<f64>("FREQuency?").expect("Failed to query");
+    assert_eq!(frequency, [20e3]);
+    print_test_footer();
+}
+
+#[test]
+fn test_session_error_queue() {
+    use crate::scpi::ScpiErrors;
+    use crate::{ErrorCheck, ErrorQueueOptions, ErrorReport};
+
+    print_test_header("test_session_error_queue");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+    session.check_errors().expect("Failed to clear error queue");
+
+    session.set_error_queue_options(ErrorQueueOptions::new().check(ErrorCheck::PerCommand));
+    session
+        .write_str("FUNCtion SQUare;:FREQuency 20 kHz;:VOLTage 2 V")
+        .expect("Valid command reported an error");
+    let error = session.write_str("NOSUCH:COMMand").unwrap_err();
+    let errors = error.get_ref().unwrap().downcast_ref::<ScpiErrors>().unwrap();
+    assert_eq!(errors.0[0].code(), -113);
+
+    session.set_error_queue_options(
+        ErrorQueueOptions::new()
+            .check(ErrorCheck::Batched)
+            .report(ErrorReport::Collect),
+    );
+    session.write_str("NOSUCH:COMMand").unwrap();
+    session.query("*IDN?").expect("Failed to query");
+    assert_eq!(session.take_scpi_warnings().len(), 1);
+    print_test_footer();
+}
+
+#[test]
+fn test_session_status_model() {
+    use crate::{StandardEvent, StatusByte, StatusWait};
+    use std::time::Duration;
+
+    print_test_header("test_session_status_model");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+
+    session.clear_status().expect("Failed to clear status");
+    session.enable_standard_events(StandardEvent::OPC | StandardEvent::ERRORS).unwrap();
+    assert_eq!(session.standard_event_enable().unwrap(), StandardEvent::OPC | StandardEvent::ERRORS);
+    session.enable_service_requests(StatusByte::ESB).unwrap();
+
+    session.write_str("*OPC").unwrap();
+    let status = session
+        .wait_for_status(StatusByte::ESB, StatusWait::Poll(Duration::from_millis(10)), Duration::from_secs(2))
+        .expect("Operation did not complete");
+    println!("Status byte is {:?}", status);
+    assert!(session.read_standard_events().unwrap().contains(StandardEvent::OPC));
+
+    session.write_str("*OPC").unwrap();
+    session
+        .wait_for_status(StatusByte::ESB, StatusWait::ServiceRequest, Duration::from_secs(2))
+        .expect("No service request");
+    session.clear_status().unwrap();
+    print_test_footer();
+}
+
+#[test]
+fn test_session_wait_opc() {
+    use crate::OpcStrategy;
+    use std::time::Duration;
+
+    print_test_header("test_session_wait_opc");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+    let timeout = session.get::<crate::attr::TmoValue>().unwrap();
+
+    for strategy in [
+        OpcStrategy::Query,
+        OpcStrategy::ServiceRequest,
+        OpcStrategy::Poll(Duration::from_millis(10)),
+    ] {
+        session.set_opc_strategy(strategy);
+        session.write_str("*RST").unwrap();
+        session
+            .wait_opc(Duration::from_secs(5))
+            .unwrap_or_else(|e| panic!("{:?} failed: {}", strategy, e));
+    }
+    assert_eq!(session.get::<crate::attr::TmoValue>().unwrap(), timeout);
+    print_test_footer();
+}
+
+#[test]
+fn test_session_events() {
+    use crate::{EventKind, StandardEvent, StatusByte};
+    use std::time::Duration;
+
+    print_test_header("test_session_events");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+
+    let mut guard = session.enable_event(EventKind::ServiceRequest).expect("Failed to enable event");
+    guard.clear_status().unwrap();
+    guard.enable_standard_events(StandardEvent::OPC).unwrap();
+    guard.enable_service_requests(StatusByte::ESB).unwrap();
+    guard.write_str("*OPC").unwrap();
+
+    let event = guard
+        .wait_event(EventKind::AllEnabled, Duration::from_secs(2))
+        .expect("No service request");
+    assert_eq!(event.kind(), EventKind::ServiceRequest);
+    assert!(guard.read_stb().unwrap().contains(StatusByte::RQS));
+    drop(event);
+
+    guard.disable().expect("Failed to disable event");
+    session.clear_status().unwrap();
+    print_test_footer();
+}
+
+#[ignore]
+#[test]
+fn test_session_event_handler() {
+    use crate::{EventKind, StandardEvent, StatusByte};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    print_test_header("test_session_event_handler");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+
+    let (sender, receiver) = mpsc::channel();
+    let mut handler = session
+        .forward_events(EventKind::ServiceRequest, sender)
+        .expect("Failed to install handler");
+    handler.clear_status().unwrap();
+    handler.enable_standard_events(StandardEvent::OPC).unwrap();
+    handler.enable_service_requests(StatusByte::ESB).unwrap();
+    handler.write_str("*OPC").unwrap();
+
+    let kind = receiver.recv_timeout(Duration::from_secs(2)).expect("No service request");
+    assert_eq!(kind, EventKind::ServiceRequest);
+    drop(handler);
+
+    session.clear_status().unwrap();
+    print_test_footer();
+}
+
+#[ignore]
+#[test]
+fn test_async_session() {
+    use crate::AsyncSession;
+    use std::future::Future;
+    use std::task::{Context, Waker};
+
+    print_test_header("test_async_session");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let session = AsyncSession::new(rm.open(resource).expect("Failed to open session"))
+        .expect("Failed to install the I/O completion handler");
+
+    let identity = futures::executor::block_on(async {
+        let written = session.write(b"*IDN?\n".to_vec()).await?;
+        assert_eq!(written, 6);
+        session.read(vec![0; 256]).await
+    })
+    .expect("Async query failed");
+    println!("Device identity: {}", String::from_utf8_lossy(&identity).trim_end());
+    assert!(!identity.is_empty());
+
+    // A read started with nothing to read and then dropped is terminated.
+    let mut pending = Box::pin(session.read(vec![0; 256]));
+    let _ = pending.as_mut().poll(&mut Context::from_waker(Waker::noop()));
+    drop(pending);
+
+    print_test_footer();
+}
+
+#[ignore]
+#[test]
+fn test_session_locks() {
+    use std::time::Duration;
+
+    print_test_header("test_session_locks");
+
+    let resource = std::str::from_utf8(&DEVICE_ADDRESS[..DEVICE_ADDRESS.len() - 1]).unwrap();
+    let rm = crate::ResourceManager::new().expect("Failed to open default resource manager");
+    let mut session = rm.open(resource).expect("Failed to open session");
+    let mut other = rm.open(resource).expect("Failed to open second session");
+
+    {
+        let mut guard = session.lock_exclusive(Duration::ZERO).expect("Failed to lock");
+        assert!(!guard.is_nested());
+        let nested = guard.lock_exclusive(Duration::ZERO).expect("Failed to nest lock");
+        assert!(nested.is_nested());
+        assert_eq!(nested.exclusive_locks(), 2);
+        nested.unlock().expect("Failed to unlock");
+
+        let error = other.lock_exclusive(Duration::from_millis(100)).unwrap_err();
+        assert_eq!(error.raw(), VI_ERROR_TMO);
+    }
+    assert_eq!(session.exclusive_locks(), 0);
+
+    let guard = session.lock_shared(Some("station"), Duration::ZERO).expect("Failed to lock shared");
+    assert_eq!(guard.key(), "station");
+    let shared = other.lock_shared(Some(guard.key()), Duration::ZERO).expect("Failed to share lock");
+    drop(shared);
+    drop(guard);
+
+    let identity = session
+        .with_lock(Duration::from_secs(1), |session| session.query("*IDN?"))
+        .expect("Failed to lock")
+        .expect("Failed to query");
+    println!("Device identity: {}", identity);
+
+    print_test_footer();
+}
+
+#[ignore]
+#[test]
+fn test_vi_read_async() {
+    unsafe {
+        let (default_rm, session, _) = setup("test_vi_read_async");
+
+        let scpi_command = b"*IDN?\n";
+        let mut write_count: ViUInt32 = 0;
+        let write_status = viWrite(
+            session,
+            scpi_command.as_ptr() as *const ViByte,
+            scpi_command.len() as ViUInt32,
+            &mut write_count,
+        );
+        assert_eq!(
+            write_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to write data"
+        );
+
+        let enable_status = viEnableEvent(session, VI_EVENT_IO_COMPLETION, VI_QUEUE as ViUInt16, VI_NULL);
+        assert!(enable_status >= 0, "Failed to enable I/O completion events");
+
+        let mut buffer: [u8; 256] = [0; 256];
+        let mut job_id: ViJobId = 0;
+        let async_read_status = viReadAsync(
+            session,
+            buffer.as_mut_ptr() as *mut ViByte,
+            buffer.len() as ViUInt32,
+            &mut job_id,
+        );
+        print_status_description(default_rm, async_read_status);
+        assert_eq!(
+            async_read_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to start async read"
+        );
+
+        println!("Async read job started: Job ID = {}", job_id);
+
+        // The buffer must outlive the job, so wait for it to complete.
+        let mut event_type: ViEventType = 0;
+        let mut event: ViEvent = 0;
+        let wait_status = viWaitOnEvent(session, VI_EVENT_IO_COMPLETION, 2000, &mut event_type, &mut event);
+        assert!(wait_status >= 0, "Async read did not complete");
+        viClose(event);
+        viDisableEvent(session, VI_EVENT_IO_COMPLETION, VI_QUEUE as ViUInt16);
+
+        teardown(default_rm, session);
+    }
+}
+
+#[ignore]
+#[test]
+fn test_vi_read_to_file() {
+    unsafe {
+        let (default_rm, session, _) = setup("test_vi_read_to_file");
+
+        let status = viReadToFile(
+            session,
+            b"output.dat\0".as_ptr() as *const i8,
+            1024,
+            VI_NULL as ViPUInt32,
+        );
+        assert_eq!(
+            status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to read to file"
+        );
+
+        println!("Data successfully written to output.dat");
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_write() {
+    unsafe {
+        let (default_rm, session, _) = setup("test_vi_write");
+
+        let scpi_command = b"*IDN?\n";
+        let mut write_count: ViUInt32 = 0;
+        let write_status = viWrite(
+            session,
+            scpi_command.as_ptr() as *const ViByte,
+            scpi_command.len() as ViUInt32,
+            &mut write_count,
+        );
+        assert_eq!(
+            write_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to write data"
+        );
+
+        println!("Wrote {} bytes", write_count);
+
+        teardown(default_rm, session);
+    }
+}
+
+#[test]
+fn test_vi_write_func_squ() {
+    unsafe {
+        let (default_rm, session, _) = setup("test_vi_write_func_squ");
+
+        let scpi_command = b"OUTPut ON; :FREQuency +20.0E+03; :FUNC SIN; VOLTage:OFFSet 2mV; :FUNCtion:ARBitrary:PTPeak 2";
+        let mut write_count: ViUInt32 = 0;
+        let write_status = viWrite(
+            session,
+            scpi_command.as_ptr() as *const ViByte,
+            scpi_command.len() as ViUInt32,
+            &mut write_count,
+        );
+        assert_eq!(
+            write_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to write data"
+        );
+        println!("Wrote {} bytes", write_count);
+
+        let scpi_command2 = b"SYSTem:ERRor?";
+        let mut write_count: ViUInt32 = 0;
+        let err_write_status = viWrite(
+            session,
+            scpi_command2.as_ptr() as *const ViByte,
+            scpi_command2.len() as ViUInt32,
+            &mut write_count,
+        );
+        assert_eq!(
+            err_write_status,
+            ViStatus::try_from(VI_SUCCESS).unwrap(),
+            "Failed to write data"
+        );
+        println!("Wrote {} bytes", write_count);
+
+        let mut buffer: [u8; 256] = [0; 256];
+        let mut read_count: ViUInt32 = 0;
+        let read_status = viRead(
+            session,
+            buffer.as_mut_ptr() as *mut ViByte,
+            buffer.len() as ViUInt32,
+            &mut read_count,
+        );
+        print_status_description(default_rm, read_status);
+        assert!(
+            read_status == ViStatus::try_from(VI_SUCCESS).unwrap()
+                || read_status == ViStatus::try_from(VI_SUCCESS_MAX_CNT).unwrap(),
+            "Failed to read data"
+        );
+        println!(
+            "On SCPI command written: {}.\nRead {} bytes: {:?}.\nText: {}",
+            String::from_utf8_lossy(scpi_command2),
+            read_count,
+            &buffer[..read_count as usize],
+            String::from_utf8_lossy(&buffer[..read_count as usize])
+        );
+
+        teardown(default_rm, session);
+    }
+}
+
+fn print_status_description(rm: ViSession, status: ViStatus) {
+    let mut desc: [ViChar; 256] = [0; 256];
+    unsafe {
+        viStatusDesc(rm, status, desc.as_mut_ptr());
+        let description = std::ffi::CStr::from_ptr(desc.as_ptr())
+            .to_string_lossy()
+            .to_string();
+
+        println!("Description: {}", description);
+    }
+}
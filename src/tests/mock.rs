@@ -0,0 +1,259 @@
+use std::io::Write;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::attr::{self, Attribute};
+use crate::backend::mock::{MockBackend, MockDevice, MockValue};
+use crate::ffi::*;
+use crate::{AsyncSession, EventKind, ResourceManager, StatusByte};
+
+const DMM: &str = "GPIB0::5::INSTR";
+
+fn echo(command: &[u8]) -> Option<Vec<u8>> {
+    command.ends_with(b"?\n").then(|| command.to_vec())
+}
+
+fn rm(mock: MockBackend) -> ResourceManager {
+    ResourceManager::with_backend(Arc::new(mock)).unwrap()
+}
+
+/// Answers `SYST:ERR?` from a fixed list of entries.
+struct ErrorQueue(Vec<&'static str>);
+
+impl MockDevice for ErrorQueue {
+    fn write(&mut self, _: &[u8]) -> Option<Vec<u8>> {
+        let entry = if self.0.is_empty() { "0,\"No error\"" } else { self.0.remove(0) };
+        Some(format!("{entry}\n").into_bytes())
+    }
+
+    fn status_byte(&mut self) -> u8 {
+        if self.0.is_empty() { 0 } else { StatusByte::ERROR_QUEUE.bits() }
+    }
+}
+
+#[test]
+fn test_query() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    let mut session = rm.open("GPIB::5::INSTR").unwrap();
+    assert_eq!(session.query("*IDN?").unwrap(), "*IDN?");
+
+    let err = session.read_string().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn test_open_unknown_resource() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    assert_eq!(rm.open("GPIB0::6::INSTR").unwrap_err().raw(), VI_ERROR_RSRC_NFOUND);
+}
+
+#[test]
+fn test_termchar_splits_messages() {
+    let rm = rm(MockBackend::new().with_resource(DMM, |_: &[u8]| Some(b"1\n2\n".to_vec())));
+    let mut session = rm.open(DMM).unwrap();
+    session.set::<attr::TermCharEn>(true).unwrap();
+    session.write_all(b"MEAS?\n").unwrap();
+
+    let mut buf = Vec::new();
+    session.read_until_end(&mut buf).unwrap();
+    assert_eq!(buf, b"1\n");
+    buf.clear();
+    session.read_until_end(&mut buf).unwrap();
+    assert_eq!(buf, b"2\n");
+}
+
+#[test]
+fn test_read_until_end_spans_chunks() {
+    let response: Vec<u8> = (0..100_000).map(|i| b'0' + (i % 10) as u8).collect();
+    let expected = response.clone();
+    let rm = rm(MockBackend::new().with_resource(DMM, move |_: &[u8]| Some(response.clone())));
+    let mut session = rm.open(DMM).unwrap();
+    session.write_all(b"CURV?\n").unwrap();
+
+    let mut buf = Vec::new();
+    assert_eq!(session.read_until_end(&mut buf).unwrap(), expected.len());
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn test_attributes() {
+    let mock = MockBackend::new()
+        .with_resource(DMM, echo)
+        .with_attribute(DMM, attr::ModelName::ID, "X1")
+        .with_attribute(DMM, attr::ManfId::ID, 0x1234u16);
+    let rm = rm(mock);
+    let session = rm.open(DMM).unwrap();
+    assert_eq!(session.get::<attr::ModelName>().unwrap(), "X1");
+    assert_eq!(session.get::<attr::ManfId>().unwrap(), 0x1234);
+    assert_eq!(session.get::<attr::RsrcName>().unwrap(), DMM);
+    assert_eq!(session.get::<attr::IntfNum>().unwrap(), 0);
+
+    session.set::<attr::TmoValue>(500).unwrap();
+    assert_eq!(session.get::<attr::TmoValue>().unwrap(), 500);
+    // Local attributes are per session.
+    let other = rm.open(DMM).unwrap();
+    assert_eq!(other.get::<attr::TmoValue>().unwrap(), 2000);
+
+    assert_eq!(session.get::<attr::UsbSerialNum>().unwrap_err().raw(), VI_ERROR_NSUP_ATTR);
+}
+
+#[test]
+fn test_find() {
+    let mock = MockBackend::new()
+        .with_resource("GPIB0::5::INSTR", echo)
+        .with_resource("GPIB0::7::INSTR", echo)
+        .with_resource("TCPIP0::10.0.0.2::5025::SOCKET", echo);
+    let rm = rm(mock);
+
+    let names: Vec<String> = rm.find("GPIB?*INSTR").map(|name| name.unwrap().to_string()).collect();
+    assert_eq!(names, ["GPIB0::5::INSTR", "GPIB0::7::INSTR"]);
+    assert_eq!(rm.find("?*SOCKET|GPIB0::7::?*").count(), 2);
+    assert_eq!(rm.find("ASRL[0-9]*::?*").count(), 0);
+}
+
+#[test]
+fn test_check_errors() {
+    let device = ErrorQueue(vec!["-113,\"Undefined header\"", "-222,\"Data out of range\""]);
+    let rm = rm(MockBackend::new().with_resource(DMM, device));
+    let mut session = rm.open(DMM).unwrap();
+    assert!(session.read_stb().unwrap().contains(StatusByte::ERROR_QUEUE));
+
+    let errors = session.check_errors().unwrap();
+    let codes: Vec<i32> = errors.iter().map(|error| error.code()).collect();
+    assert_eq!(codes, [-113, -222]);
+    assert!(session.read_stb().unwrap().is_empty());
+}
+
+#[test]
+fn test_event_queue() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, echo));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+    let short = Duration::from_millis(10);
+
+    assert_eq!(session.wait_event(EventKind::ServiceRequest, short).unwrap_err().raw(), VI_ERROR_NENABLED);
+    let guard = session.enable_event(EventKind::ServiceRequest).unwrap();
+    assert_eq!(guard.wait_event(EventKind::ServiceRequest, short).unwrap_err().raw(), VI_ERROR_TMO);
+
+    mock.raise_event(DMM, VI_EVENT_SERVICE_REQ, &[]).unwrap();
+    let event = guard.wait_event(EventKind::ServiceRequest, short).unwrap();
+    assert_eq!(event.kind(), EventKind::ServiceRequest);
+
+    mock.raise_event(DMM, VI_EVENT_SERVICE_REQ, &[]).unwrap();
+    guard.discard_events(EventKind::ServiceRequest).unwrap();
+    assert_eq!(guard.wait_event(EventKind::ServiceRequest, short).unwrap_err().raw(), VI_ERROR_TMO);
+
+    guard.disable().unwrap();
+    assert_eq!(session.wait_event(EventKind::ServiceRequest, short).unwrap_err().raw(), VI_ERROR_NENABLED);
+}
+
+#[test]
+fn test_event_handler() {
+    let mock = Arc::new(MockBackend::new().with_resource(DMM, echo));
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    let handler = session
+        .on_event(EventKind::Trigger, move |event| {
+            sender.send(event.get::<attr::RecvTrigId>().unwrap()).unwrap();
+        })
+        .unwrap();
+    mock.raise_event(DMM, VI_EVENT_TRIG, &[(VI_ATTR_RECV_TRIG_ID, MockValue::from(VI_TRIG_SW as i16))])
+        .unwrap();
+    assert_eq!(receiver.try_recv().unwrap(), VI_TRIG_SW as i16);
+
+    drop(handler);
+    mock.raise_event(DMM, VI_EVENT_TRIG, &[]).unwrap();
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test]
+fn test_exclusive_lock() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    let mut first = rm.open(DMM).unwrap();
+    let mut second = rm.open(DMM).unwrap();
+    let short = Duration::from_millis(10);
+
+    let mut guard = first.lock_exclusive(short).unwrap();
+    assert!(!guard.is_nested());
+    assert!(guard.lock_exclusive(short).unwrap().is_nested());
+    assert_eq!(guard.query("*IDN?").unwrap(), "*IDN?");
+
+    assert!(second.write_all(b"*RST\n").is_err());
+    assert_eq!(second.lock_exclusive(short).unwrap_err().raw(), VI_ERROR_TMO);
+
+    guard.unlock().unwrap();
+    assert_eq!(first.exclusive_locks(), 0);
+    second.with_lock(short, |session| session.write_all(b"*RST\n")).unwrap().unwrap();
+}
+
+#[test]
+fn test_shared_lock() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    let mut first = rm.open(DMM).unwrap();
+    let mut second = rm.open(DMM).unwrap();
+    let mut third = rm.open(DMM).unwrap();
+    let short = Duration::from_millis(10);
+
+    let shared = first.lock_shared(Some("bench"), short).unwrap();
+    assert_eq!(shared.key(), "bench");
+    let other = second.lock_shared(Some(shared.key()), short).unwrap();
+    assert_eq!(third.lock_shared(Some("other"), short).unwrap_err().raw(), VI_ERROR_TMO);
+    assert_eq!(third.lock_exclusive(short).unwrap_err().raw(), VI_ERROR_TMO);
+
+    drop(other);
+    drop(shared);
+    let generated = third.lock_shared(None, short).unwrap();
+    assert!(!generated.key().is_empty());
+}
+
+#[test]
+fn test_nested_lock_types() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    let mut first = rm.open(DMM).unwrap();
+    let mut second = rm.open(DMM).unwrap();
+    let short = Duration::from_millis(10);
+
+    // The shared guard would release the exclusive lock when dropped.
+    let mut exclusive = first.lock_exclusive(short).unwrap();
+    assert_eq!(exclusive.lock_shared(Some("bench"), short).unwrap_err().raw(), VI_ERROR_INV_LOCK_TYPE);
+    assert_eq!((exclusive.exclusive_locks(), exclusive.shared_locks()), (1, 0));
+    assert_eq!(second.lock_shared(Some("bench"), short).unwrap_err().raw(), VI_ERROR_TMO);
+    exclusive.unlock().unwrap();
+
+    let mut shared = first.lock_shared(Some("bench"), short).unwrap();
+    shared.lock_exclusive(short).unwrap().unlock().unwrap();
+    assert_eq!((shared.exclusive_locks(), shared.shared_locks()), (0, 1));
+    assert_eq!(second.lock_exclusive(short).unwrap_err().raw(), VI_ERROR_TMO);
+    second.lock_shared(Some("bench"), short).unwrap().unlock().unwrap();
+    shared.unlock().unwrap();
+    assert_eq!((first.exclusive_locks(), first.shared_locks()), (0, 0));
+    second.lock_exclusive(short).unwrap().unlock().unwrap();
+}
+
+#[test]
+fn test_closing_releases_locks() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    let mut first = rm.open(DMM).unwrap();
+    let mut second = rm.open(DMM).unwrap();
+    let short = Duration::from_millis(10);
+
+    std::mem::forget(first.lock_exclusive(short).unwrap());
+    assert_eq!(second.lock_exclusive(short).unwrap_err().raw(), VI_ERROR_TMO);
+    first.close().unwrap();
+    second.lock_exclusive(short).unwrap().unlock().unwrap();
+}
+
+#[test]
+fn test_async_session() {
+    let rm = rm(MockBackend::new().with_resource(DMM, echo));
+    let session = AsyncSession::new(rm.open(DMM).unwrap()).unwrap();
+
+    futures::executor::block_on(async {
+        assert_eq!(session.write(b"MEAS?\n".to_vec()).await.unwrap(), 6);
+        assert_eq!(session.read(vec![0; 64]).await.unwrap(), b"MEAS?\n");
+        let err = session.read(vec![0; 64]).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    });
+}
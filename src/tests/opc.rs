@@ -0,0 +1,192 @@
+use std::io;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::attr::{self, Attribute};
+use crate::backend::mock::{MockBackend, MockDevice};
+use crate::ffi::{VI_ERROR_TMO, VI_EVENT_SERVICE_REQ};
+use crate::{EventKind, OpcStrategy, ResourceManager, Session, StandardEvent, StatusByte};
+
+const DMM: &str = "GPIB0::5::INSTR";
+
+/// The IEEE 488.2 status model of an instrument whose `INIT` runs for
+/// `duration` serial polls.
+struct Instrument {
+    duration: u32,
+    busy: Option<u32>,
+    opc_armed: bool,
+    esr: u8,
+    ese: u8,
+    sre: u8,
+}
+
+impl Instrument {
+    fn new(duration: u32) -> Self {
+        Instrument {
+            duration,
+            busy: None,
+            opc_armed: false,
+            // Left over from an earlier `*OPC`, to be cleared by `wait_opc`.
+            esr: StandardEvent::OPC.bits(),
+            ese: 0,
+            sre: 0,
+        }
+    }
+}
+
+impl MockDevice for Instrument {
+    fn write(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        let command = std::str::from_utf8(data).unwrap().trim_end();
+        let response = match command.split_once(' ') {
+            Some(("*ESE", mask)) => {
+                self.ese = mask.parse().unwrap();
+                return None;
+            }
+            Some(("*SRE", mask)) => {
+                self.sre = mask.parse().unwrap();
+                return None;
+            }
+            _ => match command {
+                "INIT" => {
+                    self.busy = Some(self.duration);
+                    return None;
+                }
+                "*OPC" => {
+                    self.opc_armed = true;
+                    return None;
+                }
+                // Answered once the operation is complete, which the mock
+                // reports as a timeout meanwhile.
+                "*OPC?" if self.busy.is_some_and(|left| left > 0) => return None,
+                "*OPC?" => 1,
+                "*ESE?" => self.ese,
+                "*SRE?" => self.sre,
+                "*ESR?" => std::mem::take(&mut self.esr),
+                other => panic!("unexpected command {other:?}"),
+            },
+        };
+        Some(format!("{response}\n").into_bytes())
+    }
+
+    /// Each serial poll advances the operation by one step.
+    fn status_byte(&mut self) -> u8 {
+        match &mut self.busy {
+            Some(0) => self.busy = None,
+            Some(left) => *left -= 1,
+            None => {}
+        }
+        if self.busy.is_none() && self.opc_armed {
+            self.opc_armed = false;
+            self.esr |= StandardEvent::OPC.bits();
+        }
+        let mut status = StatusByte::empty();
+        if self.esr & self.ese != 0 {
+            status |= StatusByte::ESB;
+        }
+        if status.bits() & self.sre != 0 {
+            status |= StatusByte::RQS;
+        }
+        status.bits()
+    }
+}
+
+fn mock(duration: u32) -> Arc<MockBackend> {
+    let mock = MockBackend::new()
+        .with_resource(DMM, Instrument::new(duration))
+        .with_attribute(DMM, attr::TmoValue::ID, 2000u32);
+    Arc::new(mock)
+}
+
+fn rm(duration: u32) -> ResourceManager {
+    ResourceManager::with_backend(mock(duration)).unwrap()
+}
+
+fn start(session: &mut Session<'_>, strategy: OpcStrategy) {
+    session.set_opc_strategy(strategy);
+    session.write_str("INIT").unwrap();
+}
+
+#[test]
+fn test_wait_opc_query() {
+    let rm = rm(0);
+    let mut session = rm.open(DMM).unwrap();
+    start(&mut session, OpcStrategy::Query);
+    session.wait_opc(Duration::from_secs(5)).unwrap();
+    assert_eq!(session.get::<attr::TmoValue>().unwrap(), 2000);
+}
+
+#[test]
+fn test_wait_opc_query_times_out() {
+    let rm = rm(u32::MAX);
+    let mut session = rm.open(DMM).unwrap();
+    start(&mut session, OpcStrategy::Query);
+    let error = session.wait_opc(Duration::from_millis(50)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(session.get::<attr::TmoValue>().unwrap(), 2000);
+}
+
+#[test]
+fn test_wait_opc_polls_event_status() {
+    let rm = rm(3);
+    let mut session = rm.open(DMM).unwrap();
+    start(&mut session, OpcStrategy::Poll(Duration::from_millis(1)));
+    session.wait_opc(Duration::from_secs(5)).unwrap();
+
+    // The enable registers are restored and the OPC event was read.
+    assert!(session.standard_event_enable().unwrap().is_empty());
+    assert!(session.service_request_enable().unwrap().is_empty());
+    assert!(session.read_standard_events().unwrap().is_empty());
+}
+
+#[test]
+fn test_wait_opc_poll_times_out() {
+    let rm = rm(u32::MAX);
+    let mut session = rm.open(DMM).unwrap();
+    start(&mut session, OpcStrategy::Poll(Duration::from_millis(5)));
+
+    let start = Instant::now();
+    let error = session.wait_opc(Duration::from_millis(50)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(session.standard_event_enable().unwrap().is_empty());
+}
+
+#[test]
+fn test_wait_opc_service_request() {
+    // The first serial poll leaves the operation running, so the wait needs
+    // the service request raised meanwhile to poll again.
+    let mock = mock(1);
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+    start(&mut session, OpcStrategy::ServiceRequest);
+
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            std::thread::sleep(Duration::from_millis(50));
+            mock.raise_event(DMM, VI_EVENT_SERVICE_REQ, &[]).unwrap();
+        });
+        session.wait_opc(Duration::from_secs(5)).unwrap();
+    });
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(session.service_request_enable().unwrap().is_empty());
+
+    // The wait consumed the request.
+    let guard = session.enable_event(EventKind::ServiceRequest).unwrap();
+    let error = guard.wait_event(EventKind::ServiceRequest, Duration::ZERO).unwrap_err();
+    assert_eq!(error.raw(), VI_ERROR_TMO);
+}
+
+#[test]
+fn test_wait_opc_service_request_times_out() {
+    let rm = rm(u32::MAX);
+    let mut session = rm.open(DMM).unwrap();
+    start(&mut session, OpcStrategy::ServiceRequest);
+
+    let start = Instant::now();
+    let error = session.wait_opc(Duration::from_millis(50)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(session.standard_event_enable().unwrap().is_empty());
+    assert!(session.service_request_enable().unwrap().is_empty());
+}
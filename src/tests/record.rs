@@ -0,0 +1,111 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use crate::attr::{self, Attribute};
+use crate::backend::mock::MockBackend;
+use crate::backend::record::{Call, Data, Recording, RecordingBackend, ReplayBackend, ReplayOptions};
+use crate::backend::VisaBackend;
+use crate::ResourceManager;
+
+const DMM: &str = "GPIB0::5::INSTR";
+
+fn dmm(command: &[u8]) -> Option<Vec<u8>> {
+    (command == b"*IDN?\n").then(|| b"ACME,DMM-1,42,1.0\n".to_vec())
+}
+
+/// The session under test: sets the clock and the voltage and identifies the DMM.
+fn run(backend: Arc<dyn VisaBackend>, time: &str, volts: f64) -> (String, String) {
+    let rm = ResourceManager::with_backend(backend).unwrap();
+    let mut dmm = rm.open(DMM).unwrap();
+    dmm.write_str(&format!("SYST:TIME {time}")).unwrap();
+    dmm.write_str(&format!("VOLT {volts}")).unwrap();
+    (dmm.get::<attr::ModelName>().unwrap(), dmm.query("*IDN?").unwrap())
+}
+
+fn record() -> Recording {
+    let mock = MockBackend::new().with_resource(DMM, dmm).with_attribute(DMM, attr::ModelName::ID, "DMM-1");
+    let recorder = Arc::new(RecordingBackend::new(Arc::new(mock)));
+    run(recorder.clone(), "12,00,00", 1.5);
+    recorder.recording()
+}
+
+fn replay(recording: Recording, options: ReplayOptions) -> Arc<ReplayBackend> {
+    Arc::new(ReplayBackend::new(recording, options))
+}
+
+#[test]
+fn test_record() {
+    let recording = record();
+    assert!(matches!(recording.entries[0].call, Call::OpenDefaultRm { .. }));
+    assert!(recording.entries.iter().any(|entry| entry.call
+        == Call::Write {
+            vi: 2,
+            data: Data(b"VOLT 1.5\n".to_vec()),
+            count: 9
+        }));
+    assert!(matches!(recording.entries.last().unwrap().call, Call::Close { .. }));
+}
+
+#[test]
+fn test_replay() {
+    let replay = replay(record(), ReplayOptions::new());
+    let (model, idn) = run(replay.clone(), "12,00,00", 1.5);
+    assert_eq!(model, "DMM-1");
+    assert_eq!(idn, "ACME,DMM-1,42,1.0");
+    replay.finish().unwrap();
+}
+
+#[test]
+fn test_loose_replay() {
+    let options = ReplayOptions::new().float_tolerance(1e-6).ignore_arguments("syst:time");
+    let replay = replay(record(), options);
+    run(replay.clone(), "13,37,00", 1.5000001);
+    replay.finish().unwrap();
+}
+
+#[test]
+fn test_divergence() {
+    let replay = replay(record(), ReplayOptions::new().float_tolerance(1e-6));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| run(replay.clone(), "12,00,00", 2.5)));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("VOLT 2.5"), "{message}");
+
+    let divergence = replay.finish().unwrap_err();
+    assert!(matches!(divergence.expected.as_deref(), Some(Call::Write { .. })));
+    assert!(matches!(divergence.actual.as_deref(), Some(Call::Write { .. })));
+}
+
+#[test]
+fn test_missing_calls() {
+    let mut recording = record();
+    let index = recording.entries.len();
+    recording.entries.push(recording.entries[0].clone());
+    let replay = replay(recording, ReplayOptions::new());
+    run(replay.clone(), "12,00,00", 1.5);
+
+    let divergence = replay.finish().unwrap_err();
+    assert_eq!(divergence.index, index);
+    assert!(divergence.actual.is_none());
+}
+
+#[test]
+fn test_save_and_load() {
+    let mut recording = record();
+    recording.entries[0].call = Call::Read {
+        vi: 2,
+        len: 4,
+        data: Data(vec![0xff, 0, 1]),
+    };
+    let mut text = Vec::new();
+    recording.write_to(&mut text).unwrap();
+    let text = String::from_utf8(text).unwrap();
+    assert_eq!(text.lines().count(), recording.entries.len());
+    assert!(text.contains("\"op\":\"read\",\"vi\":2,\"len\":4,\"data\":[255,0,1]"), "{text}");
+    assert!(text.contains("\"data\":\"VOLT 1.5\\n\""), "{text}");
+
+    let path = std::env::temp_dir().join(format!("ni-visa-bindings-record-{}.jsonl", std::process::id()));
+    recording.save(&path).unwrap();
+    let loaded = Recording::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), recording);
+}
@@ -0,0 +1,117 @@
+use std::io;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::backend::mock::{MockBackend, MockDevice};
+use crate::ffi::*;
+use crate::{ResourceManager, StandardEvent, StatusByte, StatusWait};
+
+const DMM: &str = "GPIB0::5::INSTR";
+
+/// Reports `StatusByte::MAV` from serial poll `ready_after` on.
+struct SlowDevice {
+    polls: Arc<AtomicU32>,
+    ready_after: u32,
+}
+
+impl MockDevice for SlowDevice {
+    fn write(&mut self, _: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn status_byte(&mut self) -> u8 {
+        let poll = self.polls.fetch_add(1, Ordering::SeqCst) + 1;
+        if poll >= self.ready_after { StatusByte::MAV.bits() } else { 0 }
+    }
+}
+
+/// A mock with a `SlowDevice`, and the counter of its serial polls.
+fn slow_device(ready_after: u32) -> (Arc<MockBackend>, Arc<AtomicU32>) {
+    let polls = Arc::new(AtomicU32::new(0));
+    let device = SlowDevice {
+        polls: polls.clone(),
+        ready_after,
+    };
+    (Arc::new(MockBackend::new().with_resource(DMM, device)), polls)
+}
+
+#[test]
+fn test_status_byte_bits() {
+    assert_eq!(StatusByte::ERROR_QUEUE.bits(), 4);
+    assert_eq!(StatusByte::MAV.bits(), 16);
+    assert_eq!(StatusByte::ESB.bits(), 32);
+    assert_eq!(StatusByte::RQS, StatusByte::MSS);
+    assert_eq!(StatusByte::OPERATION.bits(), 128);
+
+    // A serial poll after `*OPC` with ESB enabled, plus device-specific bit 0.
+    let status = StatusByte::from_bits_retain(0x61);
+    assert!(status.contains(StatusByte::ESB | StatusByte::RQS));
+    assert!(!status.contains(StatusByte::MAV));
+    assert_eq!(status.bits(), 0x61);
+}
+
+#[test]
+fn test_standard_event_bits() {
+    assert_eq!(StandardEvent::OPC.bits(), 1);
+    assert_eq!(StandardEvent::QYE.bits(), 4);
+    assert_eq!(StandardEvent::CME.bits(), 32);
+    assert_eq!(StandardEvent::PON.bits(), 128);
+    assert_eq!(StandardEvent::ERRORS.bits(), 0x3C);
+
+    let events = StandardEvent::from_bits_retain(0x21);
+    assert!(events.intersects(StandardEvent::ERRORS));
+    assert_eq!(events & StandardEvent::ERRORS, StandardEvent::CME);
+}
+
+#[test]
+fn test_wait_for_status_polls() {
+    let (mock, polls) = slow_device(3);
+    let rm = ResourceManager::with_backend(mock).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    let wait = StatusWait::Poll(Duration::from_millis(1));
+    let status = session.wait_for_status(StatusByte::MAV, wait, Duration::from_secs(5)).unwrap();
+    assert_eq!(status, StatusByte::MAV);
+    assert_eq!(polls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_wait_for_status_times_out() {
+    let (mock, polls) = slow_device(u32::MAX);
+    let rm = ResourceManager::with_backend(mock).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    let start = Instant::now();
+    let wait = StatusWait::Poll(Duration::from_millis(5));
+    let error = session.wait_for_status(StatusByte::MAV, wait, Duration::from_millis(50)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(polls.load(Ordering::SeqCst) > 1);
+}
+
+#[test]
+fn test_wait_for_status_on_service_request() {
+    let (mock, polls) = slow_device(2);
+    let rm = ResourceManager::with_backend(mock.clone()).unwrap();
+    let mut session = rm.open(DMM).unwrap();
+
+    let raiser = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        mock.raise_event(DMM, VI_EVENT_SERVICE_REQ, &[]).unwrap();
+    });
+    let status = session
+        .wait_for_status(StatusByte::MAV, StatusWait::ServiceRequest, Duration::from_secs(5))
+        .unwrap();
+    raiser.join().unwrap();
+    assert_eq!(status, StatusByte::MAV);
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+
+    // Without a request, only the first poll is made.
+    let error = session
+        .wait_for_status(StatusByte::ESB, StatusWait::ServiceRequest, Duration::from_millis(20))
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(polls.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,231 @@
+use proptest::prelude::*;
+
+use crate::ffi::*;
+use crate::{PxiAddress, ResourceClass, ResourceName};
+
+fn canonical(name: &str) -> String {
+    name.parse::<ResourceName>().unwrap().to_string()
+}
+
+#[test]
+fn test_parse_usb_instr() {
+    let name: ResourceName = "USB0::0x0957::0x5407::MY59002371::0::INSTR".parse().unwrap();
+    assert_eq!(
+        name,
+        ResourceName::Usb {
+            board: 0,
+            manufacturer_id: 0x0957,
+            model_code: 0x5407,
+            serial_number: "MY59002371".to_string(),
+            interface: Some(0),
+        }
+    );
+    assert_eq!(name.interface_type(), Some(VI_INTF_USB as u16));
+    assert_eq!(name.resource_class(), Some(ResourceClass::Instr));
+}
+
+#[test]
+fn test_canonicalization() {
+    assert_eq!(canonical("usb::2391::21511::MY59002371"), "USB0::0x0957::0x5407::MY59002371::INSTR");
+    assert_eq!(canonical("USB1::0x0957::0x5407::MY1::RAW"), "USB1::0x0957::0x5407::MY1::RAW");
+    assert_eq!(canonical("gpib::5"), "GPIB0::5::INSTR");
+    assert_eq!(canonical("GPIB1::5::3::INSTR"), "GPIB1::5::3::INSTR");
+    assert_eq!(canonical("gpib-vxi::24"), "GPIB-VXI0::24::INSTR");
+    assert_eq!(canonical("VXI::8::instr"), "VXI0::8::INSTR");
+    assert_eq!(canonical("asrl3"), "ASRL3::INSTR");
+    assert_eq!(canonical("tcpip::192.168.0.10"), "TCPIP0::192.168.0.10::inst0::INSTR");
+    assert_eq!(canonical("TCPIP::scope.local::HISLIP0"), "TCPIP0::scope.local::hislip0::INSTR");
+    assert_eq!(canonical("tcpip0::10.0.0.2::5025::socket"), "TCPIP0::10.0.0.2::5025::SOCKET");
+    assert_eq!(canonical("PXI0::2-15.1::INSTR"), "PXI0::2-15.1::INSTR");
+    assert_eq!(canonical("pxi::chassis1::slot4"), "PXI0::CHASSIS1::SLOT4::INSTR");
+}
+
+#[test]
+fn test_tcpip_ipv6_and_hislip() {
+    let name: ResourceName = "TCPIP0::[fe80::1%eth0]::hislip0::INSTR".parse().unwrap();
+    assert!(name.is_hislip());
+    assert_eq!(name.to_string(), "TCPIP0::[fe80::1%eth0]::hislip0::INSTR");
+
+    let socket: ResourceName = "TCPIP::[::1]::5025::SOCKET".parse().unwrap();
+    assert_eq!(socket, ResourceName::socket("[::1]", 5025));
+    assert_eq!(ResourceName::vxi11("10.0.0.2").with_board(1).to_string(), "TCPIP1::10.0.0.2::inst0::INSTR");
+}
+
+#[test]
+fn test_aliases_and_other_classes() {
+    assert_eq!("MyScope".parse::<ResourceName>().unwrap(), ResourceName::Alias("MyScope".to_string()));
+    assert_eq!("USBScope".parse::<ResourceName>().unwrap(), ResourceName::Alias("USBScope".to_string()));
+
+    let intfc: ResourceName = "GPIB0::INTFC".parse().unwrap();
+    assert_eq!(intfc.interface_type(), Some(VI_INTF_GPIB as u16));
+    assert_eq!(intfc.resource_class(), Some(ResourceClass::Intfc));
+    assert_eq!(intfc.to_string(), "GPIB0::INTFC");
+    assert_eq!(ResourceName::Alias("MyScope".to_string()).resource_class(), None);
+
+    assert_eq!(canonical("gpib::intfc"), "GPIB0::INTFC");
+    assert_eq!(canonical("vxi1::2::Backplane"), "VXI1::2::BACKPLANE");
+    assert_eq!(canonical("pxi0::memacc"), "PXI0::MEMACC");
+    assert_eq!(canonical("tcpip::servant"), "TCPIP0::SERVANT");
+}
+
+#[test]
+fn test_resource_class_round_trip() {
+    for class in ResourceClass::ALL {
+        let text = class.to_string();
+        assert_eq!(text, text.to_ascii_uppercase());
+        assert_eq!(text.parse::<ResourceClass>().unwrap(), class);
+        assert_eq!(text.to_ascii_lowercase().parse::<ResourceClass>().unwrap(), class);
+    }
+    assert_eq!("Socket".parse::<ResourceClass>().unwrap(), ResourceClass::Socket);
+    assert_eq!(ResourceClass::Memacc.to_string(), "MEMACC");
+    for text in ["", "INSTRUMENT", "INTF", "BACK PLANE", "MEM_ACC"] {
+        assert_eq!(text.parse::<ResourceClass>().unwrap_err().raw(), VI_ERROR_INV_RSRC_NAME, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_invalid_names() {
+    for name in [
+        "",
+        "GPIB0::31::INSTR",
+        "GPIB0::INSTR",
+        "USB0::zz::0x5407::MY1::INSTR",
+        "USB0::0x10000::0x5407::MY1::INSTR",
+        "TCPIP0::fe80:0:1::INSTR",
+        "TCPIP0::[fe80::1::INSTR",
+        "TCPIP0::host::port::SOCKET",
+        "ASRL1::2::INSTR",
+        "GPIB0::::INSTR",
+        "my::alias",
+    ] {
+        let error = name.parse::<ResourceName>().unwrap_err();
+        assert_eq!(error.raw(), VI_ERROR_INV_RSRC_NAME, "{:?}", name);
+    }
+}
+
+fn host() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (any::<u8>(), any::<u8>(), any::<u8>(), any::<u8>()).prop_map(|(a, b, c, d)| format!("{}.{}.{}.{}", a, b, c, d)),
+        "[a-z][a-z0-9-]{0,10}(\\.[a-z]{2,5})?",
+        "\\[(fe80|2001:db8)::[0-9a-f]{1,4}\\]",
+    ]
+}
+
+fn pxi_address() -> impl Strategy<Value = PxiAddress> {
+    prop_oneof![
+        (proptest::option::of(0..32u16), 0..32u16, proptest::option::of(0..8u16))
+            .prop_map(|(bus, device, function)| PxiAddress::Bus { bus, device, function }),
+        (1..16u16, 1..32u16, proptest::option::of(0..8u16))
+            .prop_map(|(chassis, slot, function)| PxiAddress::Chassis { chassis, slot, function }),
+    ]
+}
+
+fn resource_name() -> impl Strategy<Value = ResourceName> {
+    let board = 0..8u16;
+    prop_oneof![
+        (board.clone(), 0..=30u8, proptest::option::of(0..=30u8)).prop_map(|(board, primary, secondary)| {
+            ResourceName::Gpib {
+                board,
+                primary,
+                secondary,
+            }
+        }),
+        (board.clone(), 0..256u16).prop_map(|(board, logical_address)| ResourceName::GpibVxi { board, logical_address }),
+        (board.clone(), 0..256u16).prop_map(|(board, logical_address)| ResourceName::Vxi { board, logical_address }),
+        board.clone().prop_map(|board| ResourceName::Asrl { board }),
+        (board.clone(), host(), "(inst|hislip)[0-9]").prop_map(|(board, host, device)| ResourceName::Tcpip {
+            board,
+            host,
+            device,
+        }),
+        (board.clone(), host(), any::<u16>()).prop_map(|(board, host, port)| ResourceName::TcpipSocket { board, host, port }),
+        (
+            board.clone(),
+            any::<u16>(),
+            any::<u16>(),
+            "[A-Z0-9]{1,12}",
+            proptest::option::of(0..4u16),
+            any::<bool>()
+        )
+            .prop_map(|(board, manufacturer_id, model_code, serial_number, interface, raw)| {
+                if raw {
+                    ResourceName::UsbRaw {
+                        board,
+                        manufacturer_id,
+                        model_code,
+                        serial_number,
+                        interface,
+                    }
+                } else {
+                    ResourceName::Usb {
+                        board,
+                        manufacturer_id,
+                        model_code,
+                        serial_number,
+                        interface,
+                    }
+                }
+            }),
+        (board, pxi_address()).prop_map(|(interface, address)| ResourceName::Pxi { interface, address }),
+        "my[A-Za-z0-9_]{1,10}".prop_map(ResourceName::Alias),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_display_parse_round_trip(name in resource_name()) {
+        let text = name.to_string();
+        prop_assert_eq!(text.parse::<ResourceName>().unwrap(), name.clone());
+        // Keywords are case-insensitive.
+        if !matches!(name, ResourceName::Alias(_)) {
+            prop_assert_eq!(text.to_ascii_lowercase().parse::<ResourceName>().unwrap().interface_type(), name.interface_type());
+        }
+    }
+}
+
+/// Compares the parser with the VISA library's own `viParseRsrcEx`.
+#[cfg(visa_linked)]
+mod against_visa {
+    use std::ffi::{CStr, CString};
+
+    use super::*;
+    use crate::ResourceManager;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_matches_vi_parse_rsrc_ex(name in resource_name()) {
+            prop_assume!(!matches!(name, ResourceName::Alias(_)));
+            let rm = ResourceManager::new().unwrap();
+            let text = CString::new(name.to_string()).unwrap();
+
+            let mut intf_type: ViUInt16 = 0;
+            let mut intf_num: ViUInt16 = 0;
+            let mut class = [0 as ViChar; 256];
+            let mut expanded = [0 as ViChar; 256];
+            let mut alias = [0 as ViChar; 256];
+            let status = unsafe {
+                viParseRsrcEx(
+                    rm.as_raw(),
+                    text.as_ptr(),
+                    &mut intf_type,
+                    &mut intf_num,
+                    class.as_mut_ptr(),
+                    expanded.as_mut_ptr(),
+                    alias.as_mut_ptr(),
+                )
+            };
+            // Valid names of interfaces that are not configured here.
+            prop_assume!(status != VI_ERROR_RSRC_NFOUND && status != VI_ERROR_INTF_NUM_NCONFIG);
+            prop_assert!(status >= 0, "viParseRsrcEx rejected {}: {:#X}", name, status);
+
+            let class = unsafe { CStr::from_ptr(class.as_ptr()) }.to_string_lossy().into_owned();
+            let expanded = unsafe { CStr::from_ptr(expanded.as_ptr()) }.to_string_lossy().into_owned();
+            prop_assert_eq!(Some(intf_type), name.interface_type());
+            prop_assert_eq!(Some(intf_num), name.board());
+            prop_assert_eq!(Some(class.as_str()), name.resource_class().map(ResourceClass::as_str));
+            prop_assert_eq!(expanded.parse::<ResourceName>().unwrap(), name);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::scpi::{ErrorClass, ScpiError, ScpiErrors};
+
+#[test]
+fn test_parse_error_queue_entries() {
+    let error: ScpiError = "-113,\"Undefined header\"".parse().unwrap();
+    assert_eq!(error, ScpiError::new(-113, "Undefined header"));
+    assert_eq!(error.class(), ErrorClass::Command);
+    assert!(!error.is_no_error());
+
+    let none: ScpiError = "+0,\"No error\"\n".parse().unwrap();
+    assert!(none.is_no_error());
+    assert_eq!(none.class(), ErrorClass::NoError);
+
+    let quoted: ScpiError = "-222, \"Data out of range;\"\"FREQ\"\" 1E12\"".parse().unwrap();
+    assert_eq!(quoted.message(), "Data out of range;\"FREQ\" 1E12");
+
+    let unquoted: ScpiError = "201,Option not installed".parse().unwrap();
+    assert_eq!(unquoted, ScpiError::new(201, "Option not installed"));
+    assert_eq!(unquoted.class(), ErrorClass::DeviceDefined);
+    assert_eq!("0".parse::<ScpiError>().unwrap(), ScpiError::new(0, ""));
+}
+
+#[test]
+fn test_invalid_entries() {
+    for response in ["", "No error", "-113,\"Undefined header", "x,\"y\""] {
+        assert!(response.parse::<ScpiError>().is_err(), "{:?}", response);
+    }
+}
+
+#[test]
+fn test_error_classes() {
+    assert_eq!(ErrorClass::from_code(-100), ErrorClass::Command);
+    assert_eq!(ErrorClass::from_code(-199), ErrorClass::Command);
+    assert_eq!(ErrorClass::from_code(-200), ErrorClass::Execution);
+    assert_eq!(ErrorClass::from_code(-350), ErrorClass::DeviceSpecific);
+    assert_eq!(ErrorClass::from_code(-410), ErrorClass::Query);
+    assert_eq!(ErrorClass::from_code(-500), ErrorClass::PowerOn);
+    assert_eq!(ErrorClass::from_code(-800), ErrorClass::OperationComplete);
+    assert_eq!(ErrorClass::from_code(-50), ErrorClass::Reserved);
+    assert_eq!(ErrorClass::from_code(-410).name(), "Query error");
+}
+
+#[test]
+fn test_error_list_display() {
+    let errors = ScpiErrors(vec![
+        ScpiError::new(-113, "Undefined header"),
+        ScpiError::new(-222, "Data out of range"),
+    ]);
+    assert_eq!(
+        errors.to_string(),
+        "-113, \"Undefined header\" (Command error); -222, \"Data out of range\" (Execution error)"
+    );
+}
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use crate::ffi::*;
+use crate::{AccessMode, OpenOptions};
+
+#[test]
+fn test_default_open_options() {
+    let options = OpenOptions::default();
+    assert_eq!(options.access_mode_bits(), VI_NO_LOCK);
+    assert_eq!(options.open_timeout_ms(), VI_TMO_IMMEDIATE);
+}
+
+#[test]
+fn test_open_options_access_mode_bits() {
+    let options = OpenOptions::new().access_mode(AccessMode::ExclusiveLock).load_config(true);
+    assert_eq!(options.access_mode_bits(), VI_EXCLUSIVE_LOCK | VI_LOAD_CONFIG);
+}
+
+#[test]
+fn test_open_timeout_is_clamped_below_infinite() {
+    let options = OpenOptions::new().open_timeout(Duration::from_millis(2500));
+    assert_eq!(options.open_timeout_ms(), 2500);
+
+    let options = OpenOptions::new().open_timeout(Duration::MAX);
+    assert_eq!(options.open_timeout_ms(), VI_TMO_INFINITE - 1);
+}
+
+#[cfg(feature = "dynamic-loading")]
+#[test]
+fn test_resource_manager_reports_missing_library() {
+    if crate::ffi::library().is_ok() {
+        return;
+    }
+    let error = crate::ResourceManager::new().unwrap_err();
+    assert_eq!(error.raw(), VI_ERROR_LIBRARY_NFOUND);
+}
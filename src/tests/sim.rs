@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use crate::backend::sim::{Simulation, SimulationError};
+use crate::{ErrorQueueOptions, ResourceManager, StatusByte};
+
+const LAB: &str = r#"
+devices:
+  generator:
+    dialogues:
+      - { q: "*IDN?", r: "ACME,FG-1,1234,1.0" }
+      - { q: "*RST" }
+    properties:
+      frequency:
+        default: 1000.0
+        get: { q: "FREQ?" }
+        set: { q: "FREQ {}" }
+        min: 1
+        max: 1.0e6
+      shape:
+        default: SIN
+        get: { q: "FUNC?" }
+        set: { q: "FUNC {}" }
+        values: [SIN, SQU, RAMP]
+      channels:
+        default: 2
+        type: int
+        get: { q: "CHAN:COUN?", r: "+{}" }
+  scope:
+    termination: { write: "\r\n", read: "\r\n" }
+    errors: { query: "SYST:ERR?" }
+    dialogues:
+      - { q: "*IDN?", r: "ACME,SC-4,99,2.1" }
+resources:
+  "TCPIP0::10.0.0.7::INSTR": { device: generator }
+  "GPIB0::10::INSTR": { device: generator }
+  "USB0::0x1234::0x0001::SN1::INSTR": { device: scope }
+"#;
+
+fn lab() -> ResourceManager {
+    let simulation = Simulation::from_yaml(LAB).unwrap();
+    ResourceManager::with_backend(Arc::new(simulation.backend())).unwrap()
+}
+
+#[test]
+fn test_find_simulated_resources() {
+    let rm = lab();
+    let mut names: Vec<String> = rm.find("?*INSTR").map(|name| name.unwrap().to_string()).collect();
+    names.sort();
+    assert_eq!(names, ["GPIB0::10::INSTR", "TCPIP0::10.0.0.7::inst0::INSTR", "USB0::0x1234::0x0001::SN1::INSTR"]);
+    assert_eq!(rm.find("GPIB?*").count(), 1);
+}
+
+#[test]
+fn test_dialogues() {
+    let rm = lab();
+    let mut generator = rm.open("TCPIP0::10.0.0.7::INSTR").unwrap();
+    assert_eq!(generator.query("*IDN?").unwrap(), "ACME,FG-1,1234,1.0");
+    generator.write_str("*RST").unwrap();
+    assert!(generator.check_errors().unwrap().is_empty());
+}
+
+#[test]
+fn test_termination() {
+    let rm = lab();
+    let mut scope = rm.open("USB0::0x1234::0x0001::SN1::INSTR").unwrap();
+    let options = scope.text_options().clone().write_termination("\r\n").read_termination("\r\n");
+    scope.set_text_options(options).unwrap();
+    assert_eq!(scope.query("*IDN?").unwrap(), "ACME,SC-4,99,2.1");
+
+    scope.write_str("*TRG").unwrap();
+    scope.set_error_queue_options(ErrorQueueOptions::new().query("SYST:ERR?"));
+    let errors = scope.check_errors().unwrap();
+    assert_eq!(errors.iter().map(|error| error.code()).collect::<Vec<_>>(), [-113]);
+}
+
+#[test]
+fn test_properties() {
+    let rm = lab();
+    let mut generator = rm.open("GPIB0::10::INSTR").unwrap();
+    assert_eq!(generator.query_ascii_values::<f64>("FREQ?").unwrap(), [1000.0]);
+    generator.write_str("FREQ 2.5e3").unwrap();
+    assert_eq!(generator.query("FREQ?").unwrap(), "2500");
+
+    generator.write_str("FUNC squ").unwrap();
+    assert_eq!(generator.query("FUNC?").unwrap(), "squ");
+    assert_eq!(generator.query("CHAN:COUN?").unwrap(), "+2");
+
+    // Every resource has its own state.
+    let mut other = rm.open("TCPIP0::10.0.0.7::INSTR").unwrap();
+    assert_eq!(other.query("FREQ?").unwrap(), "1000");
+}
+
+#[test]
+fn test_error_queue() {
+    let rm = lab();
+    let mut generator = rm.open("GPIB0::10::INSTR").unwrap();
+    generator.write_str("FREQ 2e6").unwrap();
+    generator.write_str("FUNC NOISE").unwrap();
+    generator.write_str("FREQ fast").unwrap();
+    generator.write_str("VOLT 1").unwrap();
+    assert_eq!(generator.query("FREQ?").unwrap(), "1000");
+    assert_eq!(generator.query("FUNC?").unwrap(), "SIN");
+    assert!(generator.read_stb().unwrap().contains(StatusByte::ERROR_QUEUE));
+
+    let codes: Vec<i32> = generator.check_errors().unwrap().iter().map(|error| error.code()).collect();
+    assert_eq!(codes, [-222, -224, -224, -113]);
+    assert!(generator.read_stb().unwrap().is_empty());
+
+    generator.write_str("VOLT 1").unwrap();
+    generator.write_str("*CLS").unwrap();
+    assert!(generator.check_errors().unwrap().is_empty());
+}
+
+#[test]
+fn test_toml() {
+    let simulation = Simulation::from_toml(
+        r#"
+        [devices.psu]
+        dialogues = [{ q = "*IDN?", r = "ACME,PS-2,7,0.9" }]
+        properties.voltage = { default = 0, type = "float", get = { q = "VOLT?" }, set = { q = "VOLT {}" }, max = 30 }
+
+        [resources."ASRL3::INSTR"]
+        device = "psu"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(simulation.resources().collect::<Vec<_>>(), ["ASRL3::INSTR"]);
+
+    let rm = ResourceManager::with_backend(Arc::new(simulation.backend())).unwrap();
+    let mut psu = rm.open("ASRL3::INSTR").unwrap();
+    assert_eq!(psu.query("*IDN?").unwrap(), "ACME,PS-2,7,0.9");
+    psu.write_str("VOLT 12.5").unwrap();
+    assert_eq!(psu.query("VOLT?").unwrap(), "12.5");
+}
+
+#[test]
+fn test_load() {
+    let path = std::env::temp_dir().join(format!("ni-visa-bindings-sim-{}.yaml", std::process::id()));
+    std::fs::write(&path, LAB).unwrap();
+    let simulation = Simulation::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(simulation.unwrap().resources().count(), 3);
+
+    let error = Simulation::load(path.with_extension("json")).unwrap_err();
+    assert!(matches!(error, SimulationError::Io(_)));
+}
+
+#[test]
+fn test_invalid_simulations() {
+    let invalid = |text: &str| match Simulation::from_yaml(text) {
+        Err(SimulationError::Invalid(message)) => message,
+        other => panic!("expected an invalid simulation, got {other:?}"),
+    };
+    let message = invalid("resources: { \"GPIB0::1::INSTR\": { device: missing } }");
+    assert!(message.contains("missing"), "{message}");
+
+    let message = invalid("devices: { d: { properties: { p: { default: 5, max: 1 } } } }\nresources: {}");
+    assert!(message.contains("\"p\""), "{message}");
+
+    let message = invalid("devices: { d: { properties: { p: { default: 1, set: { q: \"P\" } } } } }\nresources: {}");
+    assert!(message.contains("{}"), "{message}");
+
+    assert!(matches!(Simulation::from_yaml("resources: { a: { devise: d } }"), Err(SimulationError::Parse(_))));
+    assert!(matches!(Simulation::from_toml("resources = 1"), Err(SimulationError::Parse(_))));
+}
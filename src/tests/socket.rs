@@ -0,0 +1,169 @@
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::attr;
+use crate::backend::socket::SocketBackend;
+use crate::ffi::*;
+use crate::{ResourceManager, TextOptions, VisaError};
+
+/// The packets answering a command, or `None` to close the connection.
+fn respond(command: &str) -> Option<Vec<&'static [u8]>> {
+    match command {
+        "*IDN?" => Some(vec![b"ACME,LXI-1,7,1.0\n"]),
+        "*STB?" => Some(vec![b"+16\n"]),
+        "SPLIT?" => Some(vec![b"1.2", b"5\n"]),
+        "TWO?" => Some(vec![b"1\n2\n"]),
+        "BYE" => None,
+        _ => Some(vec![]),
+    }
+}
+
+fn serve(stream: TcpStream) {
+    let mut writer = stream.try_clone().unwrap();
+    writer.set_nodelay(true).unwrap();
+    for line in BufReader::new(stream).lines() {
+        let Some(packets) = line.ok().as_deref().and_then(respond) else {
+            return;
+        };
+        for packet in packets {
+            writer.write_all(packet).unwrap();
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+/// Starts an instrument on a free local port and returns its resource name.
+fn instrument() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            thread::spawn(move || serve(stream));
+        }
+    });
+    format!("TCPIP0::127.0.0.1::{port}::SOCKET")
+}
+
+fn rm() -> ResourceManager {
+    ResourceManager::with_backend(Arc::new(SocketBackend::new())).unwrap()
+}
+
+fn visa_error(e: std::io::Error) -> ViStatus {
+    e.get_ref().and_then(|inner| inner.downcast_ref::<VisaError>()).unwrap().raw()
+}
+
+#[test]
+fn test_query() {
+    let name = instrument();
+    let rm = rm();
+    let mut session = rm.open(&name).unwrap();
+    assert_eq!(session.query("*IDN?").unwrap(), "ACME,LXI-1,7,1.0");
+
+    assert_eq!(session.get::<attr::RsrcName>().unwrap(), name);
+    assert_eq!(session.get::<attr::RsrcClass>().unwrap(), "SOCKET");
+    assert_eq!(session.get::<attr::TcpipAddr>().unwrap(), "127.0.0.1");
+    assert_eq!(session.get::<attr::TcpipHostname>().unwrap(), "127.0.0.1");
+    assert_eq!(name, format!("TCPIP0::127.0.0.1::{}::SOCKET", session.get::<attr::TcpipPort>().unwrap()));
+}
+
+#[test]
+fn test_termchar() {
+    let rm = rm();
+    let mut session = rm.open(&instrument()).unwrap();
+    session.set_text_options(TextOptions::new()).unwrap();
+    assert!(session.get::<attr::TermCharEn>().unwrap());
+    assert_eq!(session.query("SPLIT?").unwrap(), "1.25");
+
+    session.write_str("TWO?").unwrap();
+    assert_eq!(session.read_string().unwrap(), "1");
+    assert_eq!(session.read_string().unwrap(), "2");
+}
+
+#[test]
+fn test_timeout() {
+    let rm = rm();
+    let mut session = rm.open(&instrument()).unwrap();
+    session.set::<attr::TmoValue>(100).unwrap();
+    session.write_str("UNKNOWN?").unwrap();
+
+    let start = Instant::now();
+    assert_eq!(session.read_string().unwrap_err().kind(), ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    // SUPPRESS_END_EN makes reads wait for the whole count.
+    session.set::<attr::SuppressEndEn>(true).unwrap();
+    session.write_str("*IDN?").unwrap();
+    assert_eq!(session.read_string().unwrap_err().kind(), ErrorKind::TimedOut);
+}
+
+#[test]
+fn test_socket_options() {
+    let rm = rm();
+    let session = rm.open(&instrument()).unwrap();
+    assert!(session.get::<attr::TcpipNodelay>().unwrap());
+    assert!(!session.get::<attr::TcpipKeepalive>().unwrap());
+
+    session.set::<attr::TcpipNodelay>(false).unwrap();
+    session.set::<attr::TcpipKeepalive>(true).unwrap();
+    assert!(!session.get::<attr::TcpipNodelay>().unwrap());
+    assert!(session.get::<attr::TcpipKeepalive>().unwrap());
+}
+
+#[test]
+fn test_send_end_has_no_effect() {
+    let rm = rm();
+    let mut session = rm.open(&instrument()).unwrap();
+    assert!(session.get::<attr::SendEndEn>().unwrap());
+    session.set::<attr::SendEndEn>(false).unwrap();
+    assert!(!session.get::<attr::SendEndEn>().unwrap());
+    assert_eq!(session.query("*IDN?").unwrap(), "ACME,LXI-1,7,1.0");
+}
+
+#[test]
+fn test_read_stb() {
+    let rm = rm();
+    let session = rm.open(&instrument()).unwrap();
+    assert_eq!(session.read_stb().unwrap_err().raw(), VI_ERROR_NSUP_OPER);
+    session.set::<attr::IoProt>(VI_PROT_4882_STRS as u16).unwrap();
+    assert_eq!(session.read_stb().unwrap().bits(), 16);
+}
+
+#[test]
+fn test_open_errors() {
+    let rm = rm();
+    assert_eq!(rm.open("TCPIP0::127.0.0.1::INSTR").unwrap_err().raw(), VI_ERROR_RSRC_NFOUND);
+
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let closed = format!("TCPIP0::127.0.0.1::{port}::SOCKET");
+    assert_eq!(rm.open(&closed).unwrap_err().raw(), VI_ERROR_RSRC_NFOUND);
+    assert_eq!(rm.find("?*SOCKET").count(), 0);
+}
+
+#[test]
+fn test_connection_lost() {
+    let rm = rm();
+    let mut session = rm.open(&instrument()).unwrap();
+    session.write_str("BYE").unwrap();
+    assert_eq!(visa_error(session.read_string().unwrap_err()), VI_ERROR_CONN_LOST);
+}
+
+#[test]
+fn test_locks() {
+    let name = instrument();
+    let rm = rm();
+    let mut first = rm.open(&name).unwrap();
+    let mut second = rm.open(&name).unwrap();
+    let short = Duration::from_millis(10);
+
+    let mut guard = first.lock_exclusive(short).unwrap();
+    assert_eq!(visa_error(second.write_str("*IDN?").unwrap_err()), VI_ERROR_RSRC_LOCKED);
+    assert_eq!(second.lock_exclusive(short).unwrap_err().raw(), VI_ERROR_TMO);
+    assert_eq!(guard.query("*IDN?").unwrap(), "ACME,LXI-1,7,1.0");
+
+    guard.unlock().unwrap();
+    assert_eq!(second.query("*IDN?").unwrap(), "ACME,LXI-1,7,1.0");
+}
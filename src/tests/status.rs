@@ -0,0 +1,73 @@
+use std::io;
+
+use crate::ffi::*;
+use crate::{Completion, Status, VisaError};
+
+/// Every `VI_SUCCESS*`, `VI_WARN_*` and `VI_ERROR_*` constant in the bindings.
+fn status_constants() -> Vec<(&'static str, ViStatus)> {
+    include_str!("../ffi/generated/types_64.rs")
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("pub const ")?;
+            let (name, rest) = rest.split_once(": ")?;
+            if !(name.starts_with("VI_SUCCESS") || name.starts_with("VI_WARN_") || name.starts_with("VI_ERROR_")) {
+                return None;
+            }
+            let value = rest.split_once(" = ")?.1.trim_end_matches(';');
+            Some((name, value.parse::<i64>().unwrap() as ViStatus))
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_status_code_has_a_name_and_description() {
+    let constants = status_constants();
+    assert!(constants.len() > 100, "only found {} status codes", constants.len());
+
+    for (name, value) in constants {
+        let status = Status::from_raw(value);
+        assert!(status.name().is_some(), "{} has no name", name);
+        assert!(status.description().is_some(), "{} has no description", name);
+        assert_eq!(status.name().unwrap().starts_with("VI_ERROR_"), name.starts_with("VI_ERROR_"));
+    }
+}
+
+#[test]
+fn test_status_splits_errors_from_completions() {
+    let error = Status::from_raw(VI_ERROR_TMO).into_result().unwrap_err();
+    assert_eq!(error.raw(), VI_ERROR_TMO);
+    assert_eq!(error.name(), Some("VI_ERROR_TMO"));
+    assert_eq!(
+        error.to_string(),
+        "VI_ERROR_TMO (0xBFFF0015): Timeout expired before operation completed."
+    );
+
+    let completion = Status::from(VI_SUCCESS_MAX_CNT as ViStatus).into_result().unwrap();
+    assert_eq!(completion.name(), Some("VI_SUCCESS_MAX_CNT"));
+    assert!(!completion.is_warning());
+    assert!(!Completion::SUCCESS.is_warning());
+}
+
+#[test]
+fn test_warnings_and_unknown_codes() {
+    let warning = Status::from_raw(VI_WARN_CONFIG_NLOADED as ViStatus).into_result().unwrap();
+    assert!(warning.is_warning());
+
+    let unknown = Status::from_raw(0x3FFF_7FFF).into_result().unwrap();
+    assert!(unknown.is_warning());
+    assert_eq!(unknown.to_string(), "Unknown completion code 0x3FFF7FFF");
+
+    let error = Status::from_raw(VI_ERROR_INV_SESSION).into_result().unwrap_err();
+    assert_eq!(error.name(), Some("VI_ERROR_INV_OBJECT"));
+}
+
+#[test]
+fn test_errors_convert_to_io_errors() {
+    let timeout = io::Error::from(Status::from_raw(VI_ERROR_TMO).into_result().unwrap_err());
+    assert_eq!(timeout.kind(), io::ErrorKind::TimedOut);
+    let inner = timeout.get_ref().unwrap().downcast_ref::<VisaError>().unwrap();
+    assert_eq!(inner.raw(), VI_ERROR_TMO);
+
+    let other = io::Error::from(Status::from_raw(VI_ERROR_IO).into_result().unwrap_err());
+    assert_eq!(other.kind(), io::ErrorKind::Other);
+}
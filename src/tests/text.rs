@@ -0,0 +1,43 @@
+use std::io;
+
+use crate::{Encoding, TextOptions};
+
+#[test]
+fn test_encodings() {
+    assert_eq!(Encoding::Utf8.encode("µA").unwrap(), "µA".as_bytes());
+    assert_eq!(Encoding::Latin1.encode("µA").unwrap(), b"\xB5A");
+    assert_eq!(Encoding::Ascii.encode("µA").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(Encoding::Latin1.encode("€").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    assert_eq!(Encoding::Latin1.decode(b"\xB5A").unwrap(), "µA");
+    assert_eq!(Encoding::Utf8.decode(b"\xB5A").unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(Encoding::Ascii.decode(b"\xB5A").unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_terminations() {
+    let options = TextOptions::new();
+    assert_eq!(options.encode_command("SYST:ERR?").unwrap(), b"SYST:ERR?\n");
+    assert_eq!(options.decode_response(b"+0,\"No error\"\n").unwrap(), "+0,\"No error\"");
+    // Only one termination is removed, and only at the end.
+    assert_eq!(options.decode_response(b"1\n\n").unwrap(), "1\n");
+    assert_eq!(options.decode_response(b"1").unwrap(), "1");
+    assert_eq!(options.term_char(), Some(b'\n'));
+
+    let options = TextOptions::new().write_termination("\r\n").read_termination("\r\n");
+    assert_eq!(options.encode_command("*RST").unwrap(), b"*RST\r\n");
+    assert_eq!(options.decode_response(b" 1.5 \r\n").unwrap(), " 1.5 ");
+    assert_eq!(options.term_char(), None);
+
+    let options = options.trim(true);
+    assert_eq!(options.decode_response(b" 1.5 \r\n").unwrap(), "1.5");
+    assert_eq!(options.decode_response(b" 1.5 \n").unwrap(), "1.5");
+}
+
+#[test]
+fn test_latin1_round_trip() {
+    let options = TextOptions::new().encoding(Encoding::Latin1);
+    let bytes = options.encode_command("25 °C").unwrap();
+    assert_eq!(bytes, b"25 \xB0C\n");
+    assert_eq!(options.decode_response(&bytes).unwrap(), "25 °C");
+}
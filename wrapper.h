@@ -1,9 +1,16 @@
-#ifdef _WIN32
-    #include "C:\\Program Files (x86)\\IVI Foundation\\VISA\\WinNT\\Include\\visa.h" // Windows path
-#elif __APPLE__
-    #include "/Library/Frameworks/VISA.framework/Headers/visa.h" // macOS path
-#elif __linux__
-    #include "/usr/include/visa.h" // Linux path
-#else
-    #error "Unsupported platform"
+/*
+ * Pin the 64-bit integer types to `long long` before visa.h sees them, so the
+ * generated bindings do not depend on how the host C library spells them.
+ */
+#include <limits.h>
+#if !defined(_VI_INT64_UINT64_DEFINED)
+    typedef unsigned long long ViUInt64;
+    typedef signed long long ViInt64;
+    #define _VI_INT64_UINT64_DEFINED
+    #if defined(_WIN64) || (defined(LONG_MAX) && (LONG_MAX > 0x7FFFFFFFL))
+        #define _VISA_ENV_IS_64_BIT
+    #endif
 #endif
+
+/* The include directory is located by build.rs, see build/discover.rs. */
+#include <visa.h>